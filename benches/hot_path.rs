@@ -0,0 +1,87 @@
+//! Benchmark of the iterative hot path, i.e. the wet-bulb and dew point temperatures,
+//! over a synthetic year of hourly weather data, with the mean number of iterations from the initial guesses
+//! of Stull (2011) for the wet-bulb temperature and of the Magnus formula for the dew point.
+//!
+//! Run with `cargo bench --bench hot_path`, and with `--features rayon` for the parallel batch evaluation.
+use psychroid::batch::{self, Output};
+use psychroid::{ModelOptions, MoistAir, SolverReport, UnitSystem};
+use std::f64::consts::PI;
+use std::hint::black_box;
+use std::time::Instant;
+
+const HOURS: usize = 8760;
+const RUNS: usize = 20;
+
+/// Hourly states with annual and daily cycles of the temperature and the relative humidity,
+/// from winter frost to humid summer conditions
+fn hourly_states() -> Vec<MoistAir> {
+    (0..HOURS)
+        .map(|hour| {
+            let year = 2.0 * PI * hour as f64 / HOURS as f64;
+            let day = 2.0 * PI * (hour % 24) as f64 / 24.0;
+            let t_dry_bulb = 12.0 - 15.0 * year.cos() - 5.0 * day.cos();
+            let relative_humidity = 0.6 + 0.25 * day.cos() + 0.1 * year.sin();
+            MoistAir::from_t_dry_bulb_relative_humidity(
+                t_dry_bulb,
+                relative_humidity,
+                101325.0,
+                UnitSystem::SI,
+            )
+            .unwrap()
+        })
+        .collect()
+}
+
+/// Prints the fastest of the runs in nanoseconds per call
+fn bench(name: &str, states: &[MoistAir], f: impl Fn(&MoistAir) -> f64) {
+    let mut best = f64::INFINITY;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        for state in states {
+            black_box(f(black_box(state)));
+        }
+        let elapsed = start.elapsed().as_secs_f64() * 1.0E+09 / states.len() as f64;
+        best = best.min(elapsed);
+    }
+    println!("{name:<12} {best:>8.1} ns/call");
+}
+
+/// Prints the mean number of iterations of the solution over the states
+fn iterations(name: &str, states: &[MoistAir], f: impl Fn(&MoistAir) -> SolverReport) {
+    let total: usize = states.iter().map(|state| f(state).iterations).sum();
+    let mean = total as f64 / states.len() as f64;
+    println!("{name:<12} {mean:>8.2} iterations/call");
+}
+
+fn main() {
+    let states = hourly_states();
+    bench("t_wet_bulb", &states, |state| state.t_wet_bulb().unwrap());
+    bench("t_dew_point", &states, |state| state.t_dew_point().unwrap());
+    iterations("t_wet_bulb", &states, |state| {
+        state.t_wet_bulb_with_report().unwrap().1
+    });
+    iterations("t_dew_point", &states, |state| {
+        state.t_dew_point_with_report().unwrap().1
+    });
+
+    // batch evaluation of the wet-bulb temperature over the structure-of-arrays inputs
+    let t_dry_bulb: Vec<f64> = states.iter().map(|state| state.t_dry_bulb()).collect();
+    let humidity_ratio: Vec<f64> = states.iter().map(|state| state.humidity_ratio()).collect();
+    let mut t_wet_bulb = vec![0.0; states.len()];
+    let mut best = f64::INFINITY;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        let errors = batch::from_t_dry_bulb_humidity_ratio(
+            &t_dry_bulb,
+            &humidity_ratio,
+            &[101325.0],
+            UnitSystem::SI,
+            &ModelOptions::default(),
+            &mut [(Output::TWetBulb, &mut t_wet_bulb)],
+        );
+        let elapsed = start.elapsed().as_secs_f64() * 1.0E+09 / states.len() as f64;
+        assert!(errors.is_empty());
+        best = best.min(elapsed);
+    }
+    println!("{:<12} {best:>8.1} ns/sample", "batch");
+}
@@ -1,3 +1,5 @@
+use crate::float::Float;
+
 /// Calculates the atmosphere temperature at a given altitude according to
 /// U.S. Standard Atmosphere 1976, Eq (23)
 ///
@@ -8,27 +10,32 @@
 /// * Temperature \[°C\]
 /// * Returns NaN if altitude is out of valid range (0-84852m)
 ///
+/// Generic over `f32` and `f64`, see [`Float`].
+///
 /// # Example
 /// ```
 /// use psychroid::atmosphere_temperature;
 ///
-/// let temp = atmosphere_temperature(1000.0);
+/// let temp: f64 = atmosphere_temperature(1000.0);
 /// assert!((temp - 8.5).abs() < 0.1); // Should be around 8.5°C at 1000m
+/// let temp = atmosphere_temperature(1000.0f32);
+/// assert!((temp - 8.5).abs() < 0.1);
 /// ```
-pub fn atmosphere_temperature(altitude: f64) -> f64 {
-    if !(0.0..=84852.0).contains(&altitude) {
-        return f64::NAN;
+pub fn atmosphere_temperature<F: Float>(altitude: F) -> F {
+    let c = F::of;
+    if !(c(0.0)..=c(84852.0)).contains(&altitude) {
+        return c(f64::NAN);
     }
 
     match altitude {
-        h if h <= 11000.0 => 15.0 - 0.0065 * h,
-        h if h <= 20000.0 => -56.5,
-        h if h <= 32000.0 => -56.5 + 0.001 * (h - 20000.0),
-        h if h <= 47000.0 => -44.5 + 0.0028 * (h - 32000.0),
-        h if h <= 51000.0 => -2.5,
-        h if h <= 71000.0 => -2.5 - 0.0028 * (h - 51000.0),
-        h if h <= 84852.0 => -58.5,
-        _ => f64::NAN,
+        h if h <= c(11000.0) => c(15.0) - c(0.0065) * h,
+        h if h <= c(20000.0) => c(-56.5),
+        h if h <= c(32000.0) => c(-56.5) + c(0.001) * (h - c(20000.0)),
+        h if h <= c(47000.0) => c(-44.5) + c(0.0028) * (h - c(32000.0)),
+        h if h <= c(51000.0) => c(-2.5),
+        h if h <= c(71000.0) => c(-2.5) - c(0.0028) * (h - c(51000.0)),
+        h if h <= c(84852.0) => c(-58.5),
+        _ => c(f64::NAN),
     }
 }
 
@@ -46,82 +53,97 @@ const GAS_CONSTANT: f64 = 8.31447; // Universal gas constant [J/(mol·K)]
 /// # Returns
 /// * Pressure \[Pa\]
 /// * Returns NaN if altitude is out of valid range (0-84852m)
-pub fn atmosphere_pressure(altitude: f64) -> f64 {
-    if !(0.0..=84852.0).contains(&altitude) {
-        return f64::NAN;
+///
+/// Generic over `f32` and `f64`, see [`Float`].
+pub fn atmosphere_pressure<F: Float>(altitude: F) -> F {
+    let c = F::of;
+    if !(c(0.0)..=c(84852.0)).contains(&altitude) {
+        return c(f64::NAN);
     }
 
     match altitude {
-        h if h <= 11000.0 => p0(h),
-        h if h <= 20000.0 => p1(h),
-        h if h <= 32000.0 => p2(h),
-        h if h <= 47000.0 => p3(h),
-        h if h <= 51000.0 => p4(h),
-        h if h <= 71000.0 => p5(h),
-        h if h <= 84852.0 => p6(h),
-        _ => f64::NAN,
+        h if h <= c(11000.0) => p0(h),
+        h if h <= c(20000.0) => p1(h),
+        h if h <= c(32000.0) => p2(h),
+        h if h <= c(47000.0) => p3(h),
+        h if h <= c(51000.0) => p4(h),
+        h if h <= c(71000.0) => p5(h),
+        h if h <= c(84852.0) => p6(h),
+        _ => c(f64::NAN),
     }
 }
 
-fn p0(altitude: f64) -> f64 {
+fn p0<F: Float>(altitude: F) -> F {
+    let c = F::of;
     let l_mb = -0.0065;
     let t_mb = 15.0 + 273.15;
     let p_mb = 101325.0;
     let h_b = 0.0;
-    p_mb * (t_mb / (t_mb + l_mb * (altitude - h_b)))
-        .powf(GRAVITY * MOLAR_MASS / (GAS_CONSTANT * l_mb))
+    c(p_mb)
+        * (c(t_mb) / (c(t_mb) + c(l_mb) * (altitude - c(h_b))))
+            .powf(c(GRAVITY * MOLAR_MASS / (GAS_CONSTANT * l_mb)))
 }
 
-fn p1(altitude: f64) -> f64 {
+fn p1<F: Float>(altitude: F) -> F {
+    let c = F::of;
     // let l_mb = 0.0;
     let t_mb = -56.5 + 273.15;
     let h_b = 11000.0;
-    let p_mb = p0(h_b);
-    p_mb * (-GRAVITY * MOLAR_MASS * (altitude - h_b) / (GAS_CONSTANT * t_mb)).exp()
+    let p_mb: f64 = p0(h_b);
+    c(p_mb) * (c(-GRAVITY * MOLAR_MASS) * (altitude - c(h_b)) / c(GAS_CONSTANT * t_mb)).exp()
 }
 
-fn p2(altitude: f64) -> f64 {
+fn p2<F: Float>(altitude: F) -> F {
+    let c = F::of;
     let l_mb = 0.001;
     let t_mb = -56.5 + 273.15;
     let h_b = 20000.0;
-    let p_mb = p1(h_b);
-    p_mb * (t_mb / (t_mb + l_mb * (altitude - h_b)))
-        .powf(GRAVITY * MOLAR_MASS / (GAS_CONSTANT * l_mb))
+    let p_mb: f64 = p1(h_b);
+    c(p_mb)
+        * (c(t_mb) / (c(t_mb) + c(l_mb) * (altitude - c(h_b))))
+            .powf(c(GRAVITY * MOLAR_MASS / (GAS_CONSTANT * l_mb)))
 }
 
-fn p3(altitude: f64) -> f64 {
+fn p3<F: Float>(altitude: F) -> F {
+    let c = F::of;
     let l_mb = 0.0028;
     let t_mb = -44.5 + 273.15;
     let h_b = 32000.0;
-    let p_mb = p2(h_b);
-    p_mb * (t_mb / (t_mb + l_mb * (altitude - h_b)))
-        .powf(GRAVITY * MOLAR_MASS / (GAS_CONSTANT * l_mb))
+    let p_mb: f64 = p2(h_b);
+    c(p_mb)
+        * (c(t_mb) / (c(t_mb) + c(l_mb) * (altitude - c(h_b))))
+            .powf(c(GRAVITY * MOLAR_MASS / (GAS_CONSTANT * l_mb)))
 }
 
-fn p4(altitude: f64) -> f64 {
+fn p4<F: Float>(altitude: F) -> F {
+    let c = F::of;
     // let l_mb = 0.0;
     let t_mb = -2.5 + 273.15;
     let h_b = 47000.0;
-    let p_mb = p3(h_b);
-    p_mb * (-GRAVITY * MOLAR_MASS * (altitude - h_b) / (GAS_CONSTANT * t_mb)).exp()
+    let p_mb: f64 = p3(h_b);
+    c(p_mb) * (c(-GRAVITY * MOLAR_MASS) * (altitude - c(h_b)) / c(GAS_CONSTANT * t_mb)).exp()
 }
 
-fn p5(altitude: f64) -> f64 {
+fn p5<F: Float>(altitude: F) -> F {
+    let c = F::of;
     let l_mb = -0.0028;
     let t_mb = -2.5 + 273.15;
     let h_b = 51000.0;
-    let p_mb = p4(h_b);
-    p_mb * (t_mb / (t_mb + l_mb * (altitude - h_b)))
-        .powf(GRAVITY * MOLAR_MASS / (GAS_CONSTANT * l_mb))
+    let p_mb: f64 = p4(h_b);
+    c(p_mb)
+        * (c(t_mb) / (c(t_mb) + c(l_mb) * (altitude - c(h_b))))
+            .powf(c(GRAVITY * MOLAR_MASS / (GAS_CONSTANT * l_mb)))
 }
 
-fn p6(altitude: f64) -> f64 {
+fn p6<F: Float>(altitude: F) -> F {
+    let c = F::of;
     let l_mb = -0.0020;
     let t_mb = -58.5 + 273.15;
     let h_b = 71000.0;
-    let p_mb = p5(h_b);
-    p_mb * (t_mb / (t_mb + l_mb * (altitude - h_b)))
-        .powf(GRAVITY * MOLAR_MASS / (GAS_CONSTANT * l_mb))
+    let p_mb: f64 = p5(h_b);
+    c(p_mb)
+        * (c(t_mb) / (c(t_mb) + c(l_mb) * (altitude - c(h_b))))
+            .powf(c(GRAVITY * MOLAR_MASS / (GAS_CONSTANT * l_mb)))
 }
 
 #[cfg(test)]
@@ -144,8 +166,8 @@ mod tests {
 
     #[test]
     fn test_atmosphere_temperature_out_of_range() {
-        assert!(atmosphere_temperature(-1.0).is_nan());
-        assert!(atmosphere_temperature(84853.0).is_nan());
+        assert!(atmosphere_temperature(-1.0f64).is_nan());
+        assert!(atmosphere_temperature(84853.0f64).is_nan());
     }
 
     #[test]
@@ -154,4 +176,13 @@ mod tests {
         assert_abs_diff_eq!(atmosphere_pressure(1000.0), 89.875E+03, epsilon = 1.0);
         assert_abs_diff_eq!(atmosphere_pressure(11000.0), 22.632E+03, epsilon = 1.0);
     }
+
+    #[test]
+    fn test_single_precision() {
+        for altitude in [0.0, 1000.0, 11000.0, 25000.0, 50000.0, 80000.0] {
+            let pressure = atmosphere_pressure(altitude);
+            let pressure_f32 = atmosphere_pressure(altitude as f32) as f64;
+            assert!((pressure_f32 / pressure - 1.0).abs() < 1.0E-05);
+        }
+    }
 }
@@ -0,0 +1,389 @@
+//! Batch evaluation of moist air properties over structure-of-arrays slices, e.g. trend logs
+//! with millions of samples.
+//!
+//! Each function takes the input slices of the same length, builds the state of each sample,
+//! and fills the output slices of the requested properties. A sample which fails, e.g. out of
+//! the valid range, is reported with its index in the returned list of errors, and its outputs
+//! are set to NaN, while the other samples are evaluated as usual.
+//!
+//! The total pressure is given either per sample or as a slice of length 1 for all the samples.
+//!
+//! With the `rayon` cargo feature, the samples are evaluated in parallel across cores.
+//! The results, including the order of the errors, are the same as those of the serial evaluation.
+//!
+//! # Example
+//! ```
+//! use psychroid::batch::{self, Output};
+//! use psychroid::{ModelOptions, UnitSystem};
+//!
+//! let t_dry_bulb = [20.0, 25.0, 500.0];
+//! let relative_humidity = [0.5, 0.6, 0.5];
+//! let mut t_wet_bulb = [0.0; 3];
+//! let mut humidity_ratio = [0.0; 3];
+//! let errors = batch::from_t_dry_bulb_relative_humidity(
+//!     &t_dry_bulb,
+//!     &relative_humidity,
+//!     &[101325.0],
+//!     UnitSystem::SI,
+//!     &ModelOptions::default(),
+//!     &mut [
+//!         (Output::TWetBulb, &mut t_wet_bulb),
+//!         (Output::HumidityRatio, &mut humidity_ratio),
+//!     ],
+//! );
+//! assert!(t_wet_bulb[0] < 20.0 && humidity_ratio[1] > humidity_ratio[0]);
+//! // the dry-bulb temperature of the last sample is out of range
+//! assert_eq!(errors.len(), 1);
+//! assert_eq!(errors[0].0, 2);
+//! assert!(t_wet_bulb[2].is_nan());
+//! ```
+use crate::common::UnitSystem;
+use crate::error::PsychroidError;
+use crate::functions::humidity_ratio_from_relative_humidity;
+use crate::moist_air::{ModelOptions, MoistAir, Property};
+use crate::saturated_water_vapor::check_range_t_dry_bulb;
+use alloc::vec::Vec;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Number of samples evaluated together, i.e. the unit of work distributed across cores
+const CHUNK_SIZE: usize = 1024;
+
+/// Property of moist air to be evaluated by the batch functions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Output {
+    /// Dry-bulb temperature, see [`MoistAir::t_dry_bulb`]
+    TDryBulb,
+    /// Humidity ratio, see [`MoistAir::humidity_ratio`]
+    HumidityRatio,
+    /// Relative humidity, see [`MoistAir::relative_humidity`]
+    RelativeHumidity,
+    /// Wet-bulb temperature, see [`MoistAir::t_wet_bulb`]
+    TWetBulb,
+    /// Dew point temperature, see [`MoistAir::t_dew_point`]
+    TDewPoint,
+    /// Specific enthalpy, see [`MoistAir::specific_enthalpy`]
+    SpecificEnthalpy,
+    /// Specific volume, see [`MoistAir::specific_volume`]
+    SpecificVolume,
+    /// Density, see [`MoistAir::density`]
+    Density,
+    /// Partial pressure of water vapor, see [`MoistAir::partial_water_vapor_pressure`]
+    PartialWaterVaporPressure,
+    /// Degree of saturation, see [`MoistAir::degree_of_saturation`]
+    DegreeOfSaturation,
+    /// Specific heat, see [`MoistAir::specific_heat`]
+    SpecificHeat,
+}
+
+impl Output {
+    /// Returns the name of the property, e.g. "dry-bulb temperature"
+    pub fn name(&self) -> &'static str {
+        match self {
+            Output::TDryBulb => "dry-bulb temperature",
+            Output::HumidityRatio => "humidity ratio",
+            Output::RelativeHumidity => "relative humidity",
+            Output::TWetBulb => "wet-bulb temperature",
+            Output::TDewPoint => "dew point temperature",
+            Output::SpecificEnthalpy => "specific enthalpy",
+            Output::SpecificVolume => "specific volume",
+            Output::Density => "density",
+            Output::PartialWaterVaporPressure => "partial pressure of water vapor",
+            Output::DegreeOfSaturation => "degree of saturation",
+            Output::SpecificHeat => "specific heat",
+        }
+    }
+
+    /// Evaluates the property of the state
+    pub fn evaluate(&self, moist_air: &MoistAir) -> Result<f64, PsychroidError> {
+        match self {
+            Output::TDryBulb => Ok(moist_air.t_dry_bulb()),
+            Output::HumidityRatio => Ok(moist_air.humidity_ratio()),
+            Output::RelativeHumidity => moist_air.relative_humidity(),
+            Output::TWetBulb => moist_air.t_wet_bulb(),
+            Output::TDewPoint => moist_air.t_dew_point(),
+            Output::SpecificEnthalpy => Ok(moist_air.specific_enthalpy()),
+            Output::SpecificVolume => Ok(moist_air.specific_volume()),
+            Output::Density => Ok(moist_air.density()),
+            Output::PartialWaterVaporPressure => Ok(moist_air.partial_water_vapor_pressure()),
+            Output::DegreeOfSaturation => moist_air.degree_of_saturation(),
+            Output::SpecificHeat => Ok(moist_air.specific_heat()),
+        }
+    }
+}
+
+/// The output of the same property, e.g. of an input of [`MoistAir::gradient`]
+impl From<Property> for Output {
+    fn from(property: Property) -> Self {
+        match property {
+            Property::TDryBulb(_) => Output::TDryBulb,
+            Property::HumidityRatio(_) => Output::HumidityRatio,
+            Property::RelativeHumidity(_) => Output::RelativeHumidity,
+            Property::TWetBulb(_) => Output::TWetBulb,
+            Property::TDewPoint(_) => Output::TDewPoint,
+            Property::SpecificEnthalpy(_) => Output::SpecificEnthalpy,
+            Property::SpecificVolume(_) => Output::SpecificVolume,
+        }
+    }
+}
+
+/// Evaluates the properties of the states given by dry-bulb temperature and relative humidity
+///
+/// # Arguments
+/// * `t_dry_bulb` - Dry-bulb temperatures in \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
+/// * `relative_humidity` - Relative humidities, in the range of 0 to 1
+/// * `pressure` - Total pressures in \\(\\mathrm{Pa}\\) (SI) or \\(\\mathrm{Psi}\\) (IP), per sample or of length 1
+/// * `unit` - Unit system
+/// * `options` - Model options
+/// * `outputs` - Pairs of the property and the slice to be filled with its values
+///
+/// # Returns
+/// The indices and errors of the failed samples, in ascending order of the index
+///
+/// # Panics
+/// If the lengths of the slices do not match
+pub fn from_t_dry_bulb_relative_humidity(
+    t_dry_bulb: &[f64],
+    relative_humidity: &[f64],
+    pressure: &[f64],
+    unit: UnitSystem,
+    options: &ModelOptions,
+    outputs: &mut [(Output, &mut [f64])],
+) -> Vec<(usize, PsychroidError)> {
+    check_lengths(
+        t_dry_bulb.len(),
+        &[relative_humidity.len()],
+        pressure,
+        outputs,
+    );
+    evaluate(t_dry_bulb.len(), outputs, |i| {
+        let pressure = pressure_of(pressure, i);
+        check_range_t_dry_bulb(t_dry_bulb[i], unit)?;
+        let humidity_ratio = humidity_ratio_from_relative_humidity(
+            t_dry_bulb[i],
+            relative_humidity[i],
+            pressure,
+            unit,
+            options,
+        )?;
+        MoistAir::from_t_dry_bulb_humidity_ratio_with_options(
+            t_dry_bulb[i],
+            humidity_ratio,
+            pressure,
+            unit,
+            *options,
+        )
+    })
+}
+
+/// Evaluates the properties of the states given by dry-bulb temperature and humidity ratio
+///
+/// # Arguments
+/// * `t_dry_bulb` - Dry-bulb temperatures in \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
+/// * `humidity_ratio` - Humidity ratios in \\( \\mathrm{kg_w/kg_{da}} \\) (SI) or \\( \\mathrm{lb_w/lb_{da}} \\) (IP)
+/// * `pressure` - Total pressures in \\(\\mathrm{Pa}\\) (SI) or \\(\\mathrm{Psi}\\) (IP), per sample or of length 1
+/// * `unit` - Unit system
+/// * `options` - Model options
+/// * `outputs` - Pairs of the property and the slice to be filled with its values
+///
+/// # Returns
+/// The indices and errors of the failed samples, in ascending order of the index
+///
+/// # Panics
+/// If the lengths of the slices do not match
+pub fn from_t_dry_bulb_humidity_ratio(
+    t_dry_bulb: &[f64],
+    humidity_ratio: &[f64],
+    pressure: &[f64],
+    unit: UnitSystem,
+    options: &ModelOptions,
+    outputs: &mut [(Output, &mut [f64])],
+) -> Vec<(usize, PsychroidError)> {
+    check_lengths(t_dry_bulb.len(), &[humidity_ratio.len()], pressure, outputs);
+    evaluate(t_dry_bulb.len(), outputs, |i| {
+        MoistAir::from_t_dry_bulb_humidity_ratio_with_options(
+            t_dry_bulb[i],
+            humidity_ratio[i],
+            pressure_of(pressure, i),
+            unit,
+            *options,
+        )
+    })
+}
+
+/// Returns the pressure of the sample, broadcasting a single pressure to all the samples
+fn pressure_of(pressure: &[f64], index: usize) -> f64 {
+    match pressure.len() {
+        1 => pressure[0],
+        _ => pressure[index],
+    }
+}
+
+fn check_lengths(len: usize, inputs: &[usize], pressure: &[f64], outputs: &[(Output, &mut [f64])]) {
+    for input in inputs {
+        assert_eq!(*input, len, "length of the input slices");
+    }
+    assert!(
+        pressure.len() == len || pressure.len() == 1,
+        "length of the pressure slice must be {len} or 1, not {}",
+        pressure.len()
+    );
+    for (output, values) in outputs {
+        assert_eq!(
+            values.len(),
+            len,
+            "length of the output slice of {output:?}"
+        );
+    }
+}
+
+/// Evaluates the outputs of `len` samples, of which the state is built by `state`, chunk by chunk
+fn evaluate(
+    len: usize,
+    outputs: &mut [(Output, &mut [f64])],
+    state: impl Fn(usize) -> Result<MoistAir, PsychroidError> + Sync,
+) -> Vec<(usize, PsychroidError)> {
+    // each chunk holds the pieces of all the output slices for its samples
+    let mut chunks: Vec<Vec<(Output, &mut [f64])>> = (0..len.div_ceil(CHUNK_SIZE))
+        .map(|_| Vec::with_capacity(outputs.len()))
+        .collect();
+    for (output, values) in outputs.iter_mut() {
+        for (chunk, piece) in chunks.iter_mut().zip(values.chunks_mut(CHUNK_SIZE)) {
+            chunk.push((*output, piece));
+        }
+    }
+    let evaluate_chunk = |(index, mut chunk): (usize, Vec<(Output, &mut [f64])>)| {
+        let start = index * CHUNK_SIZE;
+        let end = (start + CHUNK_SIZE).min(len);
+        let mut errors = Vec::new();
+        for i in start..end {
+            let mut error = None;
+            match state(i) {
+                Ok(moist_air) => {
+                    for (output, values) in chunk.iter_mut() {
+                        values[i - start] = match output.evaluate(&moist_air) {
+                            Ok(value) => value,
+                            Err(e) => {
+                                error.get_or_insert(e);
+                                f64::NAN
+                            }
+                        };
+                    }
+                }
+                Err(e) => {
+                    for (_, values) in chunk.iter_mut() {
+                        values[i - start] = f64::NAN;
+                    }
+                    error = Some(e);
+                }
+            }
+            if let Some(e) = error {
+                errors.push((i, e));
+            }
+        }
+        errors
+    };
+    #[cfg(feature = "rayon")]
+    let errors = chunks
+        .into_par_iter()
+        .enumerate()
+        .flat_map_iter(evaluate_chunk)
+        .collect();
+    #[cfg(not(feature = "rayon"))]
+    let errors = chunks
+        .into_iter()
+        .enumerate()
+        .flat_map(evaluate_chunk)
+        .collect();
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_batch() {
+        let options = ModelOptions::default();
+        let unit = UnitSystem::SI;
+        // more samples than a chunk, with invalid samples
+        let len = 2 * CHUNK_SIZE + 10;
+        let t_dry_bulb: Vec<f64> = (0..len).map(|i| -20.0 + 0.02 * i as f64).collect();
+        let mut relative_humidity: Vec<f64> = (0..len).map(|i| (i % 100) as f64 / 100.0).collect();
+        relative_humidity[CHUNK_SIZE + 1] = 1.5;
+        relative_humidity[len - 1] = f64::NAN;
+        let pressure: Vec<f64> = (0..len).map(|i| 101325.0 - i as f64).collect();
+        let mut t_wet_bulb = vec![0.0; len];
+        let mut specific_enthalpy = vec![0.0; len];
+        let errors = from_t_dry_bulb_relative_humidity(
+            &t_dry_bulb,
+            &relative_humidity,
+            &pressure,
+            unit,
+            &options,
+            &mut [
+                (Output::TWetBulb, &mut t_wet_bulb),
+                (Output::SpecificEnthalpy, &mut specific_enthalpy),
+            ],
+        );
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].0, CHUNK_SIZE + 1);
+        assert_eq!(errors[0].1, PsychroidError::InvalidRelativeHumidity(1.5));
+        assert_eq!(errors[1].0, len - 1);
+        assert!(matches!(errors[1].1, PsychroidError::NonFiniteInput { .. }));
+        for i in 0..len {
+            if errors.iter().any(|(index, _)| *index == i) {
+                assert!(t_wet_bulb[i].is_nan() && specific_enthalpy[i].is_nan());
+                continue;
+            }
+            let moist_air = MoistAir::from_t_dry_bulb_relative_humidity(
+                t_dry_bulb[i],
+                relative_humidity[i],
+                pressure[i],
+                unit,
+            )
+            .unwrap();
+            assert_eq!(t_wet_bulb[i], moist_air.t_wet_bulb().unwrap());
+            assert_eq!(specific_enthalpy[i], moist_air.specific_enthalpy());
+        }
+
+        // a single pressure for all the samples, and an error of an output
+        let mut t_dew_point = [0.0; 2];
+        let mut humidity_ratio = [0.0; 2];
+        let errors = from_t_dry_bulb_humidity_ratio(
+            &[30.0, 30.0],
+            &[0.01, 0.0],
+            &[101325.0],
+            unit,
+            &options,
+            &mut [
+                (Output::TDewPoint, &mut t_dew_point),
+                (Output::HumidityRatio, &mut humidity_ratio),
+            ],
+        );
+        assert!(errors.is_empty());
+        assert_relative_eq!(t_dew_point[0], 14.0, epsilon = 0.1);
+        // no dew point of dry air
+        assert!(t_dew_point[1].is_nan());
+        assert_eq!(humidity_ratio, [0.01, 0.0]);
+
+        // empty slices
+        let errors = from_t_dry_bulb_humidity_ratio(&[], &[], &[101325.0], unit, &options, &mut []);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_batch_length_mismatch() {
+        let mut values = [0.0; 2];
+        from_t_dry_bulb_humidity_ratio(
+            &[20.0, 25.0],
+            &[0.01],
+            &[101325.0],
+            UnitSystem::SI,
+            &ModelOptions::default(),
+            &mut [(Output::HumidityRatio, &mut values)],
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::common::UnitSystem;
 use crate::moist_air::MoistAir;
+use alloc::vec::Vec;
 
 /// Generates data points for constant relative humidity line on psychrometric chart
 /// <script src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
@@ -10,19 +11,22 @@ use crate::moist_air::MoistAir;
 /// * `unit` - Unit system (SI or IP)
 ///
 /// # Returns
-/// Tuple of vectors (temperatures, humidity ratios):
-/// - temperatures: temperature array in \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
-/// - humidity ratios: corresponding humidity ratio array in \\( \\mathrm{kg_w / kg_{da}} \\) (SI) or \\( \\mathrm{lb_w / lb_{da}} \\) (IP)
+/// Vector of points (temperature, humidity ratio):
+/// - temperature in \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
+/// - humidity ratio in \\( \\mathrm{kg_w / kg_{da}} \\) (SI) or \\( \\mathrm{lb_w / lb_{da}} \\) (IP)
 ///
 /// # Example
 /// ```
 /// use psychroid::{chart, UnitSystem};
 ///
-/// let (temps, hum_ratios) = chart::line_relative_humidity(
+/// let points = chart::line_relative_humidity(
 ///     0.5,      // 50% RH
 ///     101325.0, // Standard pressure
 ///     UnitSystem::SI
 /// );
+/// assert_eq!(points.first().unwrap().0, -15.0);
+/// assert_eq!(points.last().unwrap().0, 40.0);
+/// assert!(points.windows(2).all(|pair| pair[0].1 < pair[1].1));
 /// ```
 pub fn line_relative_humidity(phi: f64, pressure: f64, unit: UnitSystem) -> Vec<(f64, f64)> {
     let t_array: Vec<f64> = match unit {
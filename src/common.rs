@@ -1,3 +1,5 @@
+use crate::float::Float;
+
 /// Unit system for psychrometric calculations
 /// <script src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 ///
@@ -45,9 +47,63 @@ pub const R_DA_IP: f64 = 53.350;
 // Universal gas constant for dry air (SI version) in J/kg_da/K.
 pub const R_DA_SI: f64 = 287.042;
 
+// Gas constant for water vapor (IP version) in ft∙lbf/lb_w/R.
+pub const R_W_IP: f64 = 85.780;
+
+// Gas constant for water vapor (SI version) in J/kg_w/K.
+pub const R_W_SI: f64 = 461.524;
+
 // Ratio of molecular masses of water to dry air (non-dimension).
 pub const MASS_RATIO_WATER_DRY_AIR: f64 = 0.621945;
 
+// Molar mass of dry air in kg/kmol (SI) or lb/lbmol (IP).
+pub const MOLAR_MASS_DRY_AIR: f64 = 28.966;
+
+// Molar mass of water in kg/kmol (SI) or lb/lbmol (IP).
+pub const MOLAR_MASS_WATER: f64 = 18.015268;
+
+// Universal gas constant (SI version) in J/kmol/K.
+pub const UNIVERSAL_GAS_CONSTANT_SI: f64 = 8314.472;
+
+// Universal gas constant (IP version) in ft∙lbf/lbmol/R.
+pub const UNIVERSAL_GAS_CONSTANT_IP: f64 = 1545.349;
+
+// Mechanical equivalent of heat in ft∙lbf/Btu.
+pub const FT_LBF_PER_BTU: f64 = 778.169;
+
+// Pressure conversion factor from Psi to Pa.
+pub const PA_PER_PSI: f64 = 6894.75729;
+
+// Conversion factor of dynamic viscosity from Pa∙s to lb/ft/h.
+pub const LB_PER_FT_H_PER_PA_S: f64 = 2419.0883;
+
+// Conversion factor of thermal conductivity from W/m/K to Btu/h/ft/°F.
+pub const BTU_PER_H_FT_F_PER_W_M_K: f64 = 0.5777893;
+
+// Conversion factor of diffusivity from m²/s to ft²/h.
+pub const FT2_PER_H_PER_M2_PER_S: f64 = 38750.08;
+
+// Conversion factor of specific heat from Btu/lb/°F to J/kg/K.
+pub const J_PER_KG_K_PER_BTU_PER_LB_F: f64 = 4186.8;
+
+// Number of grains in a pound.
+pub const GRAINS_PER_POUND: f64 = 7000.0;
+
+// Conversion factor of specific enthalpy from Btu/lb to kJ/kg.
+pub const KJ_PER_KG_PER_BTU_PER_LB: f64 = 2.326;
+
+// Conversion factor of specific volume from ft³/lb to m³/kg.
+pub const M3_PER_KG_PER_FT3_PER_LB: f64 = 0.06242796057614461;
+
+// Conversion factor of specific gas constant from ft∙lbf/lb/R to J/kg/K.
+pub const J_PER_KG_K_PER_FT_LBF_PER_LB_R: f64 = 5.380320456;
+
+// Standard atmospheric pressure in Pa.
+pub const STANDARD_ATMOSPHERE_SI: f64 = 101325.0;
+
+// Standard atmospheric pressure in Psi.
+pub const STANDARD_ATMOSPHERE_IP: f64 = 14.696;
+
 // Invalid value.
 pub const INVALID: f64 = -99999.0;
 
@@ -70,6 +126,15 @@ pub const TRIPLE_POINT_WATER_IP: f64 = 32.018;
 // Triple point of water in Celsius.
 pub const TRIPLE_POINT_WATER_SI: f64 = 0.01;
 
+// Triple point pressure of water in Psi.
+pub const TRIPLE_POINT_PRESSURE_IP: f64 = 0.0887125;
+
+// Triple point pressure of water in Pa.
+pub const TRIPLE_POINT_PRESSURE_SI: f64 = 611.657;
+
+// Critical temperature of water in K.
+pub const CRITICAL_TEMPERATURE_WATER: f64 = 647.096;
+
 // Tolerance for SI and IP unit.
 pub const TOLERANCE_SI: f64 = 0.001;
 pub const TOLERANCE_IP: f64 = 0.001 * 9.0 / 5.0;
@@ -79,31 +144,31 @@ pub const TOLERANCE_IP: f64 = 0.001 * 9.0 / 5.0;
 ////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 /// Convert Fahrenheit to Rankine
-pub fn t_rankine_from_t_fahrenheit(t_f: f64) -> f64 {
-    t_f + ZERO_FAHRENHEIT_AS_RANKINE
+pub fn t_rankine_from_t_fahrenheit<F: Float>(t_f: F) -> F {
+    t_f + F::of(ZERO_FAHRENHEIT_AS_RANKINE)
 }
 
 /// Convert Rankine to Fahrenheit
-pub fn t_rankine_to_t_fahrenheit(t_r: f64) -> f64 {
-    t_r - ZERO_FAHRENHEIT_AS_RANKINE
+pub fn t_rankine_to_t_fahrenheit<F: Float>(t_r: F) -> F {
+    t_r - F::of(ZERO_FAHRENHEIT_AS_RANKINE)
 }
 
 /// Convert Celsius to Kelvin
-pub fn t_celsius_to_t_kelvin(t_c: f64) -> f64 {
-    t_c + ZERO_CELSIUS_AS_KELVIN
+pub fn t_celsius_to_t_kelvin<F: Float>(t_c: F) -> F {
+    t_c + F::of(ZERO_CELSIUS_AS_KELVIN)
 }
 
 /// Convert Kelvin to Celsius
-pub fn t_kelvin_to_t_celsius(t_k: f64) -> f64 {
-    t_k - ZERO_CELSIUS_AS_KELVIN
+pub fn t_kelvin_to_t_celsius<F: Float>(t_k: F) -> F {
+    t_k - F::of(ZERO_CELSIUS_AS_KELVIN)
 }
 
 /// Convert Celsius to Fahrenheit
-pub fn t_celsius_to_t_fahrenheit(t_c: f64) -> f64 {
-    t_c * 1.8 + 32.0
+pub fn t_celsius_to_t_fahrenheit<F: Float>(t_c: F) -> F {
+    t_c * F::of(1.8) + F::of(32.0)
 }
 
 /// Convert Fahrenheit to Celsius
-pub fn t_fahrenheit_to_t_celsius(t_f: f64) -> f64 {
-    (t_f - 32.0) / 1.8
+pub fn t_fahrenheit_to_t_celsius<F: Float>(t_f: F) -> F {
+    (t_f - F::of(32.0)) / F::of(1.8)
 }
@@ -1,9 +1,48 @@
+use crate::batch::Output;
 use crate::common::UnitSystem;
-use roots::SearchError;
-use std::error::Error;
-use std::fmt;
+use crate::moist_air::{Basis, Property};
+use crate::saturated_water_vapor::t_dry_bulb_range;
+use core::error::Error;
+use core::fmt;
 
-#[derive(Debug)]
+/// Root-finding method of an iterative calculation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverMethod {
+    NewtonRaphson,
+    Brent,
+    FixedPoint,
+}
+
+/// Reason why an iterative calculation failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverFailureReason {
+    /// The iteration limit was reached before convergence
+    NoConvergence,
+    /// The initial interval does not bracket the root
+    NoBracketing,
+    /// The derivative vanished during Newton-Raphson method
+    ZeroDerivative,
+    /// The residual was NaN, e.g. outside the domain of the correlations
+    InvalidResidual,
+}
+
+/// Errors of psychrometric calculations
+///
+/// The variants are structured so that the offending quantity, its value, the valid range and the unit system,
+/// or the details of a failed iterative calculation, can be read through the accessor methods
+/// (e.g. [`PsychroidError::quantity`] and [`PsychroidError::valid_range`]) as well as through `Display`.
+///
+/// # Example
+/// ```
+/// use psychroid::{MoistAir, UnitSystem};
+///
+/// let error = MoistAir::from_t_dry_bulb_t_dew_point(20.0, 25.0, 101325.0, UnitSystem::SI).unwrap_err();
+/// assert_eq!(error.quantity(), Some("dew point temperature"));
+/// assert_eq!(error.value(), Some(25.0));
+/// assert_eq!(error.valid_range(), Some((-100.0, 20.0)));
+/// assert_eq!(error.unit(), Some(UnitSystem::SI));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
 pub enum PsychroidError {
     /// Relative humidity is out of range (0.0〜1.0)
     InvalidRelativeHumidity(f64),
@@ -11,11 +50,209 @@ pub enum PsychroidError {
     /// Dry-bulb temperature is out of range
     InvalidTDryBulb { t_dry_bulb: f64, unit: UnitSystem },
 
-    /// その他の無効なパラメータ
-    InvalidParameter(String),
+    /// Input value is NaN or infinite
+    NonFiniteInput { name: &'static str, value: f64 },
+
+    /// Pressure is not positive
+    InvalidPressure { pressure: f64, unit: UnitSystem },
+
+    /// Humidity ratio is negative
+    InvalidHumidityRatio(f64),
+
+    /// Wet-bulb temperature is above the dry-bulb temperature,
+    /// or below the wet-bulb temperature of dry air
+    InvalidTWetBulb {
+        t_wet_bulb: f64,
+        min: f64,
+        max: f64,
+        unit: UnitSystem,
+    },
+
+    /// Dew point temperature is above the dry-bulb temperature
+    InvalidTDewPoint {
+        t_dew_point: f64,
+        t_dry_bulb: f64,
+        unit: UnitSystem,
+    },
+
+    /// Specific enthalpy is lower than `min`, that of dry air at the dry-bulb temperature
+    InvalidSpecificEnthalpy {
+        specific_enthalpy: f64,
+        min: f64,
+        unit: UnitSystem,
+    },
+
+    /// Specific volume is lower than `min`, that of dry air at the dry-bulb temperature if known
+    InvalidSpecificVolume {
+        specific_volume: f64,
+        min: f64,
+        unit: UnitSystem,
+    },
+
+    /// Saturation pressure of water vapor reaches the total pressure, i.e. at or above the boiling point
+    PressureBelowSaturation {
+        pressure: f64,
+        saturation_pressure: f64,
+        unit: UnitSystem,
+    },
+
+    /// Any other quantity is out of its valid range
+    OutOfRange {
+        quantity: &'static str,
+        value: f64,
+        min: f64,
+        max: f64,
+        unit: Option<UnitSystem>,
+    },
+
+    /// The pair of properties does not determine a state (e.g. humidity ratio and dew point)
+    DependentProperties(Property, Property),
+
+    /// The inputs of a gradient do not vary independently at the state (e.g. relative humidity of fog)
+    DependentInputs(Output, Output),
 
-    /// 数値計算におけるエラー (ニュートン法など)
-    CalculationError(String),
+    /// The property cannot be given on the basis
+    UnsupportedBasis { property: Property, basis: Basis },
+
+    /// States in different unit systems are combined
+    UnitMismatch {
+        expected: UnitSystem,
+        found: UnitSystem,
+    },
+
+    /// Iterative calculation failed, with the iteration count and the residual at the last evaluation
+    SolverFailure {
+        method: SolverMethod,
+        reason: SolverFailureReason,
+        iterations: usize,
+        residual: f64,
+    },
+
+    /// Serialized interpolation table is malformed, with the reason
+    InvalidTableData(&'static str),
+}
+
+impl PsychroidError {
+    /// Returns the name of the offending quantity
+    pub fn quantity(&self) -> Option<&'static str> {
+        match self {
+            Self::InvalidRelativeHumidity(_) => Some("relative humidity"),
+            Self::InvalidTDryBulb { .. } => Some("dry-bulb temperature"),
+            Self::NonFiniteInput { name, .. } => Some(name),
+            Self::InvalidPressure { .. } | Self::PressureBelowSaturation { .. } => Some("pressure"),
+            Self::InvalidHumidityRatio(_) => Some("humidity ratio"),
+            Self::InvalidTWetBulb { .. } => Some("wet-bulb temperature"),
+            Self::InvalidTDewPoint { .. } => Some("dew point temperature"),
+            Self::InvalidSpecificEnthalpy { .. } => Some("specific enthalpy"),
+            Self::InvalidSpecificVolume { .. } => Some("specific volume"),
+            Self::OutOfRange { quantity, .. } => Some(quantity),
+            Self::UnsupportedBasis { property, .. } => Some(property.name()),
+            Self::DependentProperties(..) | Self::DependentInputs(..) => None,
+            Self::UnitMismatch { .. } => None,
+            Self::SolverFailure { .. } | Self::InvalidTableData(_) => None,
+        }
+    }
+
+    /// Returns the offending value of the quantity
+    pub fn value(&self) -> Option<f64> {
+        match *self {
+            Self::InvalidRelativeHumidity(value) | Self::InvalidHumidityRatio(value) => Some(value),
+            Self::InvalidTDryBulb { t_dry_bulb, .. } => Some(t_dry_bulb),
+            Self::NonFiniteInput { value, .. } | Self::OutOfRange { value, .. } => Some(value),
+            Self::InvalidPressure { pressure, .. }
+            | Self::PressureBelowSaturation { pressure, .. } => Some(pressure),
+            Self::InvalidTWetBulb { t_wet_bulb, .. } => Some(t_wet_bulb),
+            Self::InvalidTDewPoint { t_dew_point, .. } => Some(t_dew_point),
+            Self::InvalidSpecificEnthalpy {
+                specific_enthalpy, ..
+            } => Some(specific_enthalpy),
+            Self::InvalidSpecificVolume {
+                specific_volume, ..
+            } => Some(specific_volume),
+            Self::UnsupportedBasis { property, .. } => Some(property.value()),
+            Self::DependentProperties(..) | Self::DependentInputs(..) => None,
+            Self::UnitMismatch { .. } => None,
+            Self::SolverFailure { .. } | Self::InvalidTableData(_) => None,
+        }
+    }
+
+    /// Returns the valid range of the quantity as (lower, upper) bounds, which may be infinite.
+    /// The bounds of the pressure, of the humidity ratio of saturated moist air and of the relative humidity
+    /// limited by the total pressure above the boiling point are exclusive.
+    pub fn valid_range(&self) -> Option<(f64, f64)> {
+        match *self {
+            Self::InvalidRelativeHumidity(_) => Some((0.0, 1.0)),
+            Self::InvalidTDryBulb { unit, .. } => Some(t_dry_bulb_range(unit)),
+            Self::InvalidPressure { .. } => Some((0.0, f64::INFINITY)),
+            Self::InvalidHumidityRatio(_) => Some((0.0, f64::INFINITY)),
+            Self::InvalidTWetBulb { min, max, .. } | Self::OutOfRange { min, max, .. } => {
+                Some((min, max))
+            }
+            Self::InvalidTDewPoint {
+                t_dry_bulb, unit, ..
+            } => Some((t_dry_bulb_range(unit).0, t_dry_bulb)),
+            Self::InvalidSpecificEnthalpy { min, .. } | Self::InvalidSpecificVolume { min, .. } => {
+                Some((min, f64::INFINITY))
+            }
+            Self::PressureBelowSaturation {
+                saturation_pressure,
+                ..
+            } => Some((saturation_pressure, f64::INFINITY)),
+            Self::NonFiniteInput { .. } | Self::UnsupportedBasis { .. } => None,
+            Self::DependentProperties(..) | Self::DependentInputs(..) => None,
+            Self::UnitMismatch { .. } => None,
+            Self::SolverFailure { .. } | Self::InvalidTableData(_) => None,
+        }
+    }
+
+    /// Returns the unit system in which the value and the valid range are given,
+    /// or `None` for dimensionless quantities
+    pub fn unit(&self) -> Option<UnitSystem> {
+        match *self {
+            Self::InvalidTDryBulb { unit, .. }
+            | Self::InvalidPressure { unit, .. }
+            | Self::InvalidTWetBulb { unit, .. }
+            | Self::InvalidTDewPoint { unit, .. }
+            | Self::InvalidSpecificEnthalpy { unit, .. }
+            | Self::InvalidSpecificVolume { unit, .. }
+            | Self::PressureBelowSaturation { unit, .. } => Some(unit),
+            Self::OutOfRange { unit, .. } => unit,
+            Self::UnitMismatch { found, .. } => Some(found),
+            _ => None,
+        }
+    }
+
+    /// Returns the root-finding method of a failed iterative calculation
+    pub fn method(&self) -> Option<SolverMethod> {
+        match *self {
+            Self::SolverFailure { method, .. } => Some(method),
+            _ => None,
+        }
+    }
+
+    /// Returns the reason why an iterative calculation failed
+    pub fn failure_reason(&self) -> Option<SolverFailureReason> {
+        match *self {
+            Self::SolverFailure { reason, .. } => Some(reason),
+            _ => None,
+        }
+    }
+
+    /// Returns the iteration count of a failed iterative calculation
+    pub fn iterations(&self) -> Option<usize> {
+        match *self {
+            Self::SolverFailure { iterations, .. } => Some(iterations),
+            _ => None,
+        }
+    }
+
+    /// Returns the residual at the last evaluation of a failed iterative calculation
+    pub fn residual(&self) -> Option<f64> {
+        match *self {
+            Self::SolverFailure { residual, .. } => Some(residual),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for PsychroidError {
@@ -31,17 +268,114 @@ impl fmt::Display for PsychroidError {
                 "Dry-bulb temperature {} is out of range for unit {:?}",
                 t_dry_bulb, unit
             ),
-            Self::InvalidParameter(msg) => write!(f, "Invalid parameter: {}", msg),
-            Self::CalculationError(msg) => write!(f, "Calculation error: {}", msg),
+            Self::NonFiniteInput { name, value } => {
+                write!(f, "Invalid {}: {}. Value must be finite", name, value)
+            }
+            Self::DependentProperties(first, second) => write!(
+                f,
+                "{} and {} are not independent",
+                first.name(),
+                second.name()
+            ),
+            Self::DependentInputs(first, second) => write!(
+                f,
+                "{} and {} do not vary independently",
+                first.name(),
+                second.name()
+            ),
+            Self::UnsupportedBasis { property, basis } => write!(
+                f,
+                "{} cannot be given on the {:?} basis",
+                property.name(),
+                basis
+            ),
+            Self::UnitMismatch { expected, found } => {
+                write!(f, "Unit system {:?} does not match {:?}", found, expected)
+            }
+            Self::SolverFailure {
+                method,
+                reason,
+                iterations,
+                residual,
+            } => write!(
+                f,
+                "{:?} failed with {:?} after {} iterations, last residual {}",
+                method, reason, iterations, residual
+            ),
+            Self::InvalidTableData(reason) => write!(f, "Invalid table data: {}", reason),
+            // the other variants are described by the quantity, the value and the valid range
+            _ => {
+                // OK to unwrap because all the other variants have a quantity, a value and a range
+                let (min, max) = self.valid_range().unwrap();
+                write!(
+                    f,
+                    "Invalid {}: {}. Value must be between {} and {}",
+                    self.quantity().unwrap(),
+                    self.value().unwrap(),
+                    min,
+                    max
+                )?;
+                match self.unit() {
+                    Some(unit) => write!(f, " for unit {:?}", unit),
+                    None => Ok(()),
+                }
+            }
         }
     }
 }
 
 impl Error for PsychroidError {}
 
-// Transform roots::SearchError into PsychroidError
-impl From<SearchError> for PsychroidError {
-    fn from(error: SearchError) -> Self {
-        PsychroidError::CalculationError(error.to_string())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moist_air::{MoistAir, PropertyPair};
+
+    #[test]
+    fn test_accessors() {
+        let unit = UnitSystem::SI;
+        let error = MoistAir::from_t_dry_bulb_enthalpy(20.0, 10.0, 101325.0, unit).unwrap_err();
+        assert_eq!(error.quantity(), Some("specific enthalpy"));
+        assert_eq!(error.value(), Some(10.0));
+        let (min, max) = error.valid_range().unwrap();
+        assert!((min - 1.006 * 20.0).abs() < 1.0E-9 && max == f64::INFINITY);
+        assert_eq!(error.unit(), Some(unit));
+        assert_eq!(error.method(), None);
+        assert_eq!(
+            error.to_string(),
+            "Invalid specific enthalpy: 10. Value must be between 20.12 and inf for unit SI"
+        );
+
+        // the wet-bulb temperature is bounded by that of dry air
+        let error = MoistAir::from_t_dry_bulb_t_wet_bulb(40.0, 0.0, 101325.0, unit).unwrap_err();
+        let (min, max) = error.valid_range().unwrap();
+        assert!(min > 0.0 && min < 40.0 && max == 40.0);
+
+        let pair = PropertyPair(Property::HumidityRatio(0.01), Property::TDewPoint(10.0));
+        let error = MoistAir::from_pair(pair, 101325.0, unit).unwrap_err();
+        assert_eq!(error.value(), None);
+        assert_eq!(
+            error.to_string(),
+            "humidity ratio and dew point temperature are not independent"
+        );
+
+        let error = PsychroidError::SolverFailure {
+            method: SolverMethod::NewtonRaphson,
+            reason: SolverFailureReason::NoConvergence,
+            iterations: 50,
+            residual: 0.5,
+        };
+        assert_eq!(error.quantity(), None);
+        assert_eq!(error.method(), Some(SolverMethod::NewtonRaphson));
+        assert_eq!(
+            error.failure_reason(),
+            Some(SolverFailureReason::NoConvergence)
+        );
+        assert_eq!(error.iterations(), Some(50));
+        assert_eq!(error.residual(), Some(0.5));
+        assert_eq!(
+            error.to_string(),
+            "NewtonRaphson failed with NoConvergence after 50 iterations, last residual 0.5"
+        );
     }
 }
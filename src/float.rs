@@ -0,0 +1,104 @@
+//! Floating-point types of the closed-form correlations, i.e. `f32`, `f64` and dual numbers.
+//!
+//! The unit conversions of [`common`](crate::common), the U.S. Standard Atmosphere of
+//! [`atmosphere`](crate::atmosphere) and the saturation pressure of
+//! [`saturated_water_vapor`](crate::saturated_water_vapor) are generic over [`Float`],
+//! so that controllers with a single-precision FPU can evaluate them in `f32`.
+//! [`MoistAir`](crate::MoistAir), the [`functions`](crate::functions) and the iterative solutions
+//! are `f64` only, since their tolerances (see [`SolverOptions`](crate::SolverOptions)) are set for double precision.
+//! The closed-form relations of moist air, the real gas and the heat capacity models are generic as well,
+//! and are evaluated in [`Dual`](crate::sensitivity::Dual) numbers for the [`sensitivity`](crate::sensitivity)
+//! of the properties.
+//!
+//! Without the `std` feature, the elementary functions, e.g. `exp`, `ln` and `powf`,
+//! are provided by `libm` through the `libm` feature of `num-traits`.
+//!
+//! # Example
+//! ```
+//! use psychroid::saturated_water_vapor::saturation_pressure;
+//! use psychroid::{SaturationReference, UnitSystem};
+//!
+//! let pws_f32: f32 = saturation_pressure(20.0f32, UnitSystem::SI, SaturationReference::Ice);
+//! let pws_f64: f64 = saturation_pressure(20.0f64, UnitSystem::SI, SaturationReference::Ice);
+//! assert!((pws_f32 as f64 / pws_f64 - 1.0).abs() < 1.0E-05);
+//! ```
+
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Floating-point type of the closed-form correlations
+///
+/// Implemented for `f32` and `f64`, and for [`Dual`](crate::sensitivity::Dual) numbers,
+/// which carry the partial derivatives through the correlations.
+/// Comparisons, e.g. the selection of the saturation over ice or over water, are made on the values.
+pub trait Float:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Converts a constant of the correlations, rounding to the nearest value of the type
+    fn of(value: f64) -> Self;
+    /// Returns the value as `f64`, without derivatives
+    fn to_f64(self) -> f64;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn abs(self) -> Self;
+    fn min(self, other: Self) -> Self;
+    fn max(self, other: Self) -> Self;
+}
+
+macro_rules! impl_float {
+    ($t:ty) => {
+        impl Float for $t {
+            #[inline]
+            fn of(value: f64) -> Self {
+                value as $t
+            }
+            #[inline]
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+            #[inline]
+            fn exp(self) -> Self {
+                num_traits::Float::exp(self)
+            }
+            #[inline]
+            fn ln(self) -> Self {
+                num_traits::Float::ln(self)
+            }
+            #[inline]
+            fn sqrt(self) -> Self {
+                num_traits::Float::sqrt(self)
+            }
+            #[inline]
+            fn powi(self, n: i32) -> Self {
+                num_traits::Float::powi(self, n)
+            }
+            #[inline]
+            fn powf(self, n: Self) -> Self {
+                num_traits::Float::powf(self, n)
+            }
+            #[inline]
+            fn abs(self) -> Self {
+                num_traits::Float::abs(self)
+            }
+            #[inline]
+            fn min(self, other: Self) -> Self {
+                num_traits::Float::min(self, other)
+            }
+            #[inline]
+            fn max(self, other: Self) -> Self {
+                num_traits::Float::max(self, other)
+            }
+        }
+    };
+}
+
+impl_float!(f32);
+impl_float!(f64);
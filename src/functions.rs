@@ -0,0 +1,545 @@
+//! Stateless psychrometric functions, i.e. conversions from one property to another
+//! without building a [`MoistAir`](crate::MoistAir).
+//!
+//! All the functions take the total pressure, the unit system and the model options,
+//! and return the same values as the corresponding methods of [`MoistAir`](crate::MoistAir).
+//! The humidity ratio is the total water content, including condensed water for fog and ice fog.
+//!
+//! # Units
+//! - Temperature: \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
+//! - Pressure: \\(\\mathrm{Pa}\\) (SI) or \\(\\mathrm{Psi}\\) (IP)
+//! - Humidity ratio: \\( \\mathrm{kg_w/kg_{da}} \\) (SI) or \\( \\mathrm{lb_w/lb_{da}} \\) (IP)
+//! - Specific enthalpy: \\( \\mathrm{kJ/kg_{da}} \\) (SI) or \\( \\mathrm{Btu/lb_{da}} \\) (IP)
+//! - Specific volume: \\( \\mathrm{m^3/kg_{da}} \\) (SI) or \\( \\mathrm{ft^3/lb_{da}} \\) (IP)
+//!
+//! # Example
+//! ```
+//! use psychroid::functions::{humidity_ratio_from_t_wet_bulb, t_dew_point_from_humidity_ratio};
+//! use psychroid::{ModelOptions, UnitSystem};
+//!
+//! let options = ModelOptions::default();
+//! let w = humidity_ratio_from_t_wet_bulb(30.0, 20.0, 101325.0, UnitSystem::SI, &options).unwrap();
+//! let t_dew_point = t_dew_point_from_humidity_ratio(w, 101325.0, UnitSystem::SI, &options).unwrap();
+//! assert!(t_dew_point < 20.0);
+//! ```
+use crate::common::UnitSystem;
+use crate::error::PsychroidError;
+use crate::moist_air;
+use crate::moist_air::ModelOptions;
+use crate::saturated_water_vapor::{check_range_t_dry_bulb, SaturatedWaterVapor};
+use crate::units;
+use crate::validation::{check_finite, check_humidity_ratio, check_pressure};
+use crate::validation::{check_relative_humidity, check_t_wet_bulb};
+use crate::validation::{specific_enthalpy_error, specific_volume_error};
+
+/// Calculates the saturation pressure of water vapor
+///
+/// Below the triple point, the saturation pressure is evaluated over ice or over supercooled water
+/// depending on [`ModelOptions::saturation_reference`]. The enhancement factor is not included.
+///
+/// # Returns
+/// * Saturation pressure \\(\\mathrm{Pa}\\) (SI) or \\(\\mathrm{Psi}\\) (IP)
+pub fn saturation_pressure(
+    t_dry_bulb: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    let saturated_water_vapor =
+        SaturatedWaterVapor::new(t_dry_bulb, unit)?.with_reference(options.saturation_reference);
+    Ok(saturated_water_vapor.saturation_pressure())
+}
+
+/// Calculates the humidity ratio of saturated moist air
+///
+/// # Returns
+/// * Saturation humidity ratio, which is infinite above the boiling point
+pub fn saturation_humidity_ratio(
+    t_dry_bulb: f64,
+    pressure: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    check_range_t_dry_bulb(t_dry_bulb, unit)?;
+    check_pressure(pressure, unit)?;
+    Ok(moist_air::saturation_humidity_ratio(
+        units::t_to_si(t_dry_bulb, unit),
+        units::pressure_to_si(pressure, unit),
+        options,
+    ))
+}
+
+/// Calculates the humidity ratio from dry-bulb temperature and relative humidity
+///
+/// # Errors
+/// * `InvalidRelativeHumidity` if the relative humidity is out of [0, 1]
+/// * `OutOfRange` if the partial pressure of water vapor reaches the total pressure above the boiling point,
+///   with the relative humidity at the total pressure as the upper bound
+pub fn humidity_ratio_from_relative_humidity(
+    t_dry_bulb: f64,
+    relative_humidity: f64,
+    pressure: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    check_relative_humidity(relative_humidity)?;
+    check_pressure(pressure, unit)?;
+    moist_air::humidity_ratio_from_relative_humidity(
+        units::t_to_si(t_dry_bulb, unit),
+        relative_humidity,
+        units::pressure_to_si(pressure, unit),
+        options,
+    )
+    .map_err(units::error_from_si(unit, options))
+}
+
+/// Calculates the relative humidity from dry-bulb temperature and humidity ratio
+///
+/// The result exceeds 1 for supersaturated moist air, of which the relative humidity of the gas phase is 1.
+pub fn relative_humidity_from_humidity_ratio(
+    t_dry_bulb: f64,
+    humidity_ratio: f64,
+    pressure: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    check_humidity_ratio(humidity_ratio)?;
+    check_pressure(pressure, unit)?;
+    moist_air::relative_humidity_from_humidity_ratio(
+        units::t_to_si(t_dry_bulb, unit),
+        humidity_ratio,
+        units::pressure_to_si(pressure, unit),
+        options,
+    )
+    .map_err(units::error_from_si(unit, options))
+}
+
+/// Calculates the humidity ratio from dry-bulb and wet-bulb temperatures
+///
+/// ASHRAE Handbook - Fundamentals (2017) Ch. 1 Eq. (33) and (35)
+///
+/// # Errors
+/// * `InvalidTWetBulb` if the wet-bulb temperature exceeds the dry-bulb temperature
+/// * `PressureBelowSaturation` if the wet-bulb temperature is not below the boiling point
+pub fn humidity_ratio_from_t_wet_bulb(
+    t_dry_bulb: f64,
+    t_wet_bulb: f64,
+    pressure: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    let options = &options.for_unit(unit);
+    check_range_t_dry_bulb(t_dry_bulb, unit)?;
+    check_t_wet_bulb(t_wet_bulb, t_dry_bulb, pressure, unit, options)?;
+    check_pressure(pressure, unit)?;
+    moist_air::humidity_ratio_from_t_wet_bulb(
+        units::t_to_si(t_dry_bulb, unit),
+        units::t_to_si(t_wet_bulb, unit),
+        units::pressure_to_si(pressure, unit),
+        options,
+    )
+    .map_err(units::error_from_si(unit, options))
+}
+
+/// Calculates the wet-bulb temperature from dry-bulb temperature and humidity ratio
+///
+/// The wet-bulb temperature is searched by Newton-Raphson method on ASHRAE Handbook - Fundamentals (2017)
+/// Ch. 1 Eq. (33) and (35), or on the energy balance of the selected enthalpy model.
+pub fn t_wet_bulb_from_humidity_ratio(
+    t_dry_bulb: f64,
+    humidity_ratio: f64,
+    pressure: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    let options = &options.for_unit(unit);
+    check_range_t_dry_bulb(t_dry_bulb, unit)?;
+    check_humidity_ratio(humidity_ratio)?;
+    check_pressure(pressure, unit)?;
+    let t_wet_bulb = moist_air::t_wet_bulb_from_humidity_ratio(
+        units::t_to_si(t_dry_bulb, unit),
+        humidity_ratio,
+        units::pressure_to_si(pressure, unit),
+        options,
+    )
+    .map_err(units::error_from_si(unit, options))?;
+    Ok(units::t_from_si(t_wet_bulb, unit))
+}
+
+/// Calculates the humidity ratio from dew point temperature
+///
+/// ASHRAE Handbook - Fundamentals (2017) Ch. 1 Eq. (20)
+pub fn humidity_ratio_from_t_dew_point(
+    t_dew_point: f64,
+    pressure: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    check_pressure(pressure, unit)?;
+    moist_air::humidity_ratio_from_t_dew_point(
+        units::t_to_si(t_dew_point, unit),
+        units::pressure_to_si(pressure, unit),
+        options,
+    )
+    .map_err(units::error_from_si(unit, options))
+}
+
+/// Calculates the dew point temperature from humidity ratio
+///
+/// # Returns
+/// * Dew point temperature, or NaN for dry air
+pub fn t_dew_point_from_humidity_ratio(
+    humidity_ratio: f64,
+    pressure: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    check_humidity_ratio(humidity_ratio)?;
+    check_pressure(pressure, unit)?;
+    let t_dew_point = moist_air::t_dew_point_from_humidity_ratio(
+        humidity_ratio,
+        units::pressure_to_si(pressure, unit),
+        options,
+    )
+    .map_err(units::error_from_si(unit, options))?;
+    Ok(units::t_from_si(t_dew_point, unit))
+}
+
+/// Calculates the saturation temperature for a partial pressure of water vapor,
+/// which is the boiling point if the partial pressure equals the total pressure
+///
+/// # Errors
+/// * `OutOfRange` if the partial pressure is not positive or exceeds the total pressure
+pub fn t_dew_point_from_partial_pressure(
+    partial_water_vapor_pressure: f64,
+    pressure: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    check_pressure(pressure, unit)?;
+    if !(partial_water_vapor_pressure > 0.0 && partial_water_vapor_pressure <= pressure) {
+        return Err(PsychroidError::OutOfRange {
+            quantity: "partial pressure of water vapor",
+            value: partial_water_vapor_pressure,
+            min: 0.0,
+            max: pressure,
+            unit: Some(unit),
+        });
+    }
+    let t_dew_point = moist_air::t_dew_point_from_partial_pressure(
+        units::pressure_to_si(partial_water_vapor_pressure, unit),
+        units::pressure_to_si(pressure, unit),
+        options,
+    )
+    .map_err(units::error_from_si(unit, options))?;
+    Ok(units::t_from_si(t_dew_point, unit))
+}
+
+/// Calculates the specific enthalpy from dry-bulb temperature and humidity ratio
+///
+/// ASHRAE Handbook - Fundamentals (2017) Ch. 1 Eq. (30), or the selected enthalpy model.
+/// The enthalpy of condensed water is included for supersaturated moist air.
+pub fn specific_enthalpy_from_humidity_ratio(
+    t_dry_bulb: f64,
+    humidity_ratio: f64,
+    pressure: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    let options = &options.for_unit(unit);
+    check_range_t_dry_bulb(t_dry_bulb, unit)?;
+    check_humidity_ratio(humidity_ratio)?;
+    check_pressure(pressure, unit)?;
+    let specific_enthalpy = moist_air::specific_enthalpy_from_humidity_ratio(
+        units::t_to_si(t_dry_bulb, unit),
+        humidity_ratio,
+        units::pressure_to_si(pressure, unit),
+        options,
+    );
+    Ok(units::specific_enthalpy_from_si(
+        specific_enthalpy,
+        unit,
+        options,
+    ))
+}
+
+/// Calculates the humidity ratio from dry-bulb temperature and specific enthalpy
+///
+/// # Errors
+/// * `InvalidSpecificEnthalpy` if the specific enthalpy is lower than that of dry air
+pub fn humidity_ratio_from_specific_enthalpy(
+    t_dry_bulb: f64,
+    specific_enthalpy: f64,
+    pressure: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    let options = &options.for_unit(unit);
+    check_range_t_dry_bulb(t_dry_bulb, unit)?;
+    check_finite("specific enthalpy", specific_enthalpy)?;
+    check_pressure(pressure, unit)?;
+    let t_dry_bulb = units::t_to_si(t_dry_bulb, unit);
+    let specific_enthalpy = units::specific_enthalpy_to_si(specific_enthalpy, unit, options);
+    let pressure = units::pressure_to_si(pressure, unit);
+    let humidity_ratio = moist_air::humidity_ratio_from_specific_enthalpy(
+        t_dry_bulb,
+        specific_enthalpy,
+        pressure,
+        options,
+    );
+    if humidity_ratio < 0.0 {
+        let error = specific_enthalpy_error(specific_enthalpy, t_dry_bulb, pressure, options);
+        return Err(units::error_from_si(unit, options)(error));
+    }
+    Ok(humidity_ratio)
+}
+
+/// Calculates the dry-bulb temperature from specific enthalpy and humidity ratio
+pub fn t_dry_bulb_from_specific_enthalpy_humidity_ratio(
+    specific_enthalpy: f64,
+    humidity_ratio: f64,
+    pressure: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    let options = &options.for_unit(unit);
+    check_finite("specific enthalpy", specific_enthalpy)?;
+    check_humidity_ratio(humidity_ratio)?;
+    check_pressure(pressure, unit)?;
+    let t_dry_bulb = moist_air::t_dry_bulb_from_specific_enthalpy_humidity_ratio(
+        units::specific_enthalpy_to_si(specific_enthalpy, unit, options),
+        humidity_ratio,
+        units::pressure_to_si(pressure, unit),
+        options,
+    )
+    .map_err(units::error_from_si(unit, options))?;
+    let t_dry_bulb = units::t_from_si(t_dry_bulb, unit);
+    check_range_t_dry_bulb(t_dry_bulb, unit)?;
+    Ok(t_dry_bulb)
+}
+
+/// Calculates the dry-bulb temperature from specific enthalpy and relative humidity
+pub fn t_dry_bulb_from_specific_enthalpy_relative_humidity(
+    specific_enthalpy: f64,
+    relative_humidity: f64,
+    pressure: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    let options = &options.for_unit(unit);
+    check_finite("specific enthalpy", specific_enthalpy)?;
+    check_relative_humidity(relative_humidity)?;
+    check_pressure(pressure, unit)?;
+    let t_dry_bulb = moist_air::t_dry_bulb_from_specific_enthalpy_relative_humidity(
+        units::specific_enthalpy_to_si(specific_enthalpy, unit, options),
+        relative_humidity,
+        units::pressure_to_si(pressure, unit),
+        options,
+    )
+    .map_err(units::error_from_si(unit, options))?;
+    let t_dry_bulb = units::t_from_si(t_dry_bulb, unit);
+    check_range_t_dry_bulb(t_dry_bulb, unit)?;
+    Ok(t_dry_bulb)
+}
+
+/// Calculates the specific volume from dry-bulb temperature and humidity ratio
+///
+/// ASHRAE Handbook - Fundamentals (2017) Ch. 1 Eq. (26).
+/// For supersaturated moist air, only the water vapor contributes to the volume.
+pub fn specific_volume_from_humidity_ratio(
+    t_dry_bulb: f64,
+    humidity_ratio: f64,
+    pressure: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    let saturation_humidity_ratio = saturation_humidity_ratio(t_dry_bulb, pressure, unit, options)?;
+    check_humidity_ratio(humidity_ratio)?;
+    let specific_volume = moist_air::specific_volume_from_humidity_ratio(
+        units::t_to_si(t_dry_bulb, unit),
+        humidity_ratio.min(saturation_humidity_ratio),
+        units::pressure_to_si(pressure, unit),
+        options,
+    );
+    Ok(units::specific_volume_from_si(specific_volume, unit))
+}
+
+/// Calculates the humidity ratio of water vapor from dry-bulb temperature and specific volume
+///
+/// # Errors
+/// * `InvalidSpecificVolume` if the specific volume is lower than that of dry air
+pub fn humidity_ratio_from_specific_volume(
+    t_dry_bulb: f64,
+    specific_volume: f64,
+    pressure: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    check_range_t_dry_bulb(t_dry_bulb, unit)?;
+    check_finite("specific volume", specific_volume)?;
+    check_pressure(pressure, unit)?;
+    let t_dry_bulb = units::t_to_si(t_dry_bulb, unit);
+    let specific_volume = units::specific_volume_to_si(specific_volume, unit);
+    let pressure = units::pressure_to_si(pressure, unit);
+    let humidity_ratio = moist_air::humidity_ratio_from_specific_volume(
+        t_dry_bulb,
+        specific_volume,
+        pressure,
+        options,
+    );
+    if humidity_ratio < 0.0 {
+        let error = specific_volume_error(specific_volume, t_dry_bulb, pressure, options);
+        return Err(units::error_from_si(unit, options)(error));
+    }
+    Ok(humidity_ratio)
+}
+
+/// Calculates the dry-bulb temperature from specific volume and humidity ratio of water vapor
+pub fn t_dry_bulb_from_specific_volume_humidity_ratio(
+    specific_volume: f64,
+    humidity_ratio: f64,
+    pressure: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    check_finite("specific volume", specific_volume)?;
+    check_humidity_ratio(humidity_ratio)?;
+    check_pressure(pressure, unit)?;
+    let t_dry_bulb = moist_air::t_dry_bulb_from_specific_volume_humidity_ratio(
+        units::specific_volume_to_si(specific_volume, unit),
+        humidity_ratio,
+        units::pressure_to_si(pressure, unit),
+        options,
+    );
+    let t_dry_bulb = units::t_from_si(t_dry_bulb, unit);
+    check_range_t_dry_bulb(t_dry_bulb, unit)?;
+    Ok(t_dry_bulb)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moist_air::MoistAir;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_consistent_with_moist_air() {
+        let options = ModelOptions::default();
+        for (unit, t_dry_bulb, pressure) in [
+            (UnitSystem::SI, 30.0, 101325.0),
+            (UnitSystem::IP, 86.0, 14.696),
+        ] {
+            let moist_air =
+                MoistAir::from_t_dry_bulb_relative_humidity(t_dry_bulb, 0.4, pressure, unit)
+                    .unwrap();
+            let w = moist_air.humidity_ratio();
+            let h = moist_air.specific_enthalpy();
+            let v = moist_air.specific_volume();
+            let t_wet_bulb = moist_air.t_wet_bulb().unwrap();
+            let t_dew_point = moist_air.t_dew_point().unwrap();
+
+            assert_eq!(
+                humidity_ratio_from_relative_humidity(t_dry_bulb, 0.4, pressure, unit, &options)
+                    .unwrap(),
+                w
+            );
+            assert_relative_eq!(
+                relative_humidity_from_humidity_ratio(t_dry_bulb, w, pressure, unit, &options)
+                    .unwrap(),
+                0.4,
+                max_relative = 1.0E-9
+            );
+            assert_eq!(
+                t_wet_bulb_from_humidity_ratio(t_dry_bulb, w, pressure, unit, &options).unwrap(),
+                t_wet_bulb
+            );
+            assert_relative_eq!(
+                humidity_ratio_from_t_wet_bulb(t_dry_bulb, t_wet_bulb, pressure, unit, &options)
+                    .unwrap(),
+                w,
+                max_relative = 1.0E-6
+            );
+            assert_eq!(
+                t_dew_point_from_humidity_ratio(w, pressure, unit, &options).unwrap(),
+                t_dew_point
+            );
+            assert_relative_eq!(
+                humidity_ratio_from_t_dew_point(t_dew_point, pressure, unit, &options).unwrap(),
+                w,
+                max_relative = 1.0E-5
+            );
+            assert_eq!(
+                specific_enthalpy_from_humidity_ratio(t_dry_bulb, w, pressure, unit, &options)
+                    .unwrap(),
+                h
+            );
+            assert_relative_eq!(
+                humidity_ratio_from_specific_enthalpy(t_dry_bulb, h, pressure, unit, &options)
+                    .unwrap(),
+                w,
+                max_relative = 1.0E-9
+            );
+            assert_relative_eq!(
+                t_dry_bulb_from_specific_enthalpy_humidity_ratio(h, w, pressure, unit, &options)
+                    .unwrap(),
+                t_dry_bulb,
+                max_relative = 1.0E-9
+            );
+            assert_relative_eq!(
+                t_dry_bulb_from_specific_enthalpy_relative_humidity(
+                    h, 0.4, pressure, unit, &options
+                )
+                .unwrap(),
+                t_dry_bulb,
+                max_relative = 1.0E-6
+            );
+            assert_eq!(
+                specific_volume_from_humidity_ratio(t_dry_bulb, w, pressure, unit, &options)
+                    .unwrap(),
+                v
+            );
+            assert_relative_eq!(
+                humidity_ratio_from_specific_volume(t_dry_bulb, v, pressure, unit, &options)
+                    .unwrap(),
+                w,
+                max_relative = 1.0E-9
+            );
+            assert_relative_eq!(
+                t_dry_bulb_from_specific_volume_humidity_ratio(v, w, pressure, unit, &options)
+                    .unwrap(),
+                t_dry_bulb,
+                max_relative = 1.0E-9
+            );
+            assert_relative_eq!(
+                t_dew_point_from_partial_pressure(
+                    moist_air.partial_water_vapor_pressure(),
+                    pressure,
+                    unit,
+                    &options
+                )
+                .unwrap(),
+                t_dew_point,
+                max_relative = 1.0E-9
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let options = ModelOptions::default();
+        let unit = UnitSystem::SI;
+        assert!(
+            humidity_ratio_from_relative_humidity(20.0, 1.5, 101325.0, unit, &options).is_err()
+        );
+        assert!(humidity_ratio_from_relative_humidity(20.0, 0.5, -1.0, unit, &options).is_err());
+        assert!(humidity_ratio_from_t_wet_bulb(20.0, 25.0, 101325.0, unit, &options).is_err());
+        assert!(t_dew_point_from_humidity_ratio(-0.01, 101325.0, unit, &options).is_err());
+        assert!(
+            humidity_ratio_from_specific_enthalpy(20.0, 0.0, 101325.0, unit, &options).is_err()
+        );
+        assert!(
+            specific_enthalpy_from_humidity_ratio(f64::NAN, 0.01, 101325.0, unit, &options)
+                .is_err()
+        );
+        assert!(t_dew_point_from_partial_pressure(2.0E+05, 101325.0, unit, &options).is_err());
+    }
+}
@@ -0,0 +1,386 @@
+use crate::common::UnitSystem;
+use crate::common::ZERO_CELSIUS_AS_KELVIN;
+use crate::common::{t_celsius_to_t_kelvin, t_fahrenheit_to_t_celsius};
+use crate::common::{J_PER_KG_K_PER_BTU_PER_LB_F, KJ_PER_KG_PER_BTU_PER_LB};
+use crate::common::{MOLAR_MASS_DRY_AIR, MOLAR_MASS_WATER, UNIVERSAL_GAS_CONSTANT_SI};
+use crate::float::Float;
+use crate::units;
+
+// Ideal gas heat capacity cp/R = a1 + a2 T + a3 T² + a4 T³ + a5 T⁴, valid from 200 K to 1000 K
+// NASA 7-coefficient polynomials, low temperature range, from GRI-Mech 3.0 thermodynamic data
+const NITROGEN: [f64; 5] = [
+    3.298677E+00,
+    1.4082404E-03,
+    -3.963222E-06,
+    5.641515E-09,
+    -2.444854E-12,
+];
+const OXYGEN: [f64; 5] = [
+    3.78245636E+00,
+    -2.99673416E-03,
+    9.84730201E-06,
+    -9.68129509E-09,
+    3.24372837E-12,
+];
+const ARGON: [f64; 5] = [2.5, 0.0, 0.0, 0.0, 0.0];
+const WATER: [f64; 5] = [
+    4.19864056E+00,
+    -2.03643410E-03,
+    6.52040211E-06,
+    -5.48797062E-09,
+    1.77197817E-12,
+];
+
+// Mole fractions of the components of dry air
+// E.W. Lemmon et al., "Thermodynamic properties of air and mixtures of nitrogen, argon, and oxygen
+// from 60 to 2000 K at pressures to 2000 MPa", Journal of Physical and Chemical Reference Data 29 (2000) 331-385.
+const DRY_AIR: [(f64, [f64; 5]); 3] = [(0.7812, NITROGEN), (0.2096, OXYGEN), (0.0092, ARGON)];
+
+// Coefficients of the IP edition of ASHRAE Handbook - Fundamentals (2017) Ch. 1 Eq. (30) in SI units,
+// i.e. 0.240 Btu/(lb·°F) of dry air, and 1061 Btu/lb at 0 °F and 0.444 Btu/(lb·°F) of water vapor
+pub(crate) const SPECIFIC_HEAT_DRY_AIR_IP_EDITION: f64 =
+    0.240 * J_PER_KG_K_PER_BTU_PER_LB_F * 1.0E-03;
+pub(crate) const SPECIFIC_HEAT_WATER_VAPOR_IP_EDITION: f64 =
+    0.444 * J_PER_KG_K_PER_BTU_PER_LB_F * 1.0E-03;
+const SPECIFIC_ENTHALPY_WATER_VAPOR_IP_EDITION: f64 =
+    (1061.0 + 0.444 * 32.0) * KJ_PER_KG_PER_BTU_PER_LB;
+
+/// Model of the specific enthalpy of dry air and water vapor
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum EnthalpyModel {
+    /// Constant heat capacities, as in ASHRAE Handbook - Fundamentals (2017) Ch. 1 Eq. (30),
+    /// with the coefficients of the edition in the unit system of the state,
+    /// i.e. of [`EnthalpyModel::LinearIp`] for the states and functions in IP units
+    #[default]
+    Linear,
+    /// Constant heat capacities with the coefficients of the IP edition of ASHRAE Handbook - Fundamentals (2017)
+    /// Ch. 1 Eq. (30), \\(h = 0.240~t + W (1061 + 0.444~t)\\), in both unit systems
+    LinearIp,
+    /// Temperature-dependent ideal gas heat capacities, integrated from the same reference states
+    VariableHeatCapacity,
+}
+
+impl EnthalpyModel {
+    /// Returns the model evaluated in the unit system, i.e. the linear model of the edition of the unit system
+    pub(crate) fn for_unit(self, unit: UnitSystem) -> Self {
+        match (self, unit) {
+            (EnthalpyModel::Linear, UnitSystem::IP) => EnthalpyModel::LinearIp,
+            (model, _) => model,
+        }
+    }
+}
+
+/// Evaluates the polynomial cp/R
+fn heat_capacity_polynomial<F: Float>(a: &[f64; 5], t_kelvin: F) -> F {
+    let a = a.map(F::of);
+    a[0] + t_kelvin * (a[1] + t_kelvin * (a[2] + t_kelvin * (a[3] + t_kelvin * a[4])))
+}
+
+/// Evaluates the integral of cp/R with respect to temperature, without the integration constant
+fn enthalpy_polynomial<F: Float>(a: &[f64; 5], t_kelvin: F) -> F {
+    let c = F::of;
+    t_kelvin
+        * (c(a[0])
+            + t_kelvin
+                * (c(a[1] / 2.0)
+                    + t_kelvin
+                        * (c(a[2] / 3.0)
+                            + t_kelvin * (c(a[3] / 4.0) + t_kelvin * c(a[4]) / c(5.0)))))
+}
+
+/// Evaluates the integral of cp/(R T) with respect to temperature, without the integration constant
+fn entropy_polynomial<F: Float>(a: &[f64; 5], t_kelvin: F) -> F {
+    let c = F::of;
+    c(a[0]) * t_kelvin.ln()
+        + t_kelvin
+            * (c(a[1])
+                + t_kelvin
+                    * (c(a[2] / 2.0) + t_kelvin * (c(a[3] / 3.0) + t_kelvin * c(a[4] / 4.0))))
+}
+
+/// Calculates the specific heat of dry air at constant pressure
+///
+/// # Arguments
+/// * `t_kelvin` - Temperature \[K\]
+///
+/// # Returns
+/// * Specific heat \[kJ/(kg·K)\]
+///
+/// # Example
+/// ```
+/// use psychroid::heat_capacity::specific_heat_dry_air;
+///
+/// let cp: f64 = specific_heat_dry_air(300.0);
+/// assert!((cp - 1.005).abs() < 0.002);
+/// ```
+pub fn specific_heat_dry_air<F: Float>(t_kelvin: F) -> F {
+    let c = F::of;
+    let cp_over_r = DRY_AIR.iter().fold(c(0.0), |sum, (x, a)| {
+        sum + c(*x) * heat_capacity_polynomial(a, t_kelvin)
+    });
+    cp_over_r * c(UNIVERSAL_GAS_CONSTANT_SI) * c(1.0E-03) / c(MOLAR_MASS_DRY_AIR)
+}
+
+/// Calculates the specific heat of water vapor at constant pressure
+///
+/// # Arguments
+/// * `t_kelvin` - Temperature \[K\]
+///
+/// # Returns
+/// * Specific heat \[kJ/(kg·K)\]
+pub fn specific_heat_water_vapor<F: Float>(t_kelvin: F) -> F {
+    let c = F::of;
+    heat_capacity_polynomial(&WATER, t_kelvin) * c(UNIVERSAL_GAS_CONSTANT_SI) * c(1.0E-03)
+        / c(MOLAR_MASS_WATER)
+}
+
+/// Calculates the enthalpy change of dry air between two temperatures
+///
+/// # Arguments
+/// * `t_from` - Initial temperature \[K\]
+/// * `t_to` - Final temperature \[K\]
+///
+/// # Returns
+/// * Enthalpy change \\(\\int c_p dT\\) \[kJ/kg\]
+pub fn enthalpy_change_dry_air<F: Float>(t_from: F, t_to: F) -> F {
+    let c = F::of;
+    let h_over_r = DRY_AIR.iter().fold(c(0.0), |sum, (x, a)| {
+        sum + c(*x) * (enthalpy_polynomial(a, t_to) - enthalpy_polynomial(a, t_from))
+    });
+    h_over_r * c(UNIVERSAL_GAS_CONSTANT_SI) * c(1.0E-03) / c(MOLAR_MASS_DRY_AIR)
+}
+
+/// Calculates the enthalpy change of water vapor between two temperatures
+///
+/// # Arguments
+/// * `t_from` - Initial temperature \[K\]
+/// * `t_to` - Final temperature \[K\]
+///
+/// # Returns
+/// * Enthalpy change \\(\\int c_p dT\\) \[kJ/kg\]
+pub fn enthalpy_change_water_vapor<F: Float>(t_from: F, t_to: F) -> F {
+    let c = F::of;
+    (enthalpy_polynomial(&WATER, t_to) - enthalpy_polynomial(&WATER, t_from))
+        * c(UNIVERSAL_GAS_CONSTANT_SI)
+        * c(1.0E-03)
+        / c(MOLAR_MASS_WATER)
+}
+
+/// Calculates the entropy change of dry air at constant pressure between two temperatures
+///
+/// # Arguments
+/// * `t_from` - Initial temperature \[K\]
+/// * `t_to` - Final temperature \[K\]
+///
+/// # Returns
+/// * Entropy change \\(\\int c_p / T~dT\\) \[kJ/(kg·K)\]
+pub fn entropy_change_dry_air<F: Float>(t_from: F, t_to: F) -> F {
+    let c = F::of;
+    let s_over_r = DRY_AIR.iter().fold(c(0.0), |sum, (x, a)| {
+        sum + c(*x) * (entropy_polynomial(a, t_to) - entropy_polynomial(a, t_from))
+    });
+    s_over_r * c(UNIVERSAL_GAS_CONSTANT_SI) * c(1.0E-03) / c(MOLAR_MASS_DRY_AIR)
+}
+
+/// Calculates the entropy change of water vapor at constant pressure between two temperatures
+///
+/// # Arguments
+/// * `t_from` - Initial temperature \[K\]
+/// * `t_to` - Final temperature \[K\]
+///
+/// # Returns
+/// * Entropy change \\(\\int c_p / T~dT\\) \[kJ/(kg·K)\]
+pub fn entropy_change_water_vapor<F: Float>(t_from: F, t_to: F) -> F {
+    let c = F::of;
+    (entropy_polynomial(&WATER, t_to) - entropy_polynomial(&WATER, t_from))
+        * c(UNIVERSAL_GAS_CONSTANT_SI)
+        * c(1.0E-03)
+        / c(MOLAR_MASS_WATER)
+}
+
+/// Calculates the specific enthalpy of dry air in kJ/kg for the temperature in °C,
+/// i.e. the core in SI units of [`specific_enthalpy_dry_air`]
+pub(crate) fn specific_enthalpy_dry_air_si<F: Float>(t: F, model: EnthalpyModel) -> F {
+    match model {
+        EnthalpyModel::Linear => F::of(1.006) * t,
+        EnthalpyModel::LinearIp => F::of(SPECIFIC_HEAT_DRY_AIR_IP_EDITION) * t,
+        EnthalpyModel::VariableHeatCapacity => {
+            enthalpy_change_dry_air(F::of(ZERO_CELSIUS_AS_KELVIN), t_celsius_to_t_kelvin(t))
+        }
+    }
+}
+
+/// Calculates the specific enthalpy of water vapor in kJ/kg for the temperature in °C,
+/// i.e. the core in SI units of [`specific_enthalpy_water_vapor`]
+pub(crate) fn specific_enthalpy_water_vapor_si<F: Float>(t: F, model: EnthalpyModel) -> F {
+    let c = F::of;
+    match model {
+        EnthalpyModel::Linear => c(2501.0) + c(1.860) * t,
+        EnthalpyModel::LinearIp => {
+            c(SPECIFIC_ENTHALPY_WATER_VAPOR_IP_EDITION)
+                + c(SPECIFIC_HEAT_WATER_VAPOR_IP_EDITION) * t
+        }
+        EnthalpyModel::VariableHeatCapacity => {
+            c(2501.0)
+                + enthalpy_change_water_vapor(c(ZERO_CELSIUS_AS_KELVIN), t_celsius_to_t_kelvin(t))
+        }
+    }
+}
+
+/// Calculates the entropy change of dry air at constant pressure in kJ/(kg·K)
+/// from 0 °C to the temperature in °C, consistent with [`specific_enthalpy_dry_air_si`]
+pub(crate) fn entropy_change_dry_air_si<F: Float>(t: F, model: EnthalpyModel) -> F {
+    let t_reference = F::of(ZERO_CELSIUS_AS_KELVIN);
+    match model {
+        EnthalpyModel::Linear => F::of(1.006) * (t_celsius_to_t_kelvin(t) / t_reference).ln(),
+        EnthalpyModel::LinearIp => {
+            F::of(SPECIFIC_HEAT_DRY_AIR_IP_EDITION) * (t_celsius_to_t_kelvin(t) / t_reference).ln()
+        }
+        EnthalpyModel::VariableHeatCapacity => {
+            entropy_change_dry_air(t_reference, t_celsius_to_t_kelvin(t))
+        }
+    }
+}
+
+/// Calculates the entropy change of water vapor at constant pressure in kJ/(kg·K)
+/// from the temperature `t_from` to `t` in °C, consistent with [`specific_enthalpy_water_vapor_si`]
+pub(crate) fn entropy_change_water_vapor_si<F: Float>(t_from: F, t: F, model: EnthalpyModel) -> F {
+    let (t_from, t) = (t_celsius_to_t_kelvin(t_from), t_celsius_to_t_kelvin(t));
+    match model {
+        EnthalpyModel::Linear => F::of(1.860) * (t / t_from).ln(),
+        EnthalpyModel::LinearIp => F::of(SPECIFIC_HEAT_WATER_VAPOR_IP_EDITION) * (t / t_from).ln(),
+        EnthalpyModel::VariableHeatCapacity => entropy_change_water_vapor(t_from, t),
+    }
+}
+
+/// Calculates the specific enthalpy of dry air, which is zero at 0 °C (SI) or 0 °F (IP)
+///
+/// # Arguments
+/// * `t` - Temperature \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
+/// * `unit` - Unit system
+/// * `model` - Enthalpy model
+///
+/// # Returns
+/// * Specific enthalpy \\(\\mathrm{kJ/kg}\\) (SI) or \\(\\mathrm{Btu/lb}\\) (IP)
+pub fn specific_enthalpy_dry_air<F: Float>(t: F, unit: UnitSystem, model: EnthalpyModel) -> F {
+    let model = model.for_unit(unit);
+    let h = specific_enthalpy_dry_air_si(units::t_to_si(t, unit), model);
+    match unit {
+        UnitSystem::SI => h,
+        UnitSystem::IP => {
+            let h_reference =
+                specific_enthalpy_dry_air_si(t_fahrenheit_to_t_celsius(F::of(0.0)), model);
+            units::specific_energy_from_si(h - h_reference, unit)
+        }
+    }
+}
+
+/// Calculates the specific enthalpy of water vapor,
+/// which is 2501 kJ/kg at 0 °C (SI) or 1061 Btu/lb at 0 °F (IP), relative to liquid water at 0 °C or 32 °F
+///
+/// # Arguments
+/// * `t` - Temperature \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
+/// * `unit` - Unit system
+/// * `model` - Enthalpy model
+///
+/// # Returns
+/// * Specific enthalpy \\(\\mathrm{kJ/kg}\\) (SI) or \\(\\mathrm{Btu/lb}\\) (IP)
+pub fn specific_enthalpy_water_vapor<F: Float>(t: F, unit: UnitSystem, model: EnthalpyModel) -> F {
+    let model = model.for_unit(unit);
+    let h = specific_enthalpy_water_vapor_si(units::t_to_si(t, unit), model);
+    units::specific_energy_from_si(h, unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_specific_heat() {
+        // Reference values of ideal gas heat capacities from NIST-JANAF thermochemical tables
+        assert_relative_eq!(specific_heat_dry_air(300.0), 1.005, max_relative = 0.002);
+        assert_relative_eq!(specific_heat_dry_air(500.0), 1.029, max_relative = 0.002);
+        assert_relative_eq!(
+            specific_heat_water_vapor(300.0),
+            1.865,
+            max_relative = 0.002
+        );
+        assert_relative_eq!(
+            specific_heat_water_vapor(500.0),
+            1.955,
+            max_relative = 0.002
+        );
+        // enthalpy change is consistent with the heat capacity
+        let dt = 1.0E-03;
+        assert_relative_eq!(
+            enthalpy_change_dry_air(400.0 - dt, 400.0 + dt) / (2.0 * dt),
+            specific_heat_dry_air(400.0),
+            max_relative = 1.0E-6
+        );
+        assert_relative_eq!(
+            enthalpy_change_water_vapor(400.0 - dt, 400.0 + dt) / (2.0 * dt),
+            specific_heat_water_vapor(400.0),
+            max_relative = 1.0E-6
+        );
+        // entropy change is consistent with the heat capacity
+        assert_relative_eq!(
+            entropy_change_dry_air(400.0 - dt, 400.0 + dt) / (2.0 * dt),
+            specific_heat_dry_air(400.0) / 400.0,
+            max_relative = 1.0E-6
+        );
+        assert_relative_eq!(
+            entropy_change_water_vapor(400.0 - dt, 400.0 + dt) / (2.0 * dt),
+            specific_heat_water_vapor(400.0) / 400.0,
+            max_relative = 1.0E-6
+        );
+    }
+
+    #[test]
+    fn test_specific_enthalpy() {
+        let model = EnthalpyModel::VariableHeatCapacity;
+        // same reference states as the linear model
+        assert_eq!(specific_enthalpy_dry_air(0.0, UnitSystem::SI, model), 0.0);
+        assert_eq!(specific_enthalpy_dry_air(0.0, UnitSystem::IP, model), 0.0);
+        assert_eq!(
+            specific_enthalpy_water_vapor(0.0, UnitSystem::SI, model),
+            2501.0
+        );
+        // close to the linear model at room temperature, and deviates at high temperature
+        for t in [-50.0, 25.0] {
+            assert_relative_eq!(
+                specific_enthalpy_dry_air(t, UnitSystem::SI, model),
+                specific_enthalpy_dry_air(t, UnitSystem::SI, EnthalpyModel::Linear),
+                max_relative = 0.01
+            );
+        }
+        let h = specific_enthalpy_dry_air(300.0, UnitSystem::SI, model);
+        assert!(h > specific_enthalpy_dry_air(300.0, UnitSystem::SI, EnthalpyModel::Linear));
+        // IP units
+        assert_relative_eq!(
+            specific_enthalpy_water_vapor(212.0, UnitSystem::IP, model),
+            specific_enthalpy_water_vapor(212.0, UnitSystem::IP, EnthalpyModel::Linear),
+            max_relative = 0.001
+        );
+        // the linear model evaluates the IP edition in IP units
+        let linear = EnthalpyModel::Linear;
+        assert_relative_eq!(
+            specific_enthalpy_dry_air(100.0, UnitSystem::IP, linear),
+            24.0,
+            max_relative = 1.0E-12
+        );
+        assert_relative_eq!(
+            specific_enthalpy_water_vapor(100.0, UnitSystem::IP, linear),
+            1105.4,
+            max_relative = 1.0E-12
+        );
+        // and the same coefficients in SI units, from 0 °C
+        let dh = specific_enthalpy_dry_air(86.0, UnitSystem::IP, linear)
+            - specific_enthalpy_dry_air(32.0, UnitSystem::IP, linear);
+        assert_relative_eq!(
+            specific_enthalpy_dry_air(30.0, UnitSystem::SI, EnthalpyModel::LinearIp),
+            dh * KJ_PER_KG_PER_BTU_PER_LB,
+            max_relative = 1.0E-12
+        );
+    }
+}
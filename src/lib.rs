@@ -1,7 +1,41 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the `std` or the `libm` feature is required for the elementary functions");
+
+extern crate alloc;
+
 pub mod atmosphere;
+pub mod batch;
 pub mod chart;
 pub mod common;
 pub mod error;
+pub mod float;
+pub mod functions;
+pub mod heat_capacity;
 pub mod moist_air;
+pub mod quantity;
+pub mod real_gas;
 pub mod saturated_water_vapor;
+pub mod sensitivity;
+pub mod solver;
+pub mod table;
+pub mod transport;
+pub mod uncertainty;
+mod units;
+mod validation;
+pub mod validity;
+#[cfg(feature = "wasm")]
 pub mod wasm;
+
+pub use atmosphere::{atmosphere_pressure, atmosphere_temperature};
+pub use common::UnitSystem;
+pub use error::{PsychroidError, SolverFailureReason, SolverMethod};
+pub use float::Float;
+pub use heat_capacity::EnthalpyModel;
+pub use moist_air::{
+    Basis, CondensedPhase, GasModel, ModelOptions, MoistAir, ProcessOutput, Property, PropertyPair,
+};
+pub use saturated_water_vapor::{SaturatedWaterVapor, SaturationBranch, SaturationReference};
+pub use solver::{SolverOptions, SolverReport};
+pub use validity::Validity;
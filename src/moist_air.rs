@@ -1,36 +1,346 @@
+use crate::batch::Output;
 use crate::common::UnitSystem;
-use crate::common::{t_celsius_to_t_fahrenheit, t_fahrenheit_to_t_celsius};
-use crate::common::{FREEZING_POINT_WATER_IP, FREEZING_POINT_WATER_SI, MASS_RATIO_WATER_DRY_AIR};
-use crate::error::PsychroidError;
-use crate::saturated_water_vapor::SaturatedWaterVapor;
-use roots::{find_root_newton_raphson, SimpleConvergency};
-
-const C14_SI: f64 = 6.54;
-const C15_SI: f64 = 14.526;
-const C16_SI: f64 = 0.7389;
-const C17_SI: f64 = 0.09486;
-const C18_SI: f64 = 0.4569;
-
-const C14_IP: f64 = 100.45;
-const C15_IP: f64 = 33.193;
-const C16_IP: f64 = 2.319;
-const C17_IP: f64 = 0.17074;
-const C18_IP: f64 = 1.2063;
-
-const TOLERANCE: f64 = 1e-8;
+use crate::common::CRITICAL_TEMPERATURE_WATER;
+use crate::common::MASS_RATIO_WATER_DRY_AIR;
+use crate::common::ZERO_CELSIUS_AS_KELVIN;
+use crate::common::{t_celsius_to_t_kelvin, R_DA_SI, R_W_SI};
+use crate::common::{BTU_PER_H_FT_F_PER_W_M_K, FT2_PER_H_PER_M2_PER_S};
+use crate::common::{GRAINS_PER_POUND, MOLAR_MASS_DRY_AIR, MOLAR_MASS_WATER};
+use crate::common::{LB_PER_FT_H_PER_PA_S, UNIVERSAL_GAS_CONSTANT_SI};
+use crate::common::{MAX_ITER_COUNT, STANDARD_ATMOSPHERE_SI};
+use crate::common::{TRIPLE_POINT_PRESSURE_SI, TRIPLE_POINT_WATER_SI};
+use crate::error::{PsychroidError, SolverFailureReason, SolverMethod};
+use crate::float::Float;
+use crate::heat_capacity::EnthalpyModel;
+use crate::heat_capacity::{entropy_change_dry_air_si, entropy_change_water_vapor_si};
+use crate::heat_capacity::{specific_enthalpy_dry_air_si, specific_enthalpy_water_vapor_si};
+use crate::heat_capacity::{specific_heat_dry_air, specific_heat_water_vapor};
+use crate::heat_capacity::{
+    SPECIFIC_HEAT_DRY_AIR_IP_EDITION, SPECIFIC_HEAT_WATER_VAPOR_IP_EDITION,
+};
+use crate::real_gas;
+use crate::saturated_water_vapor::{
+    check_range_t_dry_bulb, is_over_ice, saturation_pressure_and_deriv_si, saturation_pressure_si,
+    t_dry_bulb_range, SaturatedWaterVapor, SaturationBranch, SaturationReference,
+};
+use crate::sensitivity::{self, Gradient};
+use crate::solver::{brent, find_root_bracketed};
+use crate::solver::{Solution, SolverOptions, SolverReport};
+use crate::transport;
+use crate::units;
+use crate::validation::{check_finite, check_humidity_ratio, check_pressure};
+use crate::validation::{check_property, check_relative_humidity};
+use crate::validation::{check_t_dew_point, check_t_wet_bulb, t_wet_bulb_error};
+use crate::validation::{specific_enthalpy_error, specific_volume_error};
+use crate::validity::{self, Validity};
+use core::cell::Cell;
+
+// Specific entropy of saturated water vapor at the triple point in kJ/(kg·K), IAPWS-95
+const SPECIFIC_ENTROPY_VAPOR_TRIPLE_POINT: f64 = 9.1555;
+
+pub(crate) const TOLERANCE: f64 = 1e-8;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////
 // Moist Air
 ////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+/// Thermodynamic property of moist air, used to specify a state with [`PropertyPair`]
+///
+/// Values are given in the unit system passed to [`MoistAir::from_pair`]:
+/// temperatures in \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP),
+/// specific enthalpy in \\( \\mathrm{kJ/kg_{da}} \\) (SI) or \\( \\mathrm{Btu/lb_{da}} \\) (IP),
+/// specific volume in \\( \\mathrm{m^3/kg_{da}} \\) (SI) or \\( \\mathrm{ft^3/lb_{da}} \\) (IP).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Property {
+    TDryBulb(f64),
+    HumidityRatio(f64),
+    RelativeHumidity(f64),
+    TWetBulb(f64),
+    TDewPoint(f64),
+    SpecificEnthalpy(f64),
+    SpecificVolume(f64),
+}
+
+impl Property {
+    /// Returns the name of the property, e.g. "dry-bulb temperature"
+    pub fn name(&self) -> &'static str {
+        match self {
+            Property::TDryBulb(_) => "dry-bulb temperature",
+            Property::HumidityRatio(_) => "humidity ratio",
+            Property::RelativeHumidity(_) => "relative humidity",
+            Property::TWetBulb(_) => "wet-bulb temperature",
+            Property::TDewPoint(_) => "dew point temperature",
+            Property::SpecificEnthalpy(_) => "specific enthalpy",
+            Property::SpecificVolume(_) => "specific volume",
+        }
+    }
+
+    /// Returns the value of the property
+    pub fn value(&self) -> f64 {
+        match *self {
+            Property::TDryBulb(value)
+            | Property::HumidityRatio(value)
+            | Property::RelativeHumidity(value)
+            | Property::TWetBulb(value)
+            | Property::TDewPoint(value)
+            | Property::SpecificEnthalpy(value)
+            | Property::SpecificVolume(value) => value,
+        }
+    }
+
+    /// Returns the same property with the given value
+    pub(crate) fn with_value(self, value: f64) -> Property {
+        match self {
+            Property::TDryBulb(_) => Property::TDryBulb(value),
+            Property::HumidityRatio(_) => Property::HumidityRatio(value),
+            Property::RelativeHumidity(_) => Property::RelativeHumidity(value),
+            Property::TWetBulb(_) => Property::TWetBulb(value),
+            Property::TDewPoint(_) => Property::TDewPoint(value),
+            Property::SpecificEnthalpy(_) => Property::SpecificEnthalpy(value),
+            Property::SpecificVolume(_) => Property::SpecificVolume(value),
+        }
+    }
+
+    /// Order of preference for the property which determines the humidity ratio
+    /// during the iterative solution of [`MoistAir::from_pair`]
+    fn rank(&self) -> u8 {
+        match self {
+            Property::TDryBulb(_) => 0,
+            Property::TWetBulb(_) => 1,
+            Property::HumidityRatio(_) => 2,
+            Property::TDewPoint(_) => 3,
+            Property::RelativeHumidity(_) => 4,
+            Property::SpecificEnthalpy(_) => 5,
+            Property::SpecificVolume(_) => 6,
+        }
+    }
+
+    /// Humidity ratio and dew point both depend on the water content only
+    fn is_humidity_only(&self) -> bool {
+        matches!(self, Property::HumidityRatio(_) | Property::TDewPoint(_))
+    }
+
+    fn is_independent_of(&self, other: &Property) -> bool {
+        core::mem::discriminant(self) != core::mem::discriminant(other)
+            && !(self.is_humidity_only() && other.is_humidity_only())
+    }
+
+    /// Converts the property given on the basis into the dry-air basis,
+    /// where `amount` is the amount of the basis per unit mass of dry air
+    fn to_dry_air_basis(self, basis: Basis, amount: f64) -> Result<Property, PsychroidError> {
+        match (self, basis) {
+            (Property::SpecificVolume(_), Basis::Volume) => Err(PsychroidError::UnsupportedBasis {
+                property: self,
+                basis,
+            }),
+            (Property::HumidityRatio(value), _) => Ok(Property::HumidityRatio(value * amount)),
+            (Property::SpecificEnthalpy(value), _) => {
+                Ok(Property::SpecificEnthalpy(value * amount))
+            }
+            (Property::SpecificVolume(value), _) => Ok(Property::SpecificVolume(value * amount)),
+            (property, _) => Ok(property),
+        }
+    }
+}
+
+/// Pair of independent properties which defines a state of moist air
+///
+/// # Example
+/// ```
+/// use psychroid::{MoistAir, Property, PropertyPair, UnitSystem};
+///
+/// let pair = PropertyPair(Property::TWetBulb(20.0), Property::RelativeHumidity(0.5));
+/// let air = MoistAir::from_pair(pair, 101325.0, UnitSystem::SI).unwrap();
+/// assert!(air.t_dry_bulb() > 20.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PropertyPair(pub Property, pub Property);
+
+/// Flow exergy of moist air relative to a dead state, split into its components
+///
+/// Values are given in \\( \\mathrm{kJ/kg_{da}} \\) (SI) or \\( \\mathrm{Btu/lb_{da}} \\) (IP).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Exergy {
+    pub thermal: f64,
+    pub mechanical: f64,
+    pub chemical: f64,
+}
+
+impl Exergy {
+    /// Returns the total flow exergy
+    pub fn total(&self) -> f64 {
+        self.thermal + self.mechanical + self.chemical
+    }
+}
+
+/// Difference of exergy between two states, e.g. outlet minus inlet of a process
+impl core::ops::Sub for Exergy {
+    type Output = Exergy;
+
+    fn sub(self, other: Exergy) -> Exergy {
+        Exergy {
+            thermal: self.thermal - other.thermal,
+            mechanical: self.mechanical - other.mechanical,
+            chemical: self.chemical - other.chemical,
+        }
+    }
+}
+
+/// Equation of state used for moist air
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GasModel {
+    /// Ideal mixture of ideal gases, as in ASHRAE Handbook - Fundamentals (2017) Ch. 1
+    #[default]
+    Ideal,
+    /// Real gas corrections, i.e. the enhancement factor of the saturation pressure in air
+    /// and the compressibility factor from second virial coefficients, see [`crate::real_gas`].
+    /// The corrections become significant at high pressures.
+    Real,
+}
+
+/// Options of the thermodynamic models used to evaluate the properties of moist air
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ModelOptions {
+    pub gas_model: GasModel,
+    /// Reference phase of saturation below the triple point, used for the relative humidity,
+    /// the dew point, the wet-bulb temperature and the phase of fog
+    pub saturation_reference: SaturationReference,
+    /// Model of the specific enthalpy of dry air and water vapor, used for all the states and processes
+    pub enthalpy_model: EnthalpyModel,
+    /// Tolerance and iteration limit of the iterative solutions, e.g. of the wet-bulb temperature
+    pub solver: SolverOptions,
+}
+
+impl ModelOptions {
+    /// Returns the options evaluated in the unit system, see [`EnthalpyModel::Linear`]
+    pub(crate) fn for_unit(self, unit: UnitSystem) -> Self {
+        ModelOptions {
+            enthalpy_model: self.enthalpy_model.for_unit(unit),
+            ..self
+        }
+    }
+}
+
+/// Outlet state of a process together with the energy exchanged, returned by the `after_*` methods of [`MoistAir`]
+///
+/// # Example
+/// ```
+/// use psychroid::{MoistAir, UnitSystem};
+///
+/// let inlet = MoistAir::from_t_dry_bulb_relative_humidity(30.0, 0.6, 101325.0, UnitSystem::SI).unwrap();
+/// // compare two alternatives from the same inlet state
+/// let cooled = inlet.after_cooling_t1(1.0, 15.0).unwrap();
+/// let heated = inlet.after_heating_t1(1.0, 40.0).unwrap();
+/// assert!(cooled.q < 0.0 && heated.q > 0.0);
+/// assert!(cooled.outlet.humidity_ratio() < inlet.humidity_ratio());
+/// assert_eq!(heated.outlet.humidity_ratio(), inlet.humidity_ratio());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProcessOutput {
+    /// State of moist air after the process
+    pub outlet: MoistAir,
+    /// Heat added to the air stream \\(q = \\dot{m}_{da} (h_1 - h_0)\\), negative when heat is removed,
+    /// in \\( \\mathrm{kW} \\) (SI) or \\( \\mathrm{Btu/h} \\) (IP)
+    pub q: f64,
+    /// Entropy generated by the process \\(\\dot{S}_\\mathrm{gen} = \\dot{m}_{da} (s_1 - s_0) - q / T_1\\),
+    /// in \\( \\mathrm{kW/K} \\) (SI) or \\( \\mathrm{Btu/(h \\cdot {}^\\circ R)} \\) (IP)
+    ///
+    /// The heat is exchanged with a source or sink at the outlet temperature \\(T_1\\), i.e. the least irreversible
+    /// heat exchange which brings the air to the outlet state. The water added or removed is liquid water
+    /// at the reference state, of which the enthalpy and entropy are zero, as in the energy balance of `q`.
+    pub entropy_generation: f64,
+}
+
+impl ProcessOutput {
+    fn new(inlet: &MoistAir, outlet: MoistAir, mda: f64) -> Self {
+        let dh = outlet.specific_enthalpy_si() - inlet.specific_enthalpy_si();
+        let ds = outlet.specific_entropy_si() - inlet.specific_entropy_si();
+        let s_gen = ds - dh / t_celsius_to_t_kelvin(outlet.t_dry_bulb);
+        ProcessOutput {
+            outlet,
+            q: mda * units::specific_energy_from_si(dh, inlet.unit),
+            entropy_generation: mda * units::specific_heat_from_si(s_gen, inlet.unit),
+        }
+    }
+
+    /// Returns the exergy destroyed by the process \\(\\dot{X}_\\mathrm{dest} = T_0 \\dot{S}_\\mathrm{gen}\\)
+    /// relative to a dead state, in \\( \\mathrm{kW} \\) (SI) or \\( \\mathrm{Btu/h} \\) (IP),
+    /// see [`ProcessOutput::entropy_generation`] and [`MoistAir::exergy`]
+    ///
+    /// # Arguments
+    /// * `dead_state` - Ambient moist air in the same unit system
+    ///
+    /// # Errors
+    /// * `UnitMismatch` if the dead state is in a different unit system
+    ///
+    /// # Example
+    /// ```
+    /// use psychroid::{MoistAir, UnitSystem};
+    ///
+    /// let dead_state = MoistAir::from_t_dry_bulb_relative_humidity(25.0, 0.5, 101325.0, UnitSystem::SI).unwrap();
+    /// let inlet = MoistAir::from_t_dry_bulb_relative_humidity(30.0, 0.3, 101325.0, UnitSystem::SI).unwrap();
+    /// let humidified = inlet.after_humidify_adiabatic(1.0, 0.004).unwrap();
+    /// assert!(humidified.exergy_destruction(&dead_state).unwrap() > 0.0);
+    /// ```
+    pub fn exergy_destruction(&self, dead_state: &MoistAir) -> Result<f64, PsychroidError> {
+        if self.outlet.unit != dead_state.unit {
+            return Err(PsychroidError::UnitMismatch {
+                expected: self.outlet.unit,
+                found: dead_state.unit,
+            });
+        }
+        let t0_abs = t_celsius_to_t_kelvin(dead_state.t_dry_bulb);
+        Ok(match dead_state.unit {
+            UnitSystem::SI => t0_abs * self.entropy_generation,
+            UnitSystem::IP => t0_abs * 1.8 * self.entropy_generation,
+        })
+    }
+}
+
+/// Basis of the specific properties, i.e. the quantity to which extensive properties are referred
+///
+/// | Basis | Specific enthalpy | Humidity content | Specific volume |
+/// |---|---|---|---|
+/// | `DryAir` | \\( \\mathrm{kJ/kg_{da}} \\) | \\( \\mathrm{kg_w/kg_{da}} \\) (humidity ratio) | \\( \\mathrm{m^3/kg_{da}} \\) |
+/// | `MoistAir` | \\( \\mathrm{kJ/kg} \\) | \\( \\mathrm{kg_w/kg} \\) | \\( \\mathrm{m^3/kg} \\) |
+/// | `Volume` | \\( \\mathrm{kJ/m^3} \\) | \\( \\mathrm{kg_w/m^3} \\) | - |
+///
+/// The units are given for SI; for IP, kJ, kg and m³ are replaced by Btu, lb and ft³.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Basis {
+    /// Per unit mass of dry air, as used throughout ASHRAE Handbook - Fundamentals
+    #[default]
+    DryAir,
+    /// Per unit mass of moist air, including the condensed water of fog and ice fog
+    MoistAir,
+    /// Per unit volume of moist air
+    Volume,
+}
+
+/// Phase of the condensed water suspended in supersaturated moist air
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CondensedPhase {
+    /// Liquid water droplets (fog), at or above the triple point
+    Liquid,
+    /// Ice crystals (ice fog), below the triple point
+    Ice,
+}
+
 /// Contains all calculated psychrometric values
 /// <script src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
-#[derive(Debug)]
+///
+/// `MoistAir` is a small value type. [`PartialEq`] compares the states exactly;
+/// use [`MoistAir::approx_eq`] to compare states obtained by different calculations.
+///
+/// The state is held in SI units whatever the unit system, which only sets the units of the inputs
+/// and of the values returned by the accessors and the processes.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct MoistAir {
-    t_dry_bulb: f64,     // °C (SI) or °F (IP)
-    humidity_ratio: f64, // kg_H₂O/kg_Air (SI) or lb_H₂O/lb_Air (IP)
-    pressure: f64,       // Pa (SI) or Psi (IP)
+    t_dry_bulb: f64,     // °C
+    humidity_ratio: f64, // kg_H₂O/kg_Air, including condensed water
+    pressure: f64,       // Pa
     unit: UnitSystem,
+    options: ModelOptions,
 }
 
 /// Create a new instance of MoistAir with default values
@@ -39,16 +349,20 @@ impl Default for MoistAir {
         let t_dry_bulb = 20.0;
         let relative_humidity = 0.5;
         let pressure = 101325.0;
-        let unit = UnitSystem::SI;
         // OK to unwrap because default values are within valid range
-        let humidity_ratio =
-            humidity_ratio_from_relative_humidity(t_dry_bulb, relative_humidity, pressure, unit)
-                .unwrap();
+        let humidity_ratio = humidity_ratio_from_relative_humidity(
+            t_dry_bulb,
+            relative_humidity,
+            pressure,
+            &ModelOptions::default(),
+        )
+        .unwrap();
         MoistAir {
             t_dry_bulb,
             humidity_ratio,
             pressure,
-            unit,
+            unit: UnitSystem::SI,
+            options: ModelOptions::default(),
         }
     }
 }
@@ -60,34 +374,147 @@ impl MoistAir {
         pressure: f64,
         unit: UnitSystem,
     ) -> Result<Self, PsychroidError> {
-        let relative_humidity: f64 =
-            relative_humidity_from_humidity_ratio(t_dry_bulb, humidity_ratio, pressure, unit)?;
-        if !(0.0..=1.0 + TOLERANCE).contains(&relative_humidity) {
-            return Err(PsychroidError::InvalidRelativeHumidity(relative_humidity));
-        }
+        Self::from_t_dry_bulb_humidity_ratio_with_options(
+            t_dry_bulb,
+            humidity_ratio,
+            pressure,
+            unit,
+            ModelOptions::default(),
+        )
+    }
+
+    /// Creates a new MoistAir instance from dry-bulb temperature and humidity ratio,
+    /// evaluated with the given model options
+    ///
+    /// The humidity ratio is the total water content. If it exceeds the saturation humidity ratio,
+    /// the excess is suspended as liquid water (fog) or ice (ice fog), see [`MoistAir::condensed_phase`].
+    ///
+    /// # Errors
+    /// * `NonFiniteInput` if any input is NaN or infinite
+    /// * `InvalidTDryBulb` if the dry-bulb temperature is out of range
+    /// * `InvalidHumidityRatio` if the humidity ratio is negative
+    /// * `InvalidPressure` if the pressure is not positive
+    pub fn from_t_dry_bulb_humidity_ratio_with_options(
+        t_dry_bulb: f64,
+        humidity_ratio: f64,
+        pressure: f64,
+        unit: UnitSystem,
+        options: ModelOptions,
+    ) -> Result<Self, PsychroidError> {
+        check_range_t_dry_bulb(t_dry_bulb, unit)?;
+        check_pressure(pressure, unit)?;
+        Self::from_si(
+            units::t_to_si(t_dry_bulb, unit),
+            humidity_ratio,
+            units::pressure_to_si(pressure, unit),
+            unit,
+            options.for_unit(unit),
+        )
+    }
+
+    /// Creates a new MoistAir instance from dry-bulb temperature in °C, humidity ratio and pressure in Pa.
+    ///
+    /// All the constructors end up here, so that every state is validated the same way,
+    /// with the errors converted into the unit system.
+    fn from_si(
+        t_dry_bulb: f64,
+        humidity_ratio: f64,
+        pressure: f64,
+        unit: UnitSystem,
+        options: ModelOptions,
+    ) -> Result<Self, PsychroidError> {
+        let to_unit = units::error_from_si(unit, &options);
+        check_range_t_dry_bulb(t_dry_bulb, UnitSystem::SI).map_err(&to_unit)?;
+        check_humidity_ratio(humidity_ratio)?;
+        check_pressure(pressure, UnitSystem::SI).map_err(&to_unit)?;
         Ok(MoistAir {
             t_dry_bulb,
             humidity_ratio,
             pressure,
             unit,
+            options,
         })
     }
 
+    /// Replaces the dry-bulb temperature in °C and the humidity ratio after a process,
+    /// validated by [`MoistAir::from_si`] so that the state is left unchanged on error
+    fn update_si(&mut self, t_dry_bulb: f64, humidity_ratio: f64) -> Result<(), PsychroidError> {
+        *self = Self::from_si(
+            t_dry_bulb,
+            humidity_ratio,
+            self.pressure,
+            self.unit,
+            self.options,
+        )?;
+        Ok(())
+    }
+
+    /// Replaces the state after humidification like [`MoistAir::update_si`],
+    /// also checking that the relative humidity of the new state is defined
+    fn update_humidified_si(
+        &mut self,
+        t_dry_bulb: f64,
+        humidity_ratio: f64,
+    ) -> Result<(), PsychroidError> {
+        let humidified = Self::from_si(
+            t_dry_bulb,
+            humidity_ratio,
+            self.pressure,
+            self.unit,
+            self.options,
+        )?;
+        humidified.relative_humidity()?;
+        *self = humidified;
+        Ok(())
+    }
+
     /// Init from wet bulb temperature
+    ///
+    /// # Errors
+    /// * `InvalidTWetBulb` if the wet-bulb temperature is above the dry-bulb temperature,
+    ///   or too low for a non-negative humidity ratio
+    /// * `PressureBelowSaturation` if the wet-bulb temperature is not below the boiling point
     pub fn from_t_dry_bulb_t_wet_bulb(
         t_dry_bulb: f64,
         t_wet_bulb: f64,
         pressure: f64,
         unit: UnitSystem,
     ) -> Result<Self, PsychroidError> {
-        let humidity_ratio =
-            humidity_ratio_from_t_wet_bulb(t_dry_bulb, t_wet_bulb, pressure, unit)?;
-        Ok(MoistAir {
+        Self::from_t_dry_bulb_t_wet_bulb_with_options(
             t_dry_bulb,
-            humidity_ratio,
+            t_wet_bulb,
             pressure,
             unit,
-        })
+            ModelOptions::default(),
+        )
+    }
+
+    /// Creates a new MoistAir instance from dry-bulb and wet-bulb temperatures,
+    /// evaluated with the given model options
+    pub fn from_t_dry_bulb_t_wet_bulb_with_options(
+        t_dry_bulb: f64,
+        t_wet_bulb: f64,
+        pressure: f64,
+        unit: UnitSystem,
+        options: ModelOptions,
+    ) -> Result<Self, PsychroidError> {
+        let options = options.for_unit(unit);
+        check_range_t_dry_bulb(t_dry_bulb, unit)?;
+        check_t_wet_bulb(t_wet_bulb, t_dry_bulb, pressure, unit, &options)?;
+        check_pressure(pressure, unit)?;
+        let to_unit = units::error_from_si(unit, &options);
+        let t_dry_bulb = units::t_to_si(t_dry_bulb, unit);
+        let t_wet_bulb = units::t_to_si(t_wet_bulb, unit);
+        let pressure = units::pressure_to_si(pressure, unit);
+        let humidity_ratio =
+            humidity_ratio_from_t_wet_bulb(t_dry_bulb, t_wet_bulb, pressure, &options)
+                .map_err(&to_unit)?;
+        if humidity_ratio < 0.0 {
+            return Err(to_unit(t_wet_bulb_error(
+                t_wet_bulb, t_dry_bulb, pressure, &options,
+            )));
+        }
+        Self::from_si(t_dry_bulb, humidity_ratio, pressure, unit, options)
     }
 
     /// Creates a new MoistAir instance from dry-bulb temperature and relative humidity
@@ -104,50 +531,132 @@ impl MoistAir {
         pressure: f64,
         unit: UnitSystem,
     ) -> Result<Self, PsychroidError> {
-        if !(0.0..=1.0 + TOLERANCE).contains(&relative_humidity) {
-            return Err(PsychroidError::InvalidRelativeHumidity(relative_humidity));
-        }
-        let humidity_ratio =
-            humidity_ratio_from_relative_humidity(t_dry_bulb, relative_humidity, pressure, unit)?;
-        Ok(MoistAir {
+        Self::from_t_dry_bulb_relative_humidity_with_options(
             t_dry_bulb,
-            humidity_ratio,
+            relative_humidity,
             pressure,
             unit,
-        })
+            ModelOptions::default(),
+        )
+    }
+
+    /// Creates a new MoistAir instance from dry-bulb temperature and relative humidity,
+    /// evaluated with the given model options
+    pub fn from_t_dry_bulb_relative_humidity_with_options(
+        t_dry_bulb: f64,
+        relative_humidity: f64,
+        pressure: f64,
+        unit: UnitSystem,
+        options: ModelOptions,
+    ) -> Result<Self, PsychroidError> {
+        let options = options.for_unit(unit);
+        check_range_t_dry_bulb(t_dry_bulb, unit)?;
+        check_relative_humidity(relative_humidity)?;
+        check_pressure(pressure, unit)?;
+        let t_dry_bulb = units::t_to_si(t_dry_bulb, unit);
+        let pressure = units::pressure_to_si(pressure, unit);
+        let humidity_ratio = humidity_ratio_from_relative_humidity(
+            t_dry_bulb,
+            relative_humidity,
+            pressure,
+            &options,
+        )
+        .map_err(units::error_from_si(unit, &options))?;
+        Self::from_si(t_dry_bulb, humidity_ratio, pressure, unit, options)
     }
 
     /// Creates a new MoistAir instance from dry-bulb and dew-point temperatures
+    ///
+    /// # Errors
+    /// * `InvalidTDewPoint` if the dew point is above the dry-bulb temperature
+    /// * `PressureBelowSaturation` if the dew point is not below the boiling point
     pub fn from_t_dry_bulb_t_dew_point(
         t_dry_bulb: f64,
         t_dew_point: f64,
         pressure: f64,
         unit: UnitSystem,
     ) -> Result<Self, PsychroidError> {
-        let humidity_ratio = humidity_ratio_from_t_dew_point(t_dew_point, pressure, unit)?;
-        Ok(MoistAir {
+        Self::from_t_dry_bulb_t_dew_point_with_options(
             t_dry_bulb,
+            t_dew_point,
+            pressure,
+            unit,
+            ModelOptions::default(),
+        )
+    }
+
+    /// Creates a new MoistAir instance from dry-bulb and dew-point temperatures,
+    /// evaluated with the given model options
+    pub fn from_t_dry_bulb_t_dew_point_with_options(
+        t_dry_bulb: f64,
+        t_dew_point: f64,
+        pressure: f64,
+        unit: UnitSystem,
+        options: ModelOptions,
+    ) -> Result<Self, PsychroidError> {
+        let options = options.for_unit(unit);
+        check_range_t_dry_bulb(t_dry_bulb, unit)?;
+        check_t_dew_point(t_dew_point, t_dry_bulb, unit)?;
+        check_pressure(pressure, unit)?;
+        let pressure = units::pressure_to_si(pressure, unit);
+        let humidity_ratio =
+            humidity_ratio_from_t_dew_point(units::t_to_si(t_dew_point, unit), pressure, &options)
+                .map_err(units::error_from_si(unit, &options))?;
+        Self::from_si(
+            units::t_to_si(t_dry_bulb, unit),
             humidity_ratio,
             pressure,
             unit,
-        })
+            options,
+        )
     }
 
     /// Creates a new MoistAir instance from dry-bulb temperature and specific enthalpy
+    ///
+    /// # Errors
+    /// * `InvalidSpecificEnthalpy` if the specific enthalpy is lower than that of dry air
     pub fn from_t_dry_bulb_enthalpy(
         t_dry_bulb: f64,
         specific_enthalpy: f64,
         pressure: f64,
         unit: UnitSystem,
-    ) -> Self {
-        let humidity_ratio =
-            humidity_ratio_from_specific_enthalpy(t_dry_bulb, specific_enthalpy, unit);
-        MoistAir {
+    ) -> Result<Self, PsychroidError> {
+        Self::from_t_dry_bulb_enthalpy_with_options(
             t_dry_bulb,
-            humidity_ratio,
+            specific_enthalpy,
             pressure,
             unit,
+            ModelOptions::default(),
+        )
+    }
+
+    /// Creates a new MoistAir instance from dry-bulb temperature and specific enthalpy,
+    /// evaluated with the given model options
+    pub fn from_t_dry_bulb_enthalpy_with_options(
+        t_dry_bulb: f64,
+        specific_enthalpy: f64,
+        pressure: f64,
+        unit: UnitSystem,
+        options: ModelOptions,
+    ) -> Result<Self, PsychroidError> {
+        let options = options.for_unit(unit);
+        check_range_t_dry_bulb(t_dry_bulb, unit)?;
+        check_finite("specific enthalpy", specific_enthalpy)?;
+        check_pressure(pressure, unit)?;
+        let t_dry_bulb = units::t_to_si(t_dry_bulb, unit);
+        let specific_enthalpy = units::specific_enthalpy_to_si(specific_enthalpy, unit, &options);
+        let pressure = units::pressure_to_si(pressure, unit);
+        let humidity_ratio = humidity_ratio_from_specific_enthalpy(
+            t_dry_bulb,
+            specific_enthalpy,
+            pressure,
+            &options,
+        );
+        if humidity_ratio < 0.0 {
+            let error = specific_enthalpy_error(specific_enthalpy, t_dry_bulb, pressure, &options);
+            return Err(units::error_from_si(unit, &options)(error));
         }
+        Self::from_si(t_dry_bulb, humidity_ratio, pressure, unit, options)
     }
 
     /// Creates a new MoistAir instance from specific enthalpy and relative humidity
@@ -157,472 +666,1580 @@ impl MoistAir {
         pressure: f64,
         unit: UnitSystem,
     ) -> Result<Self, PsychroidError> {
-        let t_dry_bulb = t_dry_bulb_from_specific_enthalpy_relative_humidity(
+        Self::from_specific_enthalpy_relative_humidity_with_options(
             specific_enthalpy,
             relative_humidity,
             pressure,
             unit,
-        )?;
-        let humidity_ratio =
-            humidity_ratio_from_specific_enthalpy(t_dry_bulb, specific_enthalpy, unit);
-        Ok(MoistAir {
-            t_dry_bulb,
-            humidity_ratio,
-            pressure,
-            unit,
-        })
-    }
-
-    /// Returns the humidity ratio of moist air
-    pub fn humidity_ratio(&self) -> f64 {
-        self.humidity_ratio
+            ModelOptions::default(),
+        )
     }
 
-    /// Returns the dry bulb temperature of moist air
-    pub fn t_dry_bulb(&self) -> f64 {
-        self.t_dry_bulb
+    /// Creates a new MoistAir instance from specific enthalpy and relative humidity,
+    /// evaluated with the given model options
+    pub fn from_specific_enthalpy_relative_humidity_with_options(
+        specific_enthalpy: f64,
+        relative_humidity: f64,
+        pressure: f64,
+        unit: UnitSystem,
+        options: ModelOptions,
+    ) -> Result<Self, PsychroidError> {
+        let options = options.for_unit(unit);
+        check_finite("specific enthalpy", specific_enthalpy)?;
+        check_relative_humidity(relative_humidity)?;
+        check_pressure(pressure, unit)?;
+        let specific_enthalpy = units::specific_enthalpy_to_si(specific_enthalpy, unit, &options);
+        let pressure = units::pressure_to_si(pressure, unit);
+        let t_dry_bulb = t_dry_bulb_from_specific_enthalpy_relative_humidity(
+            specific_enthalpy,
+            relative_humidity,
+            pressure,
+            &options,
+        )
+        .map_err(units::error_from_si(unit, &options))?;
+        // round-off of the solution may give a slightly negative humidity ratio for dry air
+        let humidity_ratio = humidity_ratio_from_specific_enthalpy(
+            t_dry_bulb,
+            specific_enthalpy,
+            pressure,
+            &options,
+        )
+        .max(0.0);
+        Self::from_si(t_dry_bulb, humidity_ratio, pressure, unit, options)
     }
 
-    /// Returns the specific enthalpy of moist air
-    ///
-    /// # Returns
-    /// Specific enthalpy \\(h\\), in \\( \\mathrm{kJ/kg_{da}} \\) (SI) or \\( \\mathrm{Btu/lb_{da}} \\) (IP)
-    ///
-    /// # Formula
+    /// Creates a new MoistAir instance from any pair of independent properties
     ///
-    /// $$
-    /// \\begin{align}
-    /// h &= 1.006~t_\mathrm{da} + W (2501.0 + 1.860~t_\mathrm{da}) \\quad &\\text{(SI)} \\\\
-    /// h &= 0.240~t_\mathrm{da} + W (1061.0 + 0.444~t_\mathrm{da}) \\quad &\\text{(IP)}
-    /// \\end{align}
-    /// $$
+    /// If the dry-bulb temperature is one of the properties, the humidity ratio is calculated directly.
+    /// Otherwise, the dry-bulb temperature is searched using Newton-Raphson method safeguarded by bisection
+    /// in the range of feasible dry-bulb temperatures,
+    /// with the humidity ratio determined by one property and the residual evaluated on the other.
     ///
-    /// where:
-    /// - \\(t_\mathrm{da}\\) - dry bulb temperature in \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
-    /// - \\(W\\) - humidity ratio in \\( \\mathrm{kg_w / kg_{da}} \\) (SI) or \\( \\mathrm{lb_w / lb_{da}} \\) (IP)
+    /// # Arguments
+    /// * `pair` - Pair of independent properties
+    /// * `pressure` - Atmospheric pressure  \\(\\mathrm{Pa}\\) (SI) or  \\(\\mathrm{Psi}\\) (IP)
+    /// * `unit` - Unit system (SI or IP)
     ///
-    /// Reference: ASHRAE Fundamentals Handbook (2017) Chapter 1
-    pub fn specific_enthalpy(&self) -> f64 {
-        specific_enthalpy_from_humidity_ratio(self.t_dry_bulb, self.humidity_ratio, self.unit)
+    /// # Errors
+    /// * `DependentProperties` if the properties are not independent (e.g. humidity ratio and dew point)
+    /// * `NonFiniteInput`, `InvalidPressure` or the variant of the property if an input is out of range
+    /// * `InvalidRelativeHumidity` or `InvalidTDryBulb` if the resulting state is not feasible,
+    ///   or `OutOfRange` if the partial pressure of water vapor reaches the total pressure
+    /// * `SolverFailure` if the iterative solution does not converge
+    pub fn from_pair(
+        pair: PropertyPair,
+        pressure: f64,
+        unit: UnitSystem,
+    ) -> Result<Self, PsychroidError> {
+        Self::from_pair_with_options(pair, pressure, unit, ModelOptions::default())
     }
 
-    /// Returns the relative humidity of moist air
-    ///
-    /// # Returns
-    /// Relative humidity \\(\\phi\\) [0.0 - 1.0]
+    /// Creates a new MoistAir instance from any pair of independent properties,
+    /// evaluated with the given model options (e.g. the real gas model)
     ///
-    /// # Formula
-    /// $$
-    /// \\phi = \\frac{p_\mathrm{w}}{p_\mathrm{ws}} = \\frac{pW}{(0.621945 + W) p_\mathrm{ws}}
-    /// $$
+    /// # Example
+    /// ```
+    /// use psychroid::{GasModel, ModelOptions, MoistAir, Property, PropertyPair, UnitSystem};
     ///
-    /// where:
-    /// - \\(p_\mathrm{w}\\) - partial pressure of water vapor
-    /// - \\(p_\mathrm{ws}\\) - saturation pressure of water vapor
-    /// - \\(p\\) - total pressure
-    /// - \\(W\\) - humidity ratio (non-dimensional)
-    /// - 0.621945 - ratio of molecular mass (non-dimensional) of water vapor to dry air
-    ///
-    pub fn relative_humidity(&self) -> Result<f64, PsychroidError> {
-        let value = relative_humidity_from_humidity_ratio(
-            self.t_dry_bulb,
-            self.humidity_ratio,
-            self.pressure,
-            self.unit,
-        )?;
-        if !(0.0..=1.0 + TOLERANCE).contains(&value) {
-            return Err(PsychroidError::InvalidRelativeHumidity(value));
+    /// let options = ModelOptions {
+    ///     gas_model: GasModel::Real,
+    ///     ..Default::default()
+    /// };
+    /// let pair = PropertyPair(Property::TDryBulb(25.0), Property::RelativeHumidity(0.5));
+    /// let air = MoistAir::from_pair_with_options(pair, 1.0E+06, UnitSystem::SI, options).unwrap();
+    /// assert_eq!(air.options().gas_model, GasModel::Real);
+    /// ```
+    pub fn from_pair_with_options(
+        pair: PropertyPair,
+        pressure: f64,
+        unit: UnitSystem,
+        options: ModelOptions,
+    ) -> Result<Self, PsychroidError> {
+        let options = options.for_unit(unit);
+        let PropertyPair(first, second) = pair;
+        check_property(first, unit)?;
+        check_property(second, unit)?;
+        check_pressure(pressure, unit)?;
+        if !first.is_independent_of(&second) {
+            return Err(PsychroidError::DependentProperties(first, second));
         }
-        Ok(value)
-    }
-
-    /// Returns the dew point temperature of moist air
-    pub fn t_dew_point(&self) -> Result<f64, PsychroidError> {
-        t_dew_point_from_humidity_ratio(self.humidity_ratio, self.pressure, self.unit)
+        let pressure = units::pressure_to_si(pressure, unit);
+        let (t_dry_bulb, humidity_ratio) = Self::solve_pair(
+            units::property_to_si(first, unit, &options),
+            units::property_to_si(second, unit, &options),
+            pressure,
+            &options,
+        )
+        .map_err(units::error_from_si(unit, &options))?;
+        Self::from_si(t_dry_bulb, humidity_ratio, pressure, unit, options)
     }
 
-    /// Returns the wet bulb temperature of moist air
-    pub fn t_wet_bulb(&self) -> Result<f64, PsychroidError> {
-        t_wet_bulb_from_humidity_ratio(
-            self.t_dry_bulb,
-            self.humidity_ratio,
-            self.pressure,
-            self.unit,
-        )
+    /// Solves the dry-bulb temperature and the humidity ratio from a pair of independent properties,
+    /// all in SI units, see [`MoistAir::from_pair`]
+    fn solve_pair(
+        first: Property,
+        second: Property,
+        pressure: f64,
+        options: &ModelOptions,
+    ) -> Result<(f64, f64), PsychroidError> {
+        let unit = UnitSystem::SI;
+        let (provider, target) = match first.rank() <= second.rank() {
+            true => (first, second),
+            false => (second, first),
+        };
+        let t_dry_bulb = match provider {
+            Property::TDryBulb(t_dry_bulb) => t_dry_bulb,
+            _ => t_dry_bulb_from_property_pair(provider, target, pressure, options)?,
+        };
+        check_range_t_dry_bulb(t_dry_bulb, unit)?;
+        // the property which determines the humidity ratio at the dry-bulb temperature
+        let humidity = match provider {
+            Property::TDryBulb(_) => target,
+            _ => provider,
+        };
+        match humidity {
+            Property::TWetBulb(t_wet_bulb) => {
+                check_t_wet_bulb(t_wet_bulb, t_dry_bulb, pressure, unit, options)?
+            }
+            Property::TDewPoint(t_dew_point) => check_t_dew_point(t_dew_point, t_dry_bulb, unit)?,
+            _ => {}
+        }
+        let humidity_ratio = humidity_ratio_from_property(t_dry_bulb, humidity, pressure, options)?;
+        if humidity_ratio < 0.0 {
+            return Err(match humidity {
+                Property::TWetBulb(t_wet_bulb) => {
+                    t_wet_bulb_error(t_wet_bulb, t_dry_bulb, pressure, options)
+                }
+                Property::SpecificEnthalpy(h) => {
+                    specific_enthalpy_error(h, t_dry_bulb, pressure, options)
+                }
+                Property::SpecificVolume(v) => {
+                    specific_volume_error(v, t_dry_bulb, pressure, options)
+                }
+                _ => PsychroidError::InvalidHumidityRatio(humidity_ratio),
+            });
+        }
+        // only the humidity ratio, combined with the dry-bulb temperature or the specific enthalpy,
+        // determines the total water content of supersaturated states
+        let total_water_content = matches!(
+            (provider, target),
+            (Property::TDryBulb(_), Property::HumidityRatio(_))
+                | (Property::HumidityRatio(_), Property::SpecificEnthalpy(_))
+        );
+        let saturation_humidity_ratio = saturation_humidity_ratio(t_dry_bulb, pressure, options);
+        if !total_water_content && humidity_ratio > saturation_humidity_ratio * (1.0 + TOLERANCE) {
+            return Err(PsychroidError::InvalidRelativeHumidity(
+                humidity_ratio / saturation_humidity_ratio,
+            ));
+        }
+        Ok((t_dry_bulb, humidity_ratio))
     }
 
-    /// Returns the specific volume of moist air
+    /// Creates a new MoistAir instance from a pair of independent properties,
+    /// of which the humidity ratio, the specific enthalpy and the specific volume are given on the basis.
+    /// [`Property::HumidityRatio`] stands for the humidity content on the basis,
+    /// e.g. the mass of water per unit mass of moist air for [`Basis::MoistAir`].
     ///
-    /// # Returns
-    /// Specific volume \\(v\\) is the specific volume \\(V/M_{da}\\) in \\( \\mathrm{m^3/kg_{da}} \\) (SI) or \\( \\mathrm{ft^3/lb_{da}} \\) (IP)
+    /// # Example
+    /// ```
+    /// use psychroid::{Basis, MoistAir, Property, PropertyPair, UnitSystem};
     ///
-    /// # Formula
-    /// $$
-    /// \\begin{align}
-    /// v = 0.287042 (t_\mathrm{db} + 273.15) (1 + 1.607858 W) / p \\quad &\\text{(SI)} \\\\
-    /// v = 0.370486 (t_\mathrm{db} + 459.67) (1 + 1.607858 W) / p \\quad &\\text{(IP)}
-    /// \\end{align}
-    /// $$
-    /// where:
-    /// -
-    /// - \\(t_\\mathrm{db}\\) - dry bulb temperature in \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
-    /// - \\(W\\) -  humidity ratio in \\( \\mathrm{kg_w / kg_{da}} \\) (SI) or \\( \\mathrm{lb_w / lb_{da}} \\) (IP)
-    /// - \\(p\\) - total pressure in \\( \\mathrm{kPa} \\) (SI) or \\( \\mathrm{Psi} \\) (IP)
+    /// let air = MoistAir::from_t_dry_bulb_relative_humidity(25.0, 0.5, 101325.0, UnitSystem::SI).unwrap();
+    /// let h = air.specific_enthalpy_on(Basis::Volume);
+    /// let pair = PropertyPair(Property::TDryBulb(25.0), Property::SpecificEnthalpy(h));
+    /// let result = MoistAir::from_pair_on_basis(pair, Basis::Volume, 101325.0, UnitSystem::SI).unwrap();
+    /// assert!((result.humidity_ratio() - air.humidity_ratio()).abs() < 1.0E-9);
+    /// ```
+    pub fn from_pair_on_basis(
+        pair: PropertyPair,
+        basis: Basis,
+        pressure: f64,
+        unit: UnitSystem,
+    ) -> Result<Self, PsychroidError> {
+        Self::from_pair_on_basis_with_options(pair, basis, pressure, unit, ModelOptions::default())
+    }
+
+    /// Creates a new MoistAir instance from a pair of independent properties given on the basis,
+    /// evaluated with the given model options
     ///
-    pub fn density(&self) -> f64 {
-        let specific_volume = match self.unit {
-            UnitSystem::SI => {
-                // specific volume in m³/kg_da, pressure in kPa
-                0.287042 * (self.t_dry_bulb + 273.15) * (1.0 + 1.607858 * self.humidity_ratio)
-                    / (self.pressure * 0.001)
+    /// The amount of the basis per unit mass of dry air depends on the state,
+    /// and it is determined by fixed-point iteration.
+    pub fn from_pair_on_basis_with_options(
+        pair: PropertyPair,
+        basis: Basis,
+        pressure: f64,
+        unit: UnitSystem,
+        options: ModelOptions,
+    ) -> Result<Self, PsychroidError> {
+        let PropertyPair(first, second) = pair;
+        // initial guess of the amount of the basis from dry air
+        let mut amount = match (basis, first, second) {
+            (Basis::Volume, Property::TDryBulb(t_dry_bulb), _)
+            | (Basis::Volume, _, Property::TDryBulb(t_dry_bulb)) => {
+                let t_dry_bulb = units::t_to_si(t_dry_bulb, unit);
+                let specific_volume = specific_volume_from_humidity_ratio(
+                    t_dry_bulb,
+                    0.0,
+                    units::pressure_to_si(pressure, unit),
+                    &options,
+                );
+                units::specific_volume_from_si(specific_volume, unit)
             }
-            UnitSystem::IP => {
-                // specific volume in ft³/lb_da
-                0.370486 * (self.t_dry_bulb + 459.67) * (1.0 + 1.607858 * self.humidity_ratio)
-                    / self.pressure
+            (Basis::Volume, _, _) => {
+                let specific_volume = specific_volume_from_humidity_ratio(
+                    20.0,
+                    0.0,
+                    units::pressure_to_si(pressure, unit),
+                    &options,
+                );
+                units::specific_volume_from_si(specific_volume, unit)
             }
+            _ => 1.0,
         };
-        1.0 / specific_volume * (1.0 + self.humidity_ratio)
+        let mut residual = f64::NAN;
+        for _ in 0..MAX_ITER_COUNT {
+            let pair = PropertyPair(
+                first.to_dry_air_basis(basis, amount)?,
+                second.to_dry_air_basis(basis, amount)?,
+            );
+            let moist_air = Self::from_pair_with_options(pair, pressure, unit, options)?;
+            let next = moist_air.basis_amount(basis);
+            residual = next - amount;
+            if residual.abs() < TOLERANCE * amount {
+                return Ok(moist_air);
+            }
+            amount = next;
+        }
+        Err(PsychroidError::SolverFailure {
+            method: SolverMethod::FixedPoint,
+            reason: SolverFailureReason::NoConvergence,
+            iterations: MAX_ITER_COUNT,
+            residual,
+        })
+    }
+
+    /// Returns the amount of the basis per unit mass of dry air, i.e.
+    /// 1 for dry air, \\(1 + W\\) for moist air and \\(v\\) for volume
+    fn basis_amount(&self, basis: Basis) -> f64 {
+        match basis {
+            Basis::DryAir => 1.0,
+            Basis::MoistAir => 1.0 + self.humidity_ratio,
+            Basis::Volume => self.specific_volume(),
+        }
     }
 
-    /// Changes the unit system and converts all properties to the new unit system
+    /// Returns true if both states have the same unit system and model options,
+    /// and the absolute temperature, humidity ratio and pressure agree within the relative tolerance
     ///
-    /// # Arguments
-    /// * `unit` - The new unit system to convert to (SI or IP)
+    /// # Example
+    /// ```
+    /// use psychroid::{MoistAir, Property, PropertyPair, UnitSystem};
     ///
-    /// # Conversions performed
-    /// - Temperature: \\(^\\circ \\mathrm{F}\\) ↔ \\(^\\circ \\mathrm{C}\\)
-    /// - Pressure: \\(\\mathrm{Psi}\\) ↔ \\(\\mathrm{Pa}\\)
-    /// - Humidity ratio remains dimensionless
+    /// let air = MoistAir::from_t_dry_bulb_relative_humidity(25.0, 0.5, 101325.0, UnitSystem::SI).unwrap();
+    /// let pair = PropertyPair(Property::TDryBulb(25.0), Property::TWetBulb(air.t_wet_bulb().unwrap()));
+    /// let same = MoistAir::from_pair(pair, 101325.0, UnitSystem::SI).unwrap();
+    /// assert!(same.approx_eq(&air, 1.0E-6));
+    /// ```
+    pub fn approx_eq(&self, other: &MoistAir, max_relative: f64) -> bool {
+        let is_close =
+            |a: f64, b: f64| (a - b).abs() <= f64::EPSILON.max(max_relative * a.abs().max(b.abs()));
+        self.unit == other.unit
+            && self.options == other.options
+            && is_close(
+                t_celsius_to_t_kelvin(self.t_dry_bulb),
+                t_celsius_to_t_kelvin(other.t_dry_bulb),
+            )
+            && is_close(self.humidity_ratio, other.humidity_ratio)
+            && is_close(self.pressure, other.pressure)
+    }
+
+    /// Returns the humidity ratio of moist air, i.e. the total water content including condensed water
+    pub fn humidity_ratio(&self) -> f64 {
+        self.humidity_ratio
+    }
+
+    /// Returns the humidity ratio of the water vapor,
+    /// which is limited to the saturation humidity ratio for fog and ice fog states
+    pub fn vapor_humidity_ratio(&self) -> f64 {
+        let saturation_humidity_ratio =
+            saturation_humidity_ratio(self.t_dry_bulb, self.pressure, &self.options);
+        self.humidity_ratio.min(saturation_humidity_ratio)
+    }
+
+    /// Returns the mass of condensed water (liquid water or ice) suspended in moist air per unit mass of dry air,
+    /// in \\( \\mathrm{kg_w / kg_{da}} \\) (SI) or \\( \\mathrm{lb_w / lb_{da}} \\) (IP)
+    pub fn condensed_water_content(&self) -> f64 {
+        self.humidity_ratio - self.vapor_humidity_ratio()
+    }
+
+    /// Returns the phase of the condensed water, or `None` if moist air is not supersaturated
     ///
     /// # Example
     /// ```
-    /// use psychroid::{MoistAir, UnitSystem};
-    ///
-    /// let mut air = MoistAir::new(
-    ///     25.0,     // 25°C
-    ///     0.007,    // humidity ratio
-    ///     101325.0, // Pa
-    ///     UnitSystem::SI
-    /// );
+    /// use psychroid::{CondensedPhase, MoistAir, UnitSystem};
     ///
-    /// // Convert to IP units
-    /// air.set_unit(UnitSystem::IP);
-    /// // Now temperature is in °F, pressure in Psi
+    /// let fog = MoistAir::from_t_dry_bulb_humidity_ratio(10.0, 0.01, 101325.0, UnitSystem::SI).unwrap();
+    /// assert_eq!(fog.condensed_phase(), Some(CondensedPhase::Liquid));
+    /// assert!(fog.condensed_water_content() > 0.0);
+    /// assert!((fog.relative_humidity().unwrap() - 1.0).abs() < 1.0E-9);
     /// ```
-    pub fn set_unit(&mut self, unit: UnitSystem) {
-        if self.unit != unit {
-            self.unit = unit;
-            self.t_dry_bulb = match unit {
-                UnitSystem::SI => t_fahrenheit_to_t_celsius(self.t_dry_bulb),
-                UnitSystem::IP => t_celsius_to_t_fahrenheit(self.t_dry_bulb),
-            };
-            self.pressure = match unit {
-                UnitSystem::SI => self.pressure * 6894.75729, // Psi to Pa
-                UnitSystem::IP => self.pressure / 6894.75729, // Pa to Psi
-            };
+    pub fn condensed_phase(&self) -> Option<CondensedPhase> {
+        if self.condensed_water_content() <= 0.0 {
+            return None;
+        }
+        match is_over_water(self.t_dry_bulb, &self.options) {
+            true => Some(CondensedPhase::Liquid),
+            false => Some(CondensedPhase::Ice),
         }
     }
 
-    /// Calculates the heating energy required to change the dry-bulb temperature to a target temperature
-    /// This method modifies the dry-bulb temperature of the instance to the target temperature
-    ///
-    /// # Arguments
-    /// * `mda` - Mass flow rate of dry air \\( \\mathrm{kg/s} \\) (SI) or \\( \\mathrm{lb/h} \\) (IP)
-    /// * `t1` - Target dry-bulb temperature \\(^\\circ \\mathrm{C}\\)  (SI) or \\(^\\circ \\mathrm{F}\\)  (IP)
+    /// Returns the dry bulb temperature of moist air
+    pub fn t_dry_bulb(&self) -> f64 {
+        units::t_from_si(self.t_dry_bulb, self.unit)
+    }
+
+    /// Returns the specific enthalpy of moist air
     ///
     /// # Returns
-    /// Heating energy \\(q\\) required in \\( \\mathrm{kW} \\) (SI) or \\( \\mathrm{Btu/h} \\) (IP)
+    /// Specific enthalpy \\(h\\), in \\( \\mathrm{kJ/kg_{da}} \\) (SI) or \\( \\mathrm{Btu/lb_{da}} \\) (IP)
     ///
-    pub fn heating_t1(&mut self, mda: f64, t1: f64) -> f64 {
-        let h0 = self.specific_enthalpy();
-        self.t_dry_bulb = t1;
-        let h1 = self.specific_enthalpy();
-        mda * (h1 - h0)
-    }
-
-    /// Calculates the heating energy required to change the dry-bulb temperature by a given amount
-    /// This method modifies the dry-bulb temperature of the instance to the target temperature
+    /// # Formula
     ///
-    /// # Arguments
-    /// * `mda` - Mass flow rate of dry air \\( \\mathrm{kg/s} \\) (SI) or \\( \\mathrm{lb/h} \\) (IP)
-    /// * `dt` - Temperature change \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
+    /// $$
+    /// \\begin{align}
+    /// h &= 1.006~t_\mathrm{da} + W (2501.0 + 1.860~t_\mathrm{da})
+    /// \\end{align}
+    /// $$
     ///
-    /// # Returns
-    /// Heating energy \\(q\\) required in \\( \\mathrm{kW} \\) (SI) or \\( \\mathrm{Btu/h} \\) (IP)
+    /// where:
+    /// - \\(t_\mathrm{da}\\) - dry bulb temperature in \\(^\\circ \\mathrm{C}\\)
+    /// - \\(W\\) - humidity ratio in \\( \\mathrm{kg_w / kg_{da}} \\)
+    ///
+    /// Reference: ASHRAE Fundamentals Handbook (2017) Chapter 1
+    ///
+    /// In IP units, [`EnthalpyModel::Linear`] evaluates \\(h = 0.240~t + W (1061.0 + 0.444~t)\\) of the IP edition,
+    /// with \\(t\\) in \\(^\\circ \\mathrm{F}\\) and referred to dry air at \\(0~^\\circ \\mathrm{F}\\).
     ///
-    pub fn heating_dt(&mut self, mda: f64, dt: f64) -> f64 {
-        let h0 = self.specific_enthalpy();
-        self.t_dry_bulb += dt;
-        let h1 = self.specific_enthalpy();
-        mda * (h1 - h0)
+    /// For [`GasModel::Real`], the residual enthalpy from the second virial coefficients is added.
+    pub fn specific_enthalpy(&self) -> f64 {
+        units::specific_enthalpy_from_si(self.specific_enthalpy_si(), self.unit, &self.options)
     }
 
-    /// Calculates the temperature change for a given heating energy input
-    ///
-    /// # Arguments
-    /// * `mda` - Mass flow rate of dry air \\( \\mathrm{kg/s} \\) (SI) or \\( \\mathrm{lb/h} \\) (IP)
-    /// * `q` - Heating energy input \\( \\mathrm{kW} \\) (SI) or \\( \\mathrm{Btu/h} \\) (IP)
+    /// Returns the specific enthalpy of moist air in \\( \\mathrm{kJ/kg_{da}} \\)
+    pub(crate) fn specific_enthalpy_si(&self) -> f64 {
+        specific_enthalpy_from_humidity_ratio(
+            self.t_dry_bulb,
+            self.humidity_ratio,
+            self.pressure,
+            &self.options,
+        )
+    }
+
+    /// Returns the specific enthalpy of moist air on the basis, see [`Basis`] for the units
     ///
-    /// # Returns
-    /// Estimated new dry-bulb temperature \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
+    /// # Formula
+    /// $$
+    /// h_\\mathrm{moist} = \\frac{h}{1 + W}, \\quad h_\\mathrm{volume} = \\frac{h}{v}
+    /// $$
+    pub fn specific_enthalpy_on(&self, basis: Basis) -> f64 {
+        self.specific_enthalpy() / self.basis_amount(basis)
+    }
+
+    /// Returns the total water content, including condensed water, on the basis, see [`Basis`] for the units.
+    /// This is the humidity ratio for [`Basis::DryAir`].
     ///
     /// # Formula
-    /// Temperature change is estimated using:
     /// $$
-    /// \begin{align}
-    /// \\Delta T &= \\frac{\\Delta h}{1.006 + 1.860 W} \\quad &\\text{(SI)} \\\\
-    /// \\Delta T &= \\frac{\\Delta h}{0.240 + 0.444 W} \\quad &\\text{(IP)}
-    /// \end{align}
+    /// W_\\mathrm{moist} = \\frac{W}{1 + W}, \\quad W_\\mathrm{volume} = \\frac{W}{v}
     /// $$
-    /// where:
-    /// - \\(\\Delta h = q/\\dot{m}_{da}\\) is the specific enthalpy change
-    /// - \\(W\\) is the humidity ratio
+    pub fn humidity_content(&self, basis: Basis) -> f64 {
+        self.humidity_ratio / self.basis_amount(basis)
+    }
+
+    /// Returns the specific volume of moist air on the basis, see [`Basis`] for the units.
+    /// This is 1 for [`Basis::Volume`].
     ///
-    /// # Note
-    /// This provides an initial estimate and may need iteration for precise results
-    pub fn heating_q(&mut self, mda: f64, q: f64) {
-        let dh = q / mda; // kJ/kg_da (SI) or Btu/lb_da (IP)
-        let dt = match self.unit {
-            UnitSystem::SI => dh / (1.006 + 1.860 * self.humidity_ratio),
-            UnitSystem::IP => dh / (0.240 + 0.444 * self.humidity_ratio),
-        };
-        // new dry bulb temperature
-        self.t_dry_bulb += dt;
+    /// # Formula
+    /// $$
+    /// v_\\mathrm{moist} = \\frac{v}{1 + W} = \\frac{1}{\\rho}
+    /// $$
+    pub fn specific_volume_on(&self, basis: Basis) -> f64 {
+        self.specific_volume() / self.basis_amount(basis)
     }
 
-    pub fn cooling_t1(&mut self, mda: f64, t1: f64) -> Result<f64, PsychroidError> {
-        let t_dew_point = self.t_dew_point()?;
-        let h0 = self.specific_enthalpy();
-        if t1 < t_dew_point {
-            self.humidity_ratio =
-                humidity_ratio_from_relative_humidity(t1, 1.0, self.pressure, self.unit)?;
-        }
-        self.t_dry_bulb = t1;
-        let h1 = self.specific_enthalpy();
-        Ok(mda * (h0 - h1))
+    /// Returns the total pressure of moist air, in \\(\\mathrm{Pa}\\) (SI) or \\(\\mathrm{Psi}\\) (IP)
+    pub fn pressure(&self) -> f64 {
+        units::pressure_from_si(self.pressure, self.unit)
     }
 
-    pub fn cooling_dt(&mut self, mda: f64, dt: f64) -> Result<f64, PsychroidError> {
-        let t_dew_point = self.t_dew_point()?;
-        let h0 = self.specific_enthalpy();
-        let t1 = self.t_dry_bulb - dt;
-        if t1 < t_dew_point {
-            self.humidity_ratio =
-                humidity_ratio_from_relative_humidity(t1, 1.0, self.pressure, self.unit)?;
-        }
-        self.t_dry_bulb = t1;
-        let h1 = self.specific_enthalpy();
-        Ok(mda * (h0 - h1))
+    /// Returns the unit system of moist air
+    pub fn unit(&self) -> UnitSystem {
+        self.unit
     }
 
-    /// Calculates the temperature change for a given cooling energy removal
-    /// If the new temperature is expected to be below the dew point,
-    /// the new dry-bulb temperature is calculated based on the specific enthalpy and relative humidity of 1.0.
-    ///
-    /// # Arguments
-    /// * `mda` - Mass flow rate of dry air \\( \\mathrm{kg/s} \\) (SI) or \\( \\mathrm{lb/h} \\) (IP)
-    /// * `q` - Heating energy input \\( \\mathrm{kW} \\) (SI) or \\( \\mathrm{Btu/h} \\) (IP)
+    /// Returns the model options of moist air
+    pub fn options(&self) -> ModelOptions {
+        self.options
+    }
+
+    /// Returns the specific entropy of moist air
     ///
     /// # Returns
-    /// Estimated new dry-bulb temperature \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
+    /// Specific entropy \\(s\\), in \\( \\mathrm{kJ/(kg_{da} \\cdot K)} \\) (SI) or \\( \\mathrm{Btu/(lb_{da} \\cdot {}^\\circ R)} \\) (IP)
     ///
     /// # Formula
-    /// Temperature change is estimated using:
+    /// Moist air is treated as an ideal mixture of dry air and water vapor,
+    /// each evaluated at its partial pressure:
     /// $$
-    /// \begin{align}
-    /// \\Delta T &= \\frac{\\Delta h}{1.006 + 1.860 W} \\quad &\\text{(SI)} \\\\
-    /// \\Delta T &= \\frac{\\Delta h}{0.240 + 0.444 W} \\quad &\\text{(IP)}
-    /// \end{align}
+    /// s = c_{p,\\mathrm{da}} \\ln \\frac{T}{T_\\mathrm{ref}} - R_\\mathrm{da} \\ln \\frac{p_\\mathrm{da}}{p_\\mathrm{ref}} +
+    /// W \\left( s_{g,\\mathrm{tp}} + c_{p,\\mathrm{w}} \\ln \\frac{T}{T_\\mathrm{tp}} - R_\\mathrm{w} \\ln \\frac{p_\\mathrm{w}}{p_\\mathrm{tp}} \\right)
     /// $$
     /// where:
-    /// - \\(\\Delta h = -q/\\dot{m}_{da}\\) -  specific enthalpy change
-    /// - \\(W\\) -  humidity ratio
-    pub fn cooling_q(&mut self, mda: f64, q: f64) -> Result<(), PsychroidError> {
-        let dh = q / mda; // kJ/s
-        let h0 = self.specific_enthalpy();
-        let h1 = h0 - dh;
-        let dt = match self.unit {
-            UnitSystem::SI => dh / (1.006 + 1.860 * self.humidity_ratio),
-            UnitSystem::IP => dh / (0.240 + 0.444 * self.humidity_ratio),
+    /// - \\(T_\\mathrm{ref},~p_\\mathrm{ref}\\) - reference state of dry air, \\(0~^\\circ \\mathrm{C}\\) (SI) or \\(0~^\\circ \\mathrm{F}\\) (IP) and standard atmosphere
+    /// - \\(T_\\mathrm{tp},~p_\\mathrm{tp}\\) - triple point of water, where saturated liquid water has zero entropy
+    /// - \\(s_{g,\\mathrm{tp}}\\) - entropy of saturated water vapor at the triple point
+    ///
+    /// The reference states are the same as in ASHRAE Fundamentals Handbook (2017) Chapter 1, Table 2.
+    /// For fog and ice fog, \\(W\\) is the humidity ratio of the water vapor,
+    /// and the entropy of the condensed water is added.
+    ///
+    /// The formula is that of [`EnthalpyModel::Linear`] and [`GasModel::Ideal`]. The entropy follows the same models
+    /// as [`MoistAir::specific_enthalpy`]: for [`EnthalpyModel::VariableHeatCapacity`], \\(c_p \\ln (T / T_\\mathrm{ref})\\)
+    /// is replaced by the integral of \\(c_p / T\\), and for [`GasModel::Real`], the residual entropy
+    /// \\(-p~dB_\\mathrm{m}/dT\\) of the second virial coefficients is added.
+    pub fn specific_entropy(&self) -> f64 {
+        units::specific_entropy_from_si(self.specific_entropy_si(), self.unit, &self.options)
+    }
+
+    /// Returns the specific entropy in \\( \\mathrm{kJ/(kg_{da} \\cdot K)} \\), see [`MoistAir::specific_entropy`]
+    pub(crate) fn specific_entropy_si(&self) -> f64 {
+        let s_gas = specific_entropy_gas(
+            self.t_dry_bulb,
+            self.vapor_humidity_ratio(),
+            self.pressure,
+            &self.options,
+        );
+        // condensed water, consistent with the enthalpy of liquid water and ice
+        let t_abs = t_celsius_to_t_kelvin(self.t_dry_bulb);
+        let t_tp = t_celsius_to_t_kelvin(TRIPLE_POINT_WATER_SI);
+        let (cp_liquid, cp_ice, h_fusion) = (4.186, 2.100, 333.4);
+        let s_c = match self.condensed_phase() {
+            Some(CondensedPhase::Liquid) => cp_liquid * (t_abs / t_tp).ln(),
+            Some(CondensedPhase::Ice) => -h_fusion / t_tp + cp_ice * (t_abs / t_tp).ln(),
+            None => 0.0,
         };
-        let t1 = self.t_dry_bulb - dt;
-        let t_dew_point = self.t_dew_point()?;
-        if t1 < t_dew_point {
-            self.t_dry_bulb = t_dry_bulb_from_specific_enthalpy_relative_humidity(
-                h1,
-                1.0,
-                self.pressure,
-                self.unit,
-            )?;
-            self.humidity_ratio = humidity_ratio_from_relative_humidity(
-                self.t_dry_bulb,
-                1.0,
-                self.pressure,
-                self.unit,
-            )?;
-        } else {
-            self.t_dry_bulb = t1;
-        }
-        Ok(())
+        s_gas + self.condensed_water_content() * s_c
     }
 
-    /// Calculates the state change when adding water to moist air (adiabatic humidification).
-    /// This method modifies both temperature and humidity ratio of the instance.
+    /// Returns the flow exergy of moist air relative to a dead state (ambient air)
     ///
     /// # Arguments
-    /// * `mda` - Mass flow rate of dry air \\(\\mathrm{kg/s}\\) (SI) or \\(\\mathrm{lb/h}\\) (IP)
-    /// * `water` - Mass of water added \\(\\mathrm{kg_w/s}\\) (SI) or \\(\\mathrm{lb_w/h}\\) (IP)
+    /// * `dead_state` - Ambient moist air in the same unit system
     ///
-    /// # Description
-    /// Calculates the temperature and humidity ratio changes when water is added to an air stream.
-    /// The process is assumed to be adiabatic (constant enthalpy).
+    /// # Returns
+    /// Flow exergy per unit mass of dry air, split into thermal, mechanical and chemical parts,
+    /// in \\( \\mathrm{kJ/kg_{da}} \\) (SI) or \\( \\mathrm{Btu/lb_{da}} \\) (IP)
     ///
     /// # Formula
-    /// It is assumed that the process is adiabatic and the enthalpy remains constant.
-    /// Based on this assumption, the temperature after humidification is calculated by
     /// $$
-    /// \begin{align}
-    /// T_1 &= \frac{(1.006 + 1.860~W_0)~T_0 - 2501.0 (W_1 - W_0)}{1.006 + 1.860~W_1} \quad &\text{(SI)} \\\\
-    /// T_1 &= \frac{(0.240 + 0.444~W_0)~T_0 - 1061.0 (W_1 - W_0)}{0.240 + 0.444~W_1} \quad &\text{(IP)}
-    /// \end{align}
+    /// \\begin{align}
+    /// e_\\mathrm{th} &= h(T, p) - h(T_0, p) - T_0 \\left[ s(T, p) - s(T_0, p) \\right] \\\\
+    /// e_\\mathrm{mech} &= h(T_0, p) - h(T_0, p_0) - T_0 \\left[ s(T_0, p) - s(T_0, p_0) \\right] \\\\
+    /// e_\\mathrm{ch} &= R_\\mathrm{da} T_0 \\left[ (1 + \\tilde{W}) \\ln \\frac{1 + \\tilde{W}_0}{1 + \\tilde{W}} + \\tilde{W} \\ln \\frac{\\tilde{W}}{\\tilde{W}_0} \\right]
+    /// \\end{align}
     /// $$
-    /// where:
-    /// - \\(T_0,~T_1\\) are initial and final temperatures
-    /// - \\(W_0,~W_1\\) are initial and final humidity ratios
+    /// where \\(h\\) and \\(s\\) are the specific enthalpy and entropy at the humidity ratio \\(W\\) of this state,
+    /// following the same models as [`MoistAir::specific_enthalpy`] and [`MoistAir::specific_entropy`],
+    /// \\(\\tilde{W} = 1.607858~W\\) is the molar humidity ratio, and the subscript 0 denotes the dead state.
+    /// For [`EnthalpyModel::Linear`] and [`GasModel::Ideal`], the thermal and mechanical parts reduce to
+    /// \\((c_{p,\\mathrm{da}} + W c_{p,\\mathrm{w}}) T_0 (T/T_0 - 1 - \\ln (T/T_0))\\) and
+    /// \\((1 + \\tilde{W}) R_\\mathrm{da} T_0 \\ln (p/p_0)\\). The chemical part is that of an ideal mixture.
     ///
-    pub fn humidify_adiabatic(&mut self, mda: f64, water: f64) -> Result<(), PsychroidError> {
-        let w0 = self.humidity_ratio;
-        let w1 = w0 + water / mda;
-
-        self.t_dry_bulb = match self.unit {
-            UnitSystem::SI => {
-                ((1.006 + 1.860 * w0) * self.t_dry_bulb - 2051.0 * (w1 - w0)) / (1.006 + 1.860 * w1)
-            }
-            UnitSystem::IP => {
-                ((0.240 + 0.444 * w0) * self.t_dry_bulb - 1061.0 * (w1 - w0)) / (0.240 + 0.444 * w1)
+    /// Reference: Wepfer, Gaggioli and Obert, "Proper evaluation of available energy for HVAC",
+    /// ASHRAE Transactions 85 (1979)
+    ///
+    /// # Errors
+    /// * `UnitMismatch` if the dead state is in a different unit system
+    /// * `OutOfRange` of the humidity ratio if the dead state is dry while this state contains water vapor,
+    ///   or if either state contains condensed water
+    pub fn exergy(&self, dead_state: &MoistAir) -> Result<Exergy, PsychroidError> {
+        if self.unit != dead_state.unit {
+            return Err(PsychroidError::UnitMismatch {
+                expected: self.unit,
+                found: dead_state.unit,
+            });
+        }
+        // exergy of fog and ice fog is not supported
+        for state in [self, dead_state] {
+            if state.condensed_phase().is_some() {
+                return Err(PsychroidError::OutOfRange {
+                    quantity: "humidity ratio",
+                    value: state.humidity_ratio,
+                    min: 0.0,
+                    max: state.vapor_humidity_ratio(),
+                    unit: None,
+                });
             }
+        }
+        if dead_state.humidity_ratio <= 0.0 && self.humidity_ratio > 0.0 {
+            return Err(PsychroidError::OutOfRange {
+                quantity: "humidity ratio of the dead state",
+                value: dead_state.humidity_ratio,
+                min: 0.0,
+                max: f64::INFINITY,
+                unit: None,
+            });
+        }
+        let (t0, p0) = (dead_state.t_dry_bulb, dead_state.pressure);
+        let t0_abs = t_celsius_to_t_kelvin(t0);
+        let r_da = R_DA_SI * 0.001;
+        let w_molar = self.humidity_ratio / MASS_RATIO_WATER_DRY_AIR;
+        let w0_molar = dead_state.humidity_ratio / MASS_RATIO_WATER_DRY_AIR;
+        // flow exergy h - T0 s at the humidity ratio of this state, with all the water as vapor
+        let exergy = |t: f64, p: f64| {
+            specific_enthalpy_gas(t, self.humidity_ratio, p, &self.options)
+                - t0_abs * specific_entropy_gas(t, self.humidity_ratio, p, &self.options)
         };
-        self.humidity_ratio = w1;
-        self.relative_humidity()?;
-        Ok(())
-    }
-
-    /// Calculates the state change when adding water to moist air (isothermal humidification)
-    ///
-    /// # Arguments
-    /// * `mda` - Mass flow rate of dry air \\(\\mathrm{kg/s}\\) (SI) or \\(\\mathrm{lb/h}\\) (IP)
-    /// * `water` - Mass of water added \\(\\mathrm{kg_w/s}\\) (SI) or \\(\\mathrm{lb_w/h}\\) (IP)
-    ///
-    /// # Description
-    /// Calculates the temperature and humidity ratio changes when water is added to an air stream.
-    /// The process is assumed to be isothermal (constant dry-bulb temperature).
-    pub fn humidify_isothermal(&mut self, mda: f64, water: f64) -> Result<(), PsychroidError> {
-        let w1 = self.humidity_ratio + water / mda;
-        self.humidity_ratio = w1;
-        self.relative_humidity()?;
-        Ok(())
-    }
 
-    pub fn cooling_saturation(&mut self, mda: f64) -> Result<f64, PsychroidError> {
-        let mut conv = SimpleConvergency {
-            eps: 1e-9,
-            max_iter: 100,
+        let thermal = exergy(self.t_dry_bulb, self.pressure) - exergy(t0, self.pressure);
+        let mechanical = exergy(t0, self.pressure) - exergy(t0, p0);
+        let mixing = match w_molar > 0.0 {
+            true => w_molar * (w_molar / w0_molar).ln(),
+            false => 0.0,
         };
-        let t_saturated = find_root_newton_raphson(
-            self.t_dry_bulb,
-            |t| {
-                let saturated_water = SaturatedWaterVapor::new_relaxed(t, self.unit);
-                let pws: f64 = saturated_water.saturation_pressure();
-                self.humidity_ratio * (self.pressure - pws) - MASS_RATIO_WATER_DRY_AIR * pws
-            },
-            |t| {
-                let saturated_water = SaturatedWaterVapor::new_relaxed(t, self.unit);
-                -(self.humidity_ratio + MASS_RATIO_WATER_DRY_AIR)
-                    * saturated_water.deriv_saturation_pressure()
-            },
-            &mut conv,
-        )?;
-        let h0 = self.specific_enthalpy();
-        self.t_dry_bulb = t_saturated;
-        let h1 = self.specific_enthalpy();
-        Ok(mda * (h1 - h0))
+        let chemical =
+            r_da * t0_abs * ((1.0 + w_molar) * ((1.0 + w0_molar) / (1.0 + w_molar)).ln() + mixing);
+        Ok(Exergy {
+            thermal: units::specific_energy_from_si(thermal, self.unit),
+            mechanical: units::specific_energy_from_si(mechanical, self.unit),
+            chemical: units::specific_energy_from_si(chemical, self.unit),
+        })
     }
-}
 
-// calculate humidity ratio from dry-bulb and wet-bulb temperatures
-fn humidity_ratio_from_t_wet_bulb(
-    t_dry_bulb: f64,
-    t_wet_bulb: f64,
-    pressure: f64,
-    unit: UnitSystem,
-) -> Result<f64, PsychroidError> {
-    let saturated_water_vapor = SaturatedWaterVapor::new(t_wet_bulb, unit)?;
-    let saturation_pressure: f64 = saturated_water_vapor.saturation_pressure();
-    let saturation_humidity_ratio: f64 =
-        MASS_RATIO_WATER_DRY_AIR * saturation_pressure / (pressure - saturation_pressure);
-    let humidity_ratio: f64 = match unit {
-        UnitSystem::SI => {
-            humidity_ratio_from_t_wet_bulb_si(t_dry_bulb, t_wet_bulb, saturation_humidity_ratio)
+    /// Returns the relative humidity of moist air
+    ///
+    /// # Returns
+    /// Relative humidity \\(\\phi\\) [0.0 - 1.0]
+    ///
+    /// # Formula
+    /// $$
+    /// \\phi = \\frac{p_\mathrm{w}}{p_\mathrm{ws}} = \\frac{pW}{(0.621945 + W) p_\mathrm{ws}}
+    /// $$
+    ///
+    /// where:
+    /// - \\(p_\mathrm{w}\\) - partial pressure of water vapor
+    /// - \\(p_\mathrm{ws}\\) - saturation pressure of water vapor
+    /// - \\(p\\) - total pressure
+    /// - \\(W\\) - humidity ratio (non-dimensional)
+    /// - 0.621945 - ratio of molecular mass (non-dimensional) of water vapor to dry air
+    ///
+    /// Above the boiling point at the total pressure, \\(p_\\mathrm{ws} > p\\) and the relative humidity
+    /// cannot reach 1. Its upper limit \\(p / p_\\mathrm{ws}\\) corresponds to pure water vapor,
+    /// which is approached as the humidity ratio increases without bound.
+    /// Above the critical temperature of water, the relative humidity is 0.
+    pub fn relative_humidity(&self) -> Result<f64, PsychroidError> {
+        let value = relative_humidity_from_humidity_ratio(
+            self.t_dry_bulb,
+            self.vapor_humidity_ratio(),
+            self.pressure,
+            &self.options,
+        )
+        .map_err(units::error_from_si(self.unit, &self.options))?;
+        if !(0.0..=1.0 + TOLERANCE).contains(&value) {
+            return Err(PsychroidError::InvalidRelativeHumidity(value));
+        }
+        Ok(value)
+    }
+
+    /// Returns the dew point temperature of moist air
+    ///
+    /// Below the triple point, the saturation temperature is evaluated over ice or over supercooled water
+    /// depending on [`ModelOptions::saturation_reference`]. See also [`MoistAir::t_frost_point`].
+    ///
+    /// The dew point never exceeds the boiling point at the total pressure, see [`MoistAir::t_boiling_point`],
+    /// and approaches it as the humidity ratio increases without bound.
+    pub fn t_dew_point(&self) -> Result<f64, PsychroidError> {
+        let t_dew_point = self.t_dew_point_si()?;
+        Ok(units::t_from_si(t_dew_point, self.unit))
+    }
+
+    /// Returns the dew point temperature of moist air in °C
+    pub(crate) fn t_dew_point_si(&self) -> Result<f64, PsychroidError> {
+        t_dew_point_from_humidity_ratio(self.vapor_humidity_ratio(), self.pressure, &self.options)
+    }
+
+    /// Returns the dew point temperature of moist air with the diagnostics of the iterative solution
+    ///
+    /// The branch of the report is the phase of the saturation pressure at the dew point.
+    /// For dry air, the dew point, the residual and the initial guess are NaN.
+    ///
+    /// # Example
+    /// ```
+    /// use psychroid::{MoistAir, SaturationBranch, UnitSystem};
+    ///
+    /// let air = MoistAir::from_t_dry_bulb_relative_humidity(20.0, 0.5, 101325.0, UnitSystem::SI).unwrap();
+    /// let (t_dew_point, report) = air.t_dew_point_with_report().unwrap();
+    /// assert_eq!(t_dew_point, air.t_dew_point().unwrap());
+    /// assert_eq!(report.branch, SaturationBranch::Water);
+    /// assert!(report.iterations < 10);
+    /// ```
+    pub fn t_dew_point_with_report(&self) -> Result<(f64, SolverReport), PsychroidError> {
+        let solution =
+            t_dew_point_solution(self.vapor_humidity_ratio(), self.pressure, &self.options)?;
+        let branch = SaturatedWaterVapor::new_relaxed(solution.root, UnitSystem::SI)
+            .with_reference(self.options.saturation_reference)
+            .branch();
+        Ok(self.solution_report(solution, branch))
+    }
+
+    /// Returns the boiling point of water at the total pressure, i.e. the dew point of pure water vapor
+    ///
+    /// Above the boiling point, moist air cannot be saturated, and the saturation humidity ratio is infinite.
+    ///
+    /// # Example
+    /// ```
+    /// use psychroid::{MoistAir, UnitSystem};
+    ///
+    /// // drying air at 250°C, above the boiling point
+    /// let air = MoistAir::from_t_dry_bulb_humidity_ratio(250.0, 2.0, 101325.0, UnitSystem::SI).unwrap();
+    /// let t_boiling_point = air.t_boiling_point().unwrap();
+    /// assert!((t_boiling_point - 100.0).abs() < 0.1);
+    /// assert!(air.t_dew_point().unwrap() < t_boiling_point);
+    /// assert!(air.relative_humidity().unwrap() < 101325.0 / 3.97E+06);
+    /// ```
+    pub fn t_boiling_point(&self) -> Result<f64, PsychroidError> {
+        let t_boiling_point =
+            t_dew_point_from_partial_pressure(self.pressure, self.pressure, &self.options)?;
+        Ok(units::t_from_si(t_boiling_point, self.unit))
+    }
+
+    /// Returns the frost point temperature of moist air, i.e. the saturation temperature over ice
+    /// for the partial pressure of water vapor, regardless of the saturation reference.
+    ///
+    /// Above the triple point, where ice does not exist, the frost point coincides with the dew point.
+    ///
+    /// # Example
+    /// ```
+    /// use psychroid::{ModelOptions, MoistAir, Property, PropertyPair, SaturationReference, UnitSystem};
+    ///
+    /// // relative humidity of meteorological data is referenced to supercooled water
+    /// let options = ModelOptions {
+    ///     saturation_reference: SaturationReference::Water,
+    ///     ..Default::default()
+    /// };
+    /// let pair = PropertyPair(Property::TDryBulb(-10.0), Property::RelativeHumidity(0.8));
+    /// let air = MoistAir::from_pair_with_options(pair, 101325.0, UnitSystem::SI, options).unwrap();
+    /// // frost forms above the dew point over supercooled water
+    /// assert!(air.t_frost_point().unwrap() > air.t_dew_point().unwrap());
+    /// ```
+    pub fn t_frost_point(&self) -> Result<f64, PsychroidError> {
+        let options = ModelOptions {
+            saturation_reference: SaturationReference::Ice,
+            ..self.options
+        };
+        let t_frost_point =
+            t_dew_point_from_humidity_ratio(self.vapor_humidity_ratio(), self.pressure, &options)?;
+        Ok(units::t_from_si(t_frost_point, self.unit))
+    }
+
+    /// Returns the wet bulb temperature of moist air
+    pub fn t_wet_bulb(&self) -> Result<f64, PsychroidError> {
+        let t_wet_bulb = t_wet_bulb_from_humidity_ratio(
+            self.t_dry_bulb,
+            self.vapor_humidity_ratio(),
+            self.pressure,
+            &self.options,
+        )?;
+        Ok(units::t_from_si(t_wet_bulb, self.unit))
+    }
+
+    /// Returns the wet bulb temperature of moist air with the diagnostics of the iterative solution
+    ///
+    /// The branch of the report is the phase of the water film of the energy balance at the wet-bulb
+    /// temperature, i.e. ice below the triple point with [`SaturationReference::Ice`].
+    /// See [`SolverReport`] for an example.
+    pub fn t_wet_bulb_with_report(&self) -> Result<(f64, SolverReport), PsychroidError> {
+        let solution = t_wet_bulb_solution(
+            self.t_dry_bulb,
+            self.vapor_humidity_ratio(),
+            self.pressure,
+            &self.options,
+        )?;
+        let branch = match is_over_water(solution.root, &self.options) {
+            true => SaturationBranch::Water,
+            false => SaturationBranch::Ice,
+        };
+        Ok(self.solution_report(solution, branch))
+    }
+
+    /// Returns the temperature solved in °C and its report, converted into the unit system
+    fn solution_report(&self, solution: Solution, branch: SaturationBranch) -> (f64, SolverReport) {
+        let report = SolverReport {
+            initial_guess: units::t_from_si(solution.initial_guess, self.unit),
+            ..solution.report(branch)
+        };
+        (units::t_from_si(solution.root, self.unit), report)
+    }
+
+    /// Returns the specific volume of moist air
+    ///
+    /// # Returns
+    /// Specific volume \\(v\\) is the specific volume \\(V/M_{da}\\) in \\( \\mathrm{m^3/kg_{da}} \\) (SI) or \\( \\mathrm{ft^3/lb_{da}} \\) (IP)
+    ///
+    /// # Formula
+    /// $$
+    /// v = 0.287042 (t_\mathrm{db} + 273.15) (1 + 1.607858 W) / p
+    /// $$
+    /// where:
+    /// - \\(t_\\mathrm{db}\\) - dry bulb temperature in \\(^\\circ \\mathrm{C}\\)
+    /// - \\(W\\) -  humidity ratio in \\( \\mathrm{kg_w / kg_{da}} \\)
+    /// - \\(p\\) - total pressure in \\( \\mathrm{kPa} \\)
+    ///
+    /// In IP units, the specific volume is converted from SI units.
+    /// For [`GasModel::Real`], the specific volume is multiplied by the compressibility factor.
+    /// For fog and ice fog, only the water vapor contributes to the volume.
+    pub fn specific_volume(&self) -> f64 {
+        let specific_volume = specific_volume_from_humidity_ratio(
+            self.t_dry_bulb,
+            self.vapor_humidity_ratio(),
+            self.pressure,
+            &self.options,
+        );
+        units::specific_volume_from_si(specific_volume, self.unit)
+    }
+
+    /// Returns the density of moist air
+    ///
+    /// # Returns
+    /// Density \\(\\rho = (1 + W) / v\\) in \\( \\mathrm{kg/m^3} \\) (SI) or \\( \\mathrm{lb/ft^3} \\) (IP)
+    ///
+    /// For fog and ice fog, \\(W\\) includes the condensed water, of which the volume is neglected.
+    pub fn density(&self) -> f64 {
+        (1.0 + self.humidity_ratio) / self.specific_volume()
+    }
+
+    /// Returns the partial pressure of water vapor
+    ///
+    /// # Returns
+    /// Partial pressure \\(p_\\mathrm{w}\\) in \\(\\mathrm{Pa}\\) (SI) or \\(\\mathrm{Psi}\\) (IP)
+    ///
+    /// # Formula
+    /// $$
+    /// p_\\mathrm{w} = \\frac{pW}{0.621945 + W}
+    /// $$
+    pub fn partial_water_vapor_pressure(&self) -> f64 {
+        self.pressure() * self.mole_fraction_water_vapor()
+    }
+
+    /// Returns the mole fraction of water vapor \\(x_\\mathrm{w} = p_\\mathrm{w} / p\\) (non-dimensional)
+    pub fn mole_fraction_water_vapor(&self) -> f64 {
+        let humidity_ratio = self.vapor_humidity_ratio();
+        humidity_ratio / (MASS_RATIO_WATER_DRY_AIR + humidity_ratio)
+    }
+
+    /// Returns the degree of saturation \\(\\mu = W / W_\\mathrm{s}\\) (non-dimensional),
+    /// where \\(W_\\mathrm{s}\\) is the humidity ratio of saturated air at the same temperature and pressure
+    ///
+    /// ASHRAE Handbook - Fundamentals (2017) Ch. 1 Eq. (12)
+    ///
+    /// Above the boiling point, the degree of saturation is 0, as the saturation humidity ratio is infinite.
+    pub fn degree_of_saturation(&self) -> Result<f64, PsychroidError> {
+        check_range_t_dry_bulb(self.t_dry_bulb(), self.unit)?;
+        let saturation_humidity_ratio =
+            saturation_humidity_ratio(self.t_dry_bulb, self.pressure, &self.options);
+        Ok(self.humidity_ratio / saturation_humidity_ratio)
+    }
+
+    /// Returns the absolute humidity (density of water vapor)
+    ///
+    /// # Returns
+    /// Absolute humidity \\(d_\\mathrm{v} = W / v\\) in \\( \\mathrm{g/m^3} \\) (SI) or \\( \\mathrm{gr/ft^3} \\) (IP)
+    ///
+    /// ASHRAE Handbook - Fundamentals (2017) Ch. 1 Eq. (10)
+    pub fn absolute_humidity(&self) -> f64 {
+        let vapor_density = self.vapor_humidity_ratio() / self.specific_volume();
+        match self.unit {
+            UnitSystem::SI => vapor_density * 1000.0, // kg/m³ to g/m³
+            UnitSystem::IP => vapor_density * GRAINS_PER_POUND, // lb/ft³ to gr/ft³
+        }
+    }
+
+    /// Returns the specific humidity \\(\\gamma = W / (1 + W)\\),
+    /// in \\( \\mathrm{kg_w / kg} \\) (SI) or \\( \\mathrm{lb_w / lb} \\) (IP)
+    ///
+    /// ASHRAE Handbook - Fundamentals (2017) Ch. 1 Eq. (9b)
+    pub fn specific_humidity(&self) -> f64 {
+        let humidity_ratio = self.vapor_humidity_ratio();
+        humidity_ratio / (1.0 + humidity_ratio)
+    }
+
+    /// Returns the molar mass of moist air,
+    /// in \\( \\mathrm{kg/kmol} \\) (SI) or \\( \\mathrm{lb/lbmol} \\) (IP)
+    ///
+    /// # Formula
+    /// $$
+    /// M = (1 - x_\\mathrm{w}) M_\\mathrm{da} + x_\\mathrm{w} M_\\mathrm{w}
+    /// $$
+    pub fn molar_mass(&self) -> f64 {
+        let x_w = self.mole_fraction_water_vapor();
+        (1.0 - x_w) * MOLAR_MASS_DRY_AIR + x_w * MOLAR_MASS_WATER
+    }
+
+    /// Returns the specific gas constant of moist air \\(R = R_\\mathrm{u} / M\\),
+    /// in \\( \\mathrm{J/(kg \\cdot K)} \\) (SI) or \\( \\mathrm{ft \\cdot lbf/(lb \\cdot {}^\\circ R)} \\) (IP)
+    pub fn gas_constant(&self) -> f64 {
+        units::gas_constant_from_si(UNIVERSAL_GAS_CONSTANT_SI / self.molar_mass(), self.unit)
+    }
+
+    /// Returns the specific heat at constant pressure per unit mass of moist air
+    ///
+    /// # Returns
+    /// Specific heat \\(c_p\\) in \\( \\mathrm{kJ/(kg \\cdot K)} \\) (SI) or \\( \\mathrm{Btu/(lb \\cdot {}^\\circ F)} \\) (IP)
+    ///
+    /// # Formula
+    /// Consistent with the specific enthalpy formula of the linear enthalpy model,
+    /// $$
+    /// c_p = \\frac{1.006 + 1.860~W}{1 + W}
+    /// $$
+    /// For the variable heat capacity model, the coefficients are replaced by
+    /// the heat capacities of dry air and water vapor at the dry-bulb temperature.
+    /// In IP units, the coefficients of the IP edition, 0.240 and 0.444, are used instead.
+    pub fn specific_heat(&self) -> f64 {
+        units::specific_heat_from_si(self.specific_heat_si(), self.unit)
+    }
+
+    /// Returns the specific heat at constant pressure in \\( \\mathrm{kJ/(kg \\cdot K)} \\)
+    fn specific_heat_si(&self) -> f64 {
+        specific_heat_from_humidity_ratio(
+            self.t_dry_bulb,
+            self.vapor_humidity_ratio(),
+            &self.options,
+        )
+    }
+
+    /// Returns the ratio of specific heats \\(\\kappa = c_p / (c_p - R)\\) (non-dimensional)
+    pub fn heat_capacity_ratio(&self) -> f64 {
+        let cp = self.specific_heat_si();
+        let gas_constant = UNIVERSAL_GAS_CONSTANT_SI / self.molar_mass() * 0.001; // J to kJ
+        cp / (cp - gas_constant)
+    }
+
+    /// Returns the temperature in K and the pressure in Pa, as used by the transport properties
+    pub(crate) fn t_kelvin_pressure_si(&self) -> (f64, f64) {
+        (t_celsius_to_t_kelvin(self.t_dry_bulb), self.pressure)
+    }
+
+    /// Returns the dynamic viscosity of moist air
+    ///
+    /// # Returns
+    /// Dynamic viscosity \\(\\mu\\) in \\( \\mathrm{Pa \\cdot s} \\) (SI) or \\( \\mathrm{lb/(ft \\cdot h)} \\) (IP)
+    ///
+    /// The viscosities of dry air and water vapor are mixed by Wilke's rule,
+    /// according to Tsilingiris (2008). See [`transport::dynamic_viscosity`].
+    pub fn dynamic_viscosity(&self) -> f64 {
+        let (t_kelvin, _) = self.t_kelvin_pressure_si();
+        let mu = transport::dynamic_viscosity(t_kelvin, self.mole_fraction_water_vapor());
+        match self.unit {
+            UnitSystem::SI => mu,
+            UnitSystem::IP => mu * LB_PER_FT_H_PER_PA_S,
+        }
+    }
+
+    /// Returns the thermal conductivity of moist air
+    ///
+    /// # Returns
+    /// Thermal conductivity \\(k\\) in \\( \\mathrm{W/(m \\cdot K)} \\) (SI) or \\( \\mathrm{Btu/(h \\cdot ft \\cdot {}^\\circ F)} \\) (IP)
+    ///
+    /// See [`transport::thermal_conductivity`].
+    pub fn thermal_conductivity(&self) -> f64 {
+        let (t_kelvin, _) = self.t_kelvin_pressure_si();
+        let k = transport::thermal_conductivity(t_kelvin, self.mole_fraction_water_vapor());
+        match self.unit {
+            UnitSystem::SI => k,
+            UnitSystem::IP => k * BTU_PER_H_FT_F_PER_W_M_K,
+        }
+    }
+
+    /// Returns the mass diffusivity of water vapor in air
+    ///
+    /// # Returns
+    /// Mass diffusivity \\(D_\\mathrm{va}\\) in \\( \\mathrm{m^2/s} \\) (SI) or \\( \\mathrm{ft^2/h} \\) (IP)
+    ///
+    /// See [`transport::mass_diffusivity`].
+    pub fn mass_diffusivity(&self) -> f64 {
+        let (t_kelvin, pressure) = self.t_kelvin_pressure_si();
+        let d = transport::mass_diffusivity(t_kelvin, pressure);
+        match self.unit {
+            UnitSystem::SI => d,
+            UnitSystem::IP => d * FT2_PER_H_PER_M2_PER_S,
+        }
+    }
+
+    /// Returns the Prandtl number \\(Pr = \\mu c_p / k\\) (non-dimensional)
+    pub fn prandtl_number(&self) -> f64 {
+        let (t_kelvin, _) = self.t_kelvin_pressure_si();
+        let x_w = self.mole_fraction_water_vapor();
+        let cp = self.specific_heat_si() * 1000.0; // kJ to J
+        transport::dynamic_viscosity(t_kelvin, x_w) * cp
+            / transport::thermal_conductivity(t_kelvin, x_w)
+    }
+
+    /// Returns the Schmidt number \\(Sc = \\mu / (\\rho D_\\mathrm{va})\\) (non-dimensional)
+    pub fn schmidt_number(&self) -> f64 {
+        self.dynamic_viscosity() / (self.density() * self.mass_diffusivity())
+    }
+
+    /// Returns the Lewis number \\(Le = Sc / Pr\\) (non-dimensional)
+    pub fn lewis_number(&self) -> f64 {
+        self.schmidt_number() / self.prandtl_number()
+    }
+
+    /// Returns the partial derivatives of a property with respect to a pair of inputs and the pressure
+    ///
+    /// The inputs are any two properties which vary independently at the state,
+    /// typically the pair which defined it, e.g. dry-bulb and wet-bulb temperatures.
+    /// See [`crate::sensitivity`] for the method and the non-differentiable points.
+    ///
+    /// # Arguments
+    /// * `output` - Property to be differentiated
+    /// * `first` - First input, held constant for the derivative with respect to the second
+    /// * `second` - Second input, held constant for the derivative with respect to the first
+    ///
+    /// # Returns
+    /// The derivatives in the unit system of the state, e.g. \(\partial h / \partial p\)
+    /// in \( \mathrm{kJ/(kg_{da} \cdot Pa)} \) (SI) or \( \mathrm{Btu/(lb_{da} \cdot Psi)} \) (IP)
+    ///
+    /// # Errors
+    /// * Errors of the evaluation of the properties at the state, e.g. the dew point of dry air
+    /// * `DependentInputs` if the inputs do not determine the state, e.g. humidity ratio and dew point,
+    ///   or the relative humidity of fog, which is 1 for any humidity ratio
+    ///
+    /// # Example
+    /// ```
+    /// use psychroid::batch::Output;
+    /// use psychroid::{MoistAir, UnitSystem};
+    ///
+    /// let air = MoistAir::from_t_dry_bulb_humidity_ratio(25.0, 0.01, 101325.0, UnitSystem::SI).unwrap();
+    /// let gradient = air
+    ///     .gradient(Output::TDewPoint, Output::TDryBulb, Output::HumidityRatio)
+    ///     .unwrap();
+    /// assert_eq!(gradient.first, 0.0);
+    /// assert!((gradient.second - 1517.0).abs() < 1.0); // K per kg/kg
+    /// ```
+    pub fn gradient(
+        &self,
+        output: Output,
+        first: Output,
+        second: Output,
+    ) -> Result<Gradient, PsychroidError> {
+        sensitivity::gradient(self, output, first, second)
+    }
+
+    /// Returns the validity of a property of moist air with respect to the ranges of the correlations
+    ///
+    /// The property is either in range, near a switch between branches (e.g. the saturation over ice and
+    /// over water) or extrapolated beyond the range of a correlation (e.g. the saturation pressure over
+    /// supercooled water). See [`crate::validity`] for the ranges.
+    ///
+    /// # Errors
+    /// Errors of the evaluation of the wet-bulb and dew point temperatures, for these properties
+    pub fn validity(&self, output: Output) -> Result<Validity, PsychroidError> {
+        validity::validity(self, output)
+    }
+
+    /// Changes the unit system of the inputs and outputs of moist air.
+    /// The state itself is held in SI units and does not change.
+    ///
+    /// # Arguments
+    /// * `unit` - The new unit system to convert to (SI or IP)
+    ///
+    /// # Conversions performed
+    /// - Temperature: \\(^\\circ \\mathrm{F}\\) ↔ \\(^\\circ \\mathrm{C}\\)
+    /// - Pressure: \\(\\mathrm{Psi}\\) ↔ \\(\\mathrm{Pa}\\)
+    /// - Humidity ratio remains dimensionless
+    ///
+    /// # Example
+    /// ```
+    /// use psychroid::{MoistAir, UnitSystem};
+    ///
+    /// let mut air = MoistAir::from_t_dry_bulb_humidity_ratio(
+    ///     25.0,     // 25°C
+    ///     0.007,    // humidity ratio
+    ///     101325.0, // Pa
+    ///     UnitSystem::SI,
+    /// )
+    /// .unwrap();
+    ///
+    /// // Convert to IP units
+    /// air.set_unit(UnitSystem::IP);
+    /// // Now temperature is in °F, pressure in Psi
+    /// assert!((air.t_dry_bulb() - 77.0).abs() < 1.0E-9);
+    /// assert!((air.pressure() - 14.696).abs() < 1.0E-3);
+    /// assert_eq!(air.humidity_ratio(), 0.007);
+    /// ```
+    pub fn set_unit(&mut self, unit: UnitSystem) {
+        self.unit = unit;
+    }
+
+    /// Calculates the heating energy required to change the dry-bulb temperature to a target temperature
+    /// This method modifies the dry-bulb temperature of the instance to the target temperature
+    ///
+    /// # Arguments
+    /// * `mda` - Mass flow rate of dry air \\( \\mathrm{kg/s} \\) (SI) or \\( \\mathrm{lb/h} \\) (IP)
+    /// * `t1` - Target dry-bulb temperature \\(^\\circ \\mathrm{C}\\)  (SI) or \\(^\\circ \\mathrm{F}\\)  (IP)
+    ///
+    /// # Returns
+    /// Heating energy \\(q\\) required in \\( \\mathrm{kW} \\) (SI) or \\( \\mathrm{Btu/h} \\) (IP)
+    ///
+    /// # Errors
+    /// * `NonFiniteInput` or `InvalidTDryBulb` if the target temperature is out of range,
+    ///   in which case the state is left unchanged
+    pub fn heating_t1(&mut self, mda: f64, t1: f64) -> Result<f64, PsychroidError> {
+        let h0 = self.specific_enthalpy_si();
+        self.update_si(units::t_to_si(t1, self.unit), self.humidity_ratio)?;
+        let h1 = self.specific_enthalpy_si();
+        Ok(mda * units::specific_energy_from_si(h1 - h0, self.unit))
+    }
+
+    /// Calculates the heating energy required to change the dry-bulb temperature by a given amount
+    /// This method modifies the dry-bulb temperature of the instance to the target temperature
+    ///
+    /// # Arguments
+    /// * `mda` - Mass flow rate of dry air \\( \\mathrm{kg/s} \\) (SI) or \\( \\mathrm{lb/h} \\) (IP)
+    /// * `dt` - Temperature change \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
+    ///
+    /// # Returns
+    /// Heating energy \\(q\\) required in \\( \\mathrm{kW} \\) (SI) or \\( \\mathrm{Btu/h} \\) (IP)
+    ///
+    /// # Errors
+    /// * `NonFiniteInput` or `InvalidTDryBulb` if the new temperature is out of range,
+    ///   in which case the state is left unchanged
+    pub fn heating_dt(&mut self, mda: f64, dt: f64) -> Result<f64, PsychroidError> {
+        let h0 = self.specific_enthalpy_si();
+        self.update_si(
+            self.t_dry_bulb + units::dt_to_si(dt, self.unit),
+            self.humidity_ratio,
+        )?;
+        let h1 = self.specific_enthalpy_si();
+        Ok(mda * units::specific_energy_from_si(h1 - h0, self.unit))
+    }
+
+    /// Calculates the temperature change for a given heating energy input
+    ///
+    /// # Arguments
+    /// * `mda` - Mass flow rate of dry air \\( \\mathrm{kg/s} \\) (SI) or \\( \\mathrm{lb/h} \\) (IP)
+    /// * `q` - Heating energy input \\( \\mathrm{kW} \\) (SI) or \\( \\mathrm{Btu/h} \\) (IP)
+    ///
+    /// # Returns
+    /// Error if the new dry-bulb temperature cannot be determined
+    ///
+    /// # Formula
+    /// The new dry-bulb temperature is solved from the specific enthalpy of the selected enthalpy model:
+    /// $$
+    /// h(T_1, W) = h(T_0, W) + \\Delta h
+    /// $$
+    /// where:
+    /// - \\(\\Delta h = q/\\dot{m}_{da}\\) is the specific enthalpy change
+    /// - \\(W\\) is the humidity ratio
+    pub fn heating_q(&mut self, mda: f64, q: f64) -> Result<(), PsychroidError> {
+        let dh = units::specific_energy_to_si(q / mda, self.unit); // kJ/kg_da
+
+        // new dry bulb temperature
+        let t1 = t_dry_bulb_from_specific_enthalpy_humidity_ratio(
+            self.specific_enthalpy_si() + dh,
+            self.humidity_ratio,
+            self.pressure,
+            &self.options,
+        )?;
+        self.update_si(t1, self.humidity_ratio)
+    }
+
+    pub fn cooling_t1(&mut self, mda: f64, t1: f64) -> Result<f64, PsychroidError> {
+        let t_dew_point = self.t_dew_point_si()?;
+        let h0 = self.specific_enthalpy_si();
+        let t1 = units::t_to_si(t1, self.unit);
+        let humidity_ratio = match t1 < t_dew_point {
+            true => humidity_ratio_from_relative_humidity(t1, 1.0, self.pressure, &self.options)
+                .map_err(units::error_from_si(self.unit, &self.options))?,
+            false => self.humidity_ratio,
+        };
+        self.update_si(t1, humidity_ratio)?;
+        let h1 = self.specific_enthalpy_si();
+        Ok(mda * units::specific_energy_from_si(h0 - h1, self.unit))
+    }
+
+    pub fn cooling_dt(&mut self, mda: f64, dt: f64) -> Result<f64, PsychroidError> {
+        let t_dew_point = self.t_dew_point_si()?;
+        let h0 = self.specific_enthalpy_si();
+        let t1 = self.t_dry_bulb - units::dt_to_si(dt, self.unit);
+        let humidity_ratio = match t1 < t_dew_point {
+            true => humidity_ratio_from_relative_humidity(t1, 1.0, self.pressure, &self.options)
+                .map_err(units::error_from_si(self.unit, &self.options))?,
+            false => self.humidity_ratio,
+        };
+        self.update_si(t1, humidity_ratio)?;
+        let h1 = self.specific_enthalpy_si();
+        Ok(mda * units::specific_energy_from_si(h0 - h1, self.unit))
+    }
+
+    /// Calculates the temperature change for a given cooling energy removal
+    /// If the new temperature is expected to be below the dew point,
+    /// the new dry-bulb temperature is calculated based on the specific enthalpy and relative humidity of 1.0.
+    ///
+    /// # Arguments
+    /// * `mda` - Mass flow rate of dry air \\( \\mathrm{kg/s} \\) (SI) or \\( \\mathrm{lb/h} \\) (IP)
+    /// * `q` - Heating energy input \\( \\mathrm{kW} \\) (SI) or \\( \\mathrm{Btu/h} \\) (IP)
+    ///
+    /// # Returns
+    /// Error if the new state cannot be determined
+    ///
+    /// # Formula
+    /// Without dehumidification, the new dry-bulb temperature is solved from the specific enthalpy
+    /// of the selected enthalpy model:
+    /// $$
+    /// h(T_1, W) = h(T_0, W) + \\Delta h
+    /// $$
+    /// where:
+    /// - \\(\\Delta h = -q/\\dot{m}_{da}\\) -  specific enthalpy change
+    /// - \\(W\\) -  humidity ratio
+    pub fn cooling_q(&mut self, mda: f64, q: f64) -> Result<(), PsychroidError> {
+        let dh = units::specific_energy_to_si(q / mda, self.unit); // kJ/kg_da
+        let h0 = self.specific_enthalpy_si();
+        let h1 = h0 - dh;
+        let t1 = t_dry_bulb_from_specific_enthalpy_humidity_ratio(
+            h1,
+            self.humidity_ratio,
+            self.pressure,
+            &self.options,
+        )?;
+        let t_dew_point = self.t_dew_point_si()?;
+        if t1 < t_dew_point {
+            let t1 = t_dry_bulb_from_specific_enthalpy_relative_humidity(
+                h1,
+                1.0,
+                self.pressure,
+                &self.options,
+            )?;
+            let humidity_ratio =
+                humidity_ratio_from_relative_humidity(t1, 1.0, self.pressure, &self.options)
+                    .map_err(units::error_from_si(self.unit, &self.options))?;
+            self.update_si(t1, humidity_ratio)
+        } else {
+            self.update_si(t1, self.humidity_ratio)
+        }
+    }
+
+    /// Calculates the state change when adding water to moist air (adiabatic humidification).
+    /// This method modifies both temperature and humidity ratio of the instance.
+    ///
+    /// # Arguments
+    /// * `mda` - Mass flow rate of dry air \\(\\mathrm{kg/s}\\) (SI) or \\(\\mathrm{lb/h}\\) (IP)
+    /// * `water` - Mass of water added \\(\\mathrm{kg_w/s}\\) (SI) or \\(\\mathrm{lb_w/h}\\) (IP)
+    ///
+    /// # Description
+    /// Calculates the temperature and humidity ratio changes when water is added to an air stream.
+    /// The process is assumed to be adiabatic (constant enthalpy).
+    ///
+    /// # Formula
+    /// It is assumed that the process is adiabatic and the enthalpy remains constant.
+    /// Based on this assumption, the temperature after humidification is calculated by
+    /// $$
+    /// \begin{align}
+    /// T_1 &= \frac{(1.006 + 1.860~W_0)~T_0 - 2501.0 (W_1 - W_0)}{1.006 + 1.860~W_1} \quad &\text{(SI)} \\\\
+    /// T_1 &= \frac{(0.240 + 0.444~W_0)~T_0 - 1061.0 (W_1 - W_0)}{0.240 + 0.444~W_1} \quad &\text{(IP)}
+    /// \end{align}
+    /// $$
+    /// where:
+    /// - \\(T_0,~T_1\\) are initial and final temperatures
+    /// - \\(W_0,~W_1\\) are initial and final humidity ratios
+    ///
+    /// If the added water exceeds saturation, the excess remains suspended as fog or ice fog,
+    /// and the temperature is searched on the enthalpy including the condensed water.
+    pub fn humidify_adiabatic(&mut self, mda: f64, water: f64) -> Result<(), PsychroidError> {
+        let specific_enthalpy = self.specific_enthalpy_si();
+        let humidity_ratio = self.humidity_ratio + water / mda;
+        let t1 = t_dry_bulb_from_specific_enthalpy_humidity_ratio(
+            specific_enthalpy,
+            humidity_ratio,
+            self.pressure,
+            &self.options,
+        )?;
+        self.update_humidified_si(t1, humidity_ratio)
+    }
+
+    /// Calculates the state change when adding water to moist air (isothermal humidification)
+    ///
+    /// # Arguments
+    /// * `mda` - Mass flow rate of dry air \\(\\mathrm{kg/s}\\) (SI) or \\(\\mathrm{lb/h}\\) (IP)
+    /// * `water` - Mass of water added \\(\\mathrm{kg_w/s}\\) (SI) or \\(\\mathrm{lb_w/h}\\) (IP)
+    ///
+    /// # Description
+    /// Calculates the temperature and humidity ratio changes when water is added to an air stream.
+    /// The process is assumed to be isothermal (constant dry-bulb temperature).
+    /// If the added water exceeds saturation, the excess remains suspended as fog or ice fog.
+    pub fn humidify_isothermal(&mut self, mda: f64, water: f64) -> Result<(), PsychroidError> {
+        let w1 = self.humidity_ratio + water / mda;
+        self.update_humidified_si(self.t_dry_bulb, w1)
+    }
+
+    pub fn cooling_saturation(&mut self, mda: f64) -> Result<f64, PsychroidError> {
+        let humidity_ratio = self.vapor_humidity_ratio();
+        // moist air is cooled at constant humidity ratio down to the dew point
+        let t_saturated = self.t_dew_point_si()?;
+        if t_saturated.is_nan() {
+            return Err(PsychroidError::OutOfRange {
+                quantity: "humidity ratio",
+                value: humidity_ratio,
+                min: f64::EPSILON,
+                max: f64::INFINITY,
+                unit: None,
+            });
+        }
+        let h0 = self.specific_enthalpy_si();
+        self.update_si(t_saturated, self.humidity_ratio)?;
+        let h1 = self.specific_enthalpy_si();
+        Ok(mda * units::specific_energy_from_si(h1 - h0, self.unit))
+    }
+
+    /// Returns the state after [`MoistAir::heating_t1`] without modifying this state
+    pub fn after_heating_t1(&self, mda: f64, t1: f64) -> Result<ProcessOutput, PsychroidError> {
+        let mut outlet = *self;
+        outlet.heating_t1(mda, t1)?;
+        Ok(ProcessOutput::new(self, outlet, mda))
+    }
+
+    /// Returns the state after [`MoistAir::heating_dt`] without modifying this state
+    pub fn after_heating_dt(&self, mda: f64, dt: f64) -> Result<ProcessOutput, PsychroidError> {
+        let mut outlet = *self;
+        outlet.heating_dt(mda, dt)?;
+        Ok(ProcessOutput::new(self, outlet, mda))
+    }
+
+    /// Returns the state after [`MoistAir::heating_q`] without modifying this state
+    pub fn after_heating_q(&self, mda: f64, q: f64) -> Result<ProcessOutput, PsychroidError> {
+        let mut outlet = *self;
+        outlet.heating_q(mda, q)?;
+        Ok(ProcessOutput::new(self, outlet, mda))
+    }
+
+    /// Returns the state after [`MoistAir::cooling_t1`] without modifying this state
+    pub fn after_cooling_t1(&self, mda: f64, t1: f64) -> Result<ProcessOutput, PsychroidError> {
+        let mut outlet = *self;
+        outlet.cooling_t1(mda, t1)?;
+        Ok(ProcessOutput::new(self, outlet, mda))
+    }
+
+    /// Returns the state after [`MoistAir::cooling_dt`] without modifying this state
+    pub fn after_cooling_dt(&self, mda: f64, dt: f64) -> Result<ProcessOutput, PsychroidError> {
+        let mut outlet = *self;
+        outlet.cooling_dt(mda, dt)?;
+        Ok(ProcessOutput::new(self, outlet, mda))
+    }
+
+    /// Returns the state after [`MoistAir::cooling_q`] without modifying this state
+    pub fn after_cooling_q(&self, mda: f64, q: f64) -> Result<ProcessOutput, PsychroidError> {
+        let mut outlet = *self;
+        outlet.cooling_q(mda, q)?;
+        Ok(ProcessOutput::new(self, outlet, mda))
+    }
+
+    /// Returns the state after [`MoistAir::humidify_adiabatic`] without modifying this state
+    pub fn after_humidify_adiabatic(
+        &self,
+        mda: f64,
+        water: f64,
+    ) -> Result<ProcessOutput, PsychroidError> {
+        let mut outlet = *self;
+        outlet.humidify_adiabatic(mda, water)?;
+        Ok(ProcessOutput::new(self, outlet, mda))
+    }
+
+    /// Returns the state after [`MoistAir::humidify_isothermal`] without modifying this state
+    pub fn after_humidify_isothermal(
+        &self,
+        mda: f64,
+        water: f64,
+    ) -> Result<ProcessOutput, PsychroidError> {
+        let mut outlet = *self;
+        outlet.humidify_isothermal(mda, water)?;
+        Ok(ProcessOutput::new(self, outlet, mda))
+    }
+
+    /// Returns the state after [`MoistAir::cooling_saturation`] without modifying this state
+    pub fn after_cooling_saturation(&self, mda: f64) -> Result<ProcessOutput, PsychroidError> {
+        let mut outlet = *self;
+        outlet.cooling_saturation(mda)?;
+        Ok(ProcessOutput::new(self, outlet, mda))
+    }
+}
+
+/// Calculate the enhancement factor of the saturation pressure of water vapor in moist air.
+/// Returns 1 for the ideal gas model.
+fn enhancement_factor<F: Float>(
+    t: F,
+    saturation_pressure: F,
+    pressure: F,
+    options: &ModelOptions,
+) -> F {
+    match options.gas_model {
+        // moist air cannot be saturated above the boiling point
+        GasModel::Real if saturation_pressure < pressure => {
+            real_gas::enhancement_factor(t, pressure, saturation_pressure)
         }
-        UnitSystem::IP => {
-            humidity_ratio_from_t_wet_bulb_ip(t_dry_bulb, t_wet_bulb, saturation_humidity_ratio)
+        _ => F::of(1.0),
+    }
+}
+
+/// Calculate the compressibility factor of moist air. Returns 1 for the ideal gas model.
+fn compressibility_factor<F: Float>(
+    t_dry_bulb: F,
+    humidity_ratio: F,
+    pressure: F,
+    options: &ModelOptions,
+) -> F {
+    match options.gas_model {
+        GasModel::Ideal => F::of(1.0),
+        GasModel::Real => {
+            let x_w = humidity_ratio / (F::of(MASS_RATIO_WATER_DRY_AIR) + humidity_ratio);
+            real_gas::compressibility_factor(t_celsius_to_t_kelvin(t_dry_bulb), pressure, x_w)
         }
-    };
-    Ok(humidity_ratio)
+    }
+}
+
+/// Calculate the residual enthalpy of moist air per unit mass of dry air in \\(\\mathrm{kJ/kg_{da}}\\),
+/// relative to that of dry air at the reference state of the enthalpy (0 °C, 101.325 kPa).
+/// Returns 0 for the ideal gas model.
+fn residual_enthalpy<F: Float>(
+    t_dry_bulb: F,
+    humidity_ratio: F,
+    pressure: F,
+    options: &ModelOptions,
+) -> F {
+    let c = F::of;
+    match options.gas_model {
+        GasModel::Ideal => c(0.0),
+        GasModel::Real => {
+            let t_kelvin = t_celsius_to_t_kelvin(t_dry_bulb);
+            let x_w = humidity_ratio / (c(MASS_RATIO_WATER_DRY_AIR) + humidity_ratio);
+            // moles of moist air per unit mass of dry air, in mol/g
+            let moles =
+                (c(1.0) + humidity_ratio / c(MASS_RATIO_WATER_DRY_AIR)) / c(MOLAR_MASS_DRY_AIR);
+            // J/g = kJ/kg
+            moles * real_gas::residual_enthalpy(t_kelvin, pressure, x_w)
+                - c(real_gas::residual_enthalpy(
+                    ZERO_CELSIUS_AS_KELVIN,
+                    STANDARD_ATMOSPHERE_SI,
+                    0.0,
+                ) / MOLAR_MASS_DRY_AIR)
+        }
+    }
 }
 
-/// ASHRAE Handbook - Fundamentals (2013) IP Ch. 1 Eq. (35) and (37)
-fn humidity_ratio_from_t_wet_bulb_ip(
+/// Calculate the residual entropy of moist air per unit mass of dry air in \\(\\mathrm{kJ/(kg_{da} \\cdot K)}\\),
+/// relative to that of dry air at the reference state of the entropy (0 °C, 101.325 kPa).
+/// Returns 0 for the ideal gas model.
+fn residual_entropy(
     t_dry_bulb: f64,
-    t_wet_bulb: f64,
-    saturation_humidity_ratio: f64,
+    humidity_ratio: f64,
+    pressure: f64,
+    options: &ModelOptions,
 ) -> f64 {
-    match t_wet_bulb >= FREEZING_POINT_WATER_IP {
-        true => {
-            ((1093.0 - 0.556 * t_wet_bulb) * saturation_humidity_ratio
-                - 0.240 * (t_dry_bulb - t_wet_bulb))
-                / (1093.0 + 0.444 * t_dry_bulb - t_wet_bulb)
-        }
-        false => {
-            ((1220.0 - 0.04 * t_wet_bulb) * saturation_humidity_ratio
-                - 0.240 * (t_dry_bulb - t_wet_bulb))
-                / (1220.0 + 0.444 * t_dry_bulb - 0.48 * t_wet_bulb)
+    match options.gas_model {
+        GasModel::Ideal => 0.0,
+        GasModel::Real => {
+            let t_kelvin = t_celsius_to_t_kelvin(t_dry_bulb);
+            let x_w = humidity_ratio / (MASS_RATIO_WATER_DRY_AIR + humidity_ratio);
+            // moles of moist air per unit mass of dry air, in mol/g
+            let moles = (1.0 + humidity_ratio / MASS_RATIO_WATER_DRY_AIR) / MOLAR_MASS_DRY_AIR;
+            // J/(g·K) = kJ/(kg·K)
+            moles * real_gas::residual_entropy(t_kelvin, pressure, x_w)
+                - real_gas::residual_entropy(ZERO_CELSIUS_AS_KELVIN, STANDARD_ATMOSPHERE_SI, 0.0)
+                    / MOLAR_MASS_DRY_AIR
         }
     }
 }
 
+// calculate humidity ratio from dry-bulb and wet-bulb temperatures
+pub(crate) fn humidity_ratio_from_t_wet_bulb<F: Float>(
+    t_dry_bulb: F,
+    t_wet_bulb: F,
+    pressure: F,
+    options: &ModelOptions,
+) -> Result<F, PsychroidError> {
+    check_range_t_dry_bulb(t_wet_bulb.to_f64(), UnitSystem::SI)?;
+    let saturation_pressure = saturation_pressure_si(t_wet_bulb, options.saturation_reference);
+    let saturation_pressure =
+        enhancement_factor(t_wet_bulb, saturation_pressure, pressure, options)
+            * saturation_pressure;
+    if saturation_pressure >= pressure {
+        return Err(PsychroidError::PressureBelowSaturation {
+            pressure: pressure.to_f64(),
+            saturation_pressure: saturation_pressure.to_f64(),
+            unit: UnitSystem::SI,
+        });
+    }
+    let saturation_humidity_ratio =
+        F::of(MASS_RATIO_WATER_DRY_AIR) * saturation_pressure / (pressure - saturation_pressure);
+    let humidity_ratio = match options.enthalpy_model {
+        EnthalpyModel::Linear => humidity_ratio_from_t_wet_bulb_linear(
+            t_dry_bulb,
+            t_wet_bulb,
+            saturation_humidity_ratio,
+            options,
+        ),
+        EnthalpyModel::LinearIp | EnthalpyModel::VariableHeatCapacity => {
+            humidity_ratio_from_t_wet_bulb_enthalpy(
+                t_dry_bulb,
+                t_wet_bulb,
+                saturation_humidity_ratio,
+                options,
+            )
+        }
+    };
+    Ok(humidity_ratio)
+}
+
+/// Calculate the humidity ratio from the energy balance of the adiabatic saturation
+/// for the enthalpy models other than the linear one
+/// <script src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+///
+/// # Formula
+/// $$
+/// W = \\frac{W_s^\* (h_g(t^\*) - h_c(t^\*)) - (h_\\mathrm{da}(t) - h_\\mathrm{da}(t^\*))}{h_g(t) - h_c(t^\*)}
+/// $$
+/// where \\(h_c\\) is the specific enthalpy of liquid water or ice at the wet-bulb temperature.
+/// With the linear model, this reduces to ASHRAE Handbook - Fundamentals (2017) Ch. 1 Eq. (33) and (35).
+fn humidity_ratio_from_t_wet_bulb_enthalpy<F: Float>(
+    t_dry_bulb: F,
+    t_wet_bulb: F,
+    saturation_humidity_ratio: F,
+    options: &ModelOptions,
+) -> F {
+    let model = options.enthalpy_model;
+    let condensed_water_enthalpy = condensed_water_enthalpy(t_wet_bulb, options);
+    let dry_air_enthalpy_difference = specific_enthalpy_dry_air_si(t_dry_bulb, model)
+        - specific_enthalpy_dry_air_si(t_wet_bulb, model);
+    (saturation_humidity_ratio
+        * (specific_enthalpy_water_vapor_si(t_wet_bulb, model) - condensed_water_enthalpy)
+        - dry_air_enthalpy_difference)
+        / (specific_enthalpy_water_vapor_si(t_dry_bulb, model) - condensed_water_enthalpy)
+}
+
 /// ASHRAE Handbook - Fundamentals (2017) SI Ch. 1 Eq. (33) and (35)
-fn humidity_ratio_from_t_wet_bulb_si(
-    t_dry_bulb: f64,
-    t_wet_bulb: f64,
-    saturation_humidity_ratio: f64,
-) -> f64 {
-    match t_wet_bulb >= FREEZING_POINT_WATER_SI {
+fn humidity_ratio_from_t_wet_bulb_linear<F: Float>(
+    t_dry_bulb: F,
+    t_wet_bulb: F,
+    saturation_humidity_ratio: F,
+    options: &ModelOptions,
+) -> F {
+    let c = F::of;
+    match is_over_water(t_wet_bulb, options) {
         true => {
-            ((2501.0 - 2.326 * t_wet_bulb) * saturation_humidity_ratio
-                - 1.006 * (t_dry_bulb - t_wet_bulb))
-                / (2501.0 + 1.860 * t_dry_bulb - 4.186 * t_wet_bulb)
+            ((c(2501.0) - c(2.326) * t_wet_bulb) * saturation_humidity_ratio
+                - c(1.006) * (t_dry_bulb - t_wet_bulb))
+                / (c(2501.0) + c(1.860) * t_dry_bulb - c(4.186) * t_wet_bulb)
         }
         false => {
-            ((2830. - 0.24 * t_wet_bulb) * saturation_humidity_ratio
-                - 1.006 * (t_dry_bulb - t_wet_bulb))
-                / (2830.0 + 1.860 * t_dry_bulb - 2.100 * t_wet_bulb)
+            ((c(2830.) - c(0.24) * t_wet_bulb) * saturation_humidity_ratio
+                - c(1.006) * (t_dry_bulb - t_wet_bulb))
+                / (c(2830.0) + c(1.860) * t_dry_bulb - c(2.100) * t_wet_bulb)
         }
     }
 }
 
 /// Calculate wet-bulb temperature from dry-bulb temperature and humidity ratio
-fn t_wet_bulb_from_humidity_ratio(
+pub(crate) fn t_wet_bulb_from_humidity_ratio(
     t_dry_bulb: f64,
     humidity_ratio: f64,
     pressure: f64,
-    unit: UnitSystem,
+    options: &ModelOptions,
 ) -> Result<f64, PsychroidError> {
-    match unit {
-        UnitSystem::SI => t_wet_bulb_from_humidity_ratio_si(t_dry_bulb, humidity_ratio, pressure),
-        UnitSystem::IP => t_wet_bulb_from_humidity_ratio_ip(t_dry_bulb, humidity_ratio, pressure),
+    t_wet_bulb_solution(t_dry_bulb, humidity_ratio, pressure, options).map(|solution| solution.root)
+}
+
+/// Solve the wet-bulb temperature from dry-bulb temperature and humidity ratio,
+/// with the iteration count and the residual of the solution
+fn t_wet_bulb_solution(
+    t_dry_bulb: f64,
+    humidity_ratio: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> Result<Solution, PsychroidError> {
+    match options.enthalpy_model {
+        EnthalpyModel::Linear => {
+            t_wet_bulb_from_humidity_ratio_linear(t_dry_bulb, humidity_ratio, pressure, options)
+        }
+        EnthalpyModel::LinearIp | EnthalpyModel::VariableHeatCapacity => {
+            // Newton-Raphson method on the energy balance, with the derivative by central difference,
+            // safeguarded by bisection between the lower limit of the range and the initial guess
+            let f = |t_wet_bulb: f64| {
+                humidity_ratio_from_t_wet_bulb(t_dry_bulb, t_wet_bulb, pressure, options)
+                    .unwrap_or(f64::NAN)
+                    - humidity_ratio
+            };
+            let step = 1.0E-04;
+            let fd = |t_wet_bulb: f64| {
+                let deriv = (f(t_wet_bulb + step) - f(t_wet_bulb - step)) / (2.0 * step);
+                (f(t_wet_bulb), deriv)
+            };
+            let (t_init, t_upper) =
+                match t_wet_bulb_initial_guess(t_dry_bulb, humidity_ratio, pressure, options)? {
+                    Some(guess) => guess,
+                    None => return Ok(Solution::initial(t_dry_bulb, f(t_dry_bulb))),
+                };
+            let bracket = (t_dry_bulb_range(UnitSystem::SI).0, t_upper);
+            find_root_bracketed(t_init, bracket, fd, &options.solver)
+        }
+    }
+}
+
+/// Returns the initial guess of the wet-bulb temperature for Newton-Raphson method and the upper end of the bracket,
+/// or `None` if moist air is saturated, i.e. the wet-bulb temperature equals the dry-bulb temperature
+/// <script src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+///
+/// # Formula
+/// $$
+/// \\begin{align}
+/// t^\\* &= t \\arctan\\left(0.151977 (\\phi + 8.313659)^{1/2}\\right) + \\arctan(t + \\phi) - \\arctan(\\phi - 1.676331) \\\\
+/// &+ 0.00391838~\\phi^{3/2} \\arctan(0.023101~\\phi) - 4.686035
+/// \\end{align}
+/// $$
+/// where \\(\\phi\\) is the relative humidity in %.
+///
+/// R. Stull, "Wet-bulb temperature from relative humidity and air temperature",
+/// Journal of Applied Meteorology and Climatology 50 (2011) 2267-2269.
+/// The approximation holds within about 1 K at standard pressure for -20 to 50 °C and 5 to 99 %,
+/// and within 1.5 K for cold and dry air and below the freezing point, where the wet bulb is iced.
+/// Outside this range, the initial guess is the upper end of the bracket, i.e. the dry-bulb temperature,
+/// or slightly below the boiling point if the dry-bulb temperature is above it.
+fn t_wet_bulb_initial_guess(
+    t_dry_bulb: f64,
+    humidity_ratio: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> Result<Option<(f64, f64)>, PsychroidError> {
+    let saturation_humidity_ratio = saturation_humidity_ratio(t_dry_bulb, pressure, options);
+    if humidity_ratio >= saturation_humidity_ratio {
+        return Ok(None);
+    }
+    if !saturation_humidity_ratio.is_finite() {
+        let t_boiling_point = t_dew_point_from_partial_pressure(pressure, pressure, options)?;
+        return Ok(Some((t_boiling_point - 0.01, t_boiling_point - 0.01)));
+    }
+    if !(-20.0..=50.0).contains(&t_dry_bulb) {
+        return Ok(Some((t_dry_bulb, t_dry_bulb)));
     }
+    // relative humidity in % from the ratio of the partial pressures
+    let phi = 100.0 * humidity_ratio * (MASS_RATIO_WATER_DRY_AIR + saturation_humidity_ratio)
+        / (saturation_humidity_ratio * (MASS_RATIO_WATER_DRY_AIR + humidity_ratio));
+    // through num-traits, which falls back to libm without the standard library
+    let atan: fn(f64) -> f64 = num_traits::Float::atan;
+    let t_wet_bulb = t_dry_bulb * atan(0.151977 * (phi + 8.313659).sqrt()) + atan(t_dry_bulb + phi)
+        - atan(phi - 1.676331)
+        + 0.00391838 * phi * phi.sqrt() * atan(0.023101 * phi)
+        - 4.686035;
+    let t_init = t_wet_bulb.clamp(t_dry_bulb_range(UnitSystem::SI).0, t_dry_bulb);
+    Ok(Some((t_init, t_dry_bulb)))
 }
 
 /// Calculate wet-bulb temperature from dry-bulb temperature and humidity ratio
@@ -648,606 +2265,2210 @@ fn t_wet_bulb_from_humidity_ratio(
 /// \\end{align}
 /// $$
 ///
-fn t_wet_bulb_from_humidity_ratio_si(
+fn t_wet_bulb_from_humidity_ratio_linear(
     t_dry_bulb: f64,
     humidity_ratio: f64,
     pressure: f64,
-) -> Result<f64, PsychroidError> {
-    let f = |t_wet_bulb: f64| {
-        let saturation_water_vapor = SaturatedWaterVapor::new_relaxed(t_wet_bulb, UnitSystem::SI);
-        let saturation_pressure = saturation_water_vapor.saturation_pressure();
+    options: &ModelOptions,
+) -> Result<Solution, PsychroidError> {
+    // residual and its derivative at once, sharing the saturation pressure
+    let fd = |t_wet_bulb: f64| {
+        let (pws, deriv_pws) =
+            saturation_pressure_and_deriv_si(t_wet_bulb, options.saturation_reference);
+        let factor = enhancement_factor(t_wet_bulb, pws, pressure, options);
+        let saturation_pressure = factor * pws;
         let saturation_humidity_ratio =
             MASS_RATIO_WATER_DRY_AIR * saturation_pressure / (pressure - saturation_pressure);
-        match t_wet_bulb >= FREEZING_POINT_WATER_SI {
-            true => {
+        let deriv_saturation_humidity_ratio =
+            MASS_RATIO_WATER_DRY_AIR * pressure * factor * deriv_pws
+                / (pressure - saturation_pressure).powi(2);
+        match is_over_water(t_wet_bulb, options) {
+            true => (
                 humidity_ratio * (2501.0 + 1.860 * t_dry_bulb - 4.186 * t_wet_bulb)
                     - (2501.0 - 2.326 * t_wet_bulb) * saturation_humidity_ratio
-                    + 1.006 * (t_dry_bulb - t_wet_bulb)
-            }
-            false => {
+                    + 1.006 * (t_dry_bulb - t_wet_bulb),
+                -4.186 * humidity_ratio - 2501.0 * deriv_saturation_humidity_ratio
+                    + 2.326 * saturation_humidity_ratio
+                    + 2.326 * t_wet_bulb * deriv_saturation_humidity_ratio
+                    - 1.006,
+            ),
+            false => (
                 humidity_ratio * (2830.0 + 1.860 * t_dry_bulb - 2.100 * t_wet_bulb)
                     - (2830.0 - 0.240 * t_wet_bulb) * saturation_humidity_ratio
-                    + 1.006 * (t_dry_bulb - t_wet_bulb)
+                    + 1.006 * (t_dry_bulb - t_wet_bulb),
+                -2.100 * humidity_ratio - 2830.0 * deriv_saturation_humidity_ratio
+                    + 0.240 * saturation_humidity_ratio
+                    + 0.240 * t_wet_bulb * deriv_saturation_humidity_ratio
+                    - 1.006,
+            ),
+        }
+    };
+    let (t_init, t_upper) =
+        match t_wet_bulb_initial_guess(t_dry_bulb, humidity_ratio, pressure, options)? {
+            Some(guess) => guess,
+            None => return Ok(Solution::initial(t_dry_bulb, fd(t_dry_bulb).0)),
+        };
+    let bracket = (t_dry_bulb_range(UnitSystem::SI).0, t_upper);
+    find_root_bracketed(t_init, bracket, fd, &options.solver)
+}
+
+/// Calculates the humidity ratio from dry-bulb temperature and relative humidity
+///
+/// Above the boiling point, the partial pressure of water vapor must be lower than the total pressure,
+/// otherwise `OutOfRange` is returned with the relative humidity at which it reaches the total pressure
+/// as the (exclusive) upper bound.
+pub(crate) fn humidity_ratio_from_relative_humidity(
+    t_dry_bulb: f64,
+    relative_humidity: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    if relative_humidity == 0.0 {
+        return Ok(0.0);
+    }
+    // calculate vapor pressure from relative humidity
+    check_range_t_dry_bulb(t_dry_bulb, UnitSystem::SI)?;
+    let pws = saturation_pressure_si(t_dry_bulb, options.saturation_reference);
+    let pw = relative_humidity * enhancement_factor(t_dry_bulb, pws, pressure, options) * pws;
+    if pw >= pressure {
+        return Err(PsychroidError::OutOfRange {
+            quantity: "relative humidity",
+            value: relative_humidity,
+            min: 0.0,
+            max: relative_humidity * pressure / pw,
+            unit: None,
+        });
+    }
+    Ok(MASS_RATIO_WATER_DRY_AIR * pw / (pressure - pw))
+}
+
+/// Calculates the relative humidity from dry-bulb temperature and humidity ratio
+pub(crate) fn relative_humidity_from_humidity_ratio<F: Float>(
+    t_dry_bulb: F,
+    humidity_ratio: F,
+    pressure: F,
+    options: &ModelOptions,
+) -> Result<F, PsychroidError> {
+    let water_pressure =
+        pressure * humidity_ratio / (F::of(MASS_RATIO_WATER_DRY_AIR) + humidity_ratio);
+    check_range_t_dry_bulb(t_dry_bulb.to_f64(), UnitSystem::SI)?;
+    let pws = saturation_pressure_si(t_dry_bulb, options.saturation_reference);
+    Ok(water_pressure / (enhancement_factor(t_dry_bulb, pws, pressure, options) * pws))
+}
+
+/// Calculate the dew point temperature from dry-bulb temperature and relative humidity
+/// If the relative humidity is 0 or very close to 0, NaN is returned as the dew point temperature
+pub(crate) fn t_dew_point_from_humidity_ratio(
+    humidity_ratio: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    t_dew_point_solution(humidity_ratio, pressure, options).map(|solution| solution.root)
+}
+
+/// Solve the dew point temperature from humidity ratio, with the iteration count and the residual.
+/// For the humidity ratio of 0 or very close to 0, NaN is returned as the solution without iteration.
+fn t_dew_point_solution(
+    humidity_ratio: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> Result<Solution, PsychroidError> {
+    if humidity_ratio <= f64::EPSILON {
+        return Ok(Solution::initial(f64::NAN, f64::NAN));
+    }
+    let partial_water_vapor_pressure =
+        pressure * humidity_ratio / (MASS_RATIO_WATER_DRY_AIR + humidity_ratio);
+    partial_pressure_solution(partial_water_vapor_pressure, pressure, options)
+}
+
+/// Calculate the saturation temperature for a given partial pressure of water vapor in \\(\\mathrm{Pa}\\)
+///
+/// The initial guess is given by the Magnus formula, see `t_dew_point_initial_guess`.
+pub(crate) fn t_dew_point_from_partial_pressure(
+    partial_water_vapor_pressure: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    partial_pressure_solution(partial_water_vapor_pressure, pressure, options)
+        .map(|solution| solution.root)
+}
+
+/// Solve the saturation temperature for a given partial pressure of water vapor,
+/// with the iteration count and the residual of the solution
+fn partial_pressure_solution(
+    partial_water_vapor_pressure: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> Result<Solution, PsychroidError> {
+    // the derivative of the enhancement factor is negligible
+    let fd = |t: f64| {
+        let (pws, deriv_pws) = saturation_pressure_and_deriv_si(t, options.saturation_reference);
+        let factor = enhancement_factor(t, pws, pressure, options);
+        (
+            factor * pws - partial_water_vapor_pressure,
+            factor * deriv_pws,
+        )
+    };
+    let t_init = t_dew_point_initial_guess(partial_water_vapor_pressure, options);
+    // the saturation pressure increases from the lower limit of the range up to infinity at the critical point
+    find_root_bracketed(
+        t_init,
+        t_dry_bulb_range(UnitSystem::SI),
+        fd,
+        &options.solver,
+    )
+}
+
+/// Returns the initial guess of the saturation temperature for a given partial pressure of water vapor in Pa
+/// <script src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+///
+/// # Formula
+/// The Magnus formula \\(p_\\mathrm{ws} = c \\exp(a t / (b + t))\\) solved for the temperature:
+///
+/// $$
+/// t = \\frac{b \\alpha}{a - \\alpha}, \\quad \\alpha = \\ln(p_\\mathrm{w} / c)
+/// $$
+///
+/// with \\(a = 17.625\\), \\(b = 243.04\\), \\(c = 610.94\\) over water and
+/// \\(a = 22.587\\), \\(b = 273.86\\), \\(c = 611.21\\) over ice.
+///
+/// O.A. Alduchov and R.E. Eskridge, "Improved Magnus form approximation of saturation vapor pressure",
+/// Journal of Applied Meteorology 35 (1996) 601-609.
+fn t_dew_point_initial_guess(partial_water_vapor_pressure: f64, options: &ModelOptions) -> f64 {
+    let over_ice = options.saturation_reference == SaturationReference::Ice
+        && partial_water_vapor_pressure < TRIPLE_POINT_PRESSURE_SI;
+    let (a, b, c) = match over_ice {
+        true => (22.587, 273.86, 611.21),
+        false => (17.625, 243.04, 610.94),
+    };
+    let alpha = (partial_water_vapor_pressure / c).ln();
+    b * alpha / (a - alpha)
+}
+
+pub(crate) fn humidity_ratio_from_t_dew_point<F: Float>(
+    t_dew_point: F,
+    pressure: F,
+    options: &ModelOptions,
+) -> Result<F, PsychroidError> {
+    check_range_t_dry_bulb(t_dew_point.to_f64(), UnitSystem::SI)?;
+    let saturation_pressure = saturation_pressure_si(t_dew_point, options.saturation_reference);
+    let saturation_pressure =
+        enhancement_factor(t_dew_point, saturation_pressure, pressure, options)
+            * saturation_pressure;
+    if saturation_pressure >= pressure {
+        return Err(PsychroidError::PressureBelowSaturation {
+            pressure: pressure.to_f64(),
+            saturation_pressure: saturation_pressure.to_f64(),
+            unit: UnitSystem::SI,
+        });
+    }
+    Ok(F::of(MASS_RATIO_WATER_DRY_AIR) * saturation_pressure / (pressure - saturation_pressure))
+}
+
+/// Returns true if water at the given temperature is in equilibrium as liquid water,
+/// i.e. at or above the triple point, or below it with the saturation referenced to supercooled water,
+/// consistently with the branch of the saturation pressure
+fn is_over_water<F: Float>(t: F, options: &ModelOptions) -> bool {
+    !is_over_ice(t, options.saturation_reference)
+}
+
+/// Calculate the humidity ratio of saturated moist air at the given dry-bulb temperature.
+/// Returns infinity if the saturation pressure exceeds the total pressure.
+pub(crate) fn saturation_humidity_ratio<F: Float>(
+    t_dry_bulb: F,
+    pressure: F,
+    options: &ModelOptions,
+) -> F {
+    let pws = saturation_pressure_si(t_dry_bulb, options.saturation_reference);
+    let saturation_pressure = enhancement_factor(t_dry_bulb, pws, pressure, options) * pws;
+    match saturation_pressure < pressure {
+        true => {
+            F::of(MASS_RATIO_WATER_DRY_AIR) * saturation_pressure / (pressure - saturation_pressure)
+        }
+        false => F::of(f64::INFINITY),
+    }
+}
+
+/// Calculate the specific enthalpy of condensed water in \\(\\mathrm{kJ/kg_w}\\), i.e. liquid water
+/// at or above the triple point and ice below, unless the saturation is referenced to supercooled water
+///
+/// # Formula
+/// $$
+/// h_\\mathrm{w} = 4.186~t, \\quad h_\\mathrm{i} = -333.4 + 2.1~t
+/// $$
+///
+/// ASHRAE Handbook - Fundamentals (2017) Ch. 1
+fn condensed_water_enthalpy<F: Float>(t_dry_bulb: F, options: &ModelOptions) -> F {
+    let c = F::of;
+    match is_over_water(t_dry_bulb, options) {
+        true => c(4.186) * t_dry_bulb,
+        false => c(-333.4) + c(2.1) * t_dry_bulb,
+    }
+}
+
+/// Calculate the specific heat per unit mass of moist air from dry-bulb temperature and
+/// the humidity ratio of the water vapor, see [`MoistAir::specific_heat`]
+pub(crate) fn specific_heat_from_humidity_ratio<F: Float>(
+    t_dry_bulb: F,
+    humidity_ratio: F,
+    options: &ModelOptions,
+) -> F {
+    let c = F::of;
+    let cp_per_dry_air = match options.enthalpy_model {
+        EnthalpyModel::Linear => c(1.006) + c(1.860) * humidity_ratio,
+        EnthalpyModel::LinearIp => {
+            c(SPECIFIC_HEAT_DRY_AIR_IP_EDITION)
+                + c(SPECIFIC_HEAT_WATER_VAPOR_IP_EDITION) * humidity_ratio
+        }
+        EnthalpyModel::VariableHeatCapacity => {
+            let t_kelvin = t_celsius_to_t_kelvin(t_dry_bulb);
+            specific_heat_dry_air(t_kelvin) + humidity_ratio * specific_heat_water_vapor(t_kelvin)
+        }
+    };
+    cp_per_dry_air / (c(1.0) + humidity_ratio)
+}
+
+/// Calculate the specific enthalpy from dry-bulb temperature and humidity ratio
+///
+/// ASHRAE Handbook - Fundamentals (2017) SI Ch. 1 Eq. (30)
+///
+/// The specific enthalpies of dry air and water vapor follow [`ModelOptions::enthalpy_model`].
+/// If the humidity ratio exceeds saturation, the excess water is condensed,
+/// and its enthalpy is added to that of saturated moist air
+pub(crate) fn specific_enthalpy_from_humidity_ratio<F: Float>(
+    t_dry_bulb: F,
+    humidity_ratio: F,
+    pressure: F,
+    options: &ModelOptions,
+) -> F {
+    let saturation_humidity_ratio = saturation_humidity_ratio(t_dry_bulb, pressure, options);
+    let vapor_humidity_ratio = humidity_ratio.min(saturation_humidity_ratio);
+    let model = options.enthalpy_model;
+    let specific_enthalpy = specific_enthalpy_dry_air_si(t_dry_bulb, model)
+        + vapor_humidity_ratio * specific_enthalpy_water_vapor_si(t_dry_bulb, model);
+    let condensed_water_content = humidity_ratio - vapor_humidity_ratio;
+    specific_enthalpy
+        + residual_enthalpy(t_dry_bulb, vapor_humidity_ratio, pressure, options)
+        + match condensed_water_content > F::of(0.0) {
+            true => condensed_water_content * condensed_water_enthalpy(t_dry_bulb, options),
+            false => F::of(0.0),
+        }
+}
+
+/// Calculate the humidity ratio from specific enthalpy and dry-bulb temperature
+///
+/// ASHRAE Handbook - Fundamentals (2017) SI Ch. 1 Eq. (30)
+pub(crate) fn humidity_ratio_from_specific_enthalpy(
+    t_dry_bulb: f64,
+    specific_enthalpy: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> f64 {
+    let model = options.enthalpy_model;
+    let ideal = |specific_enthalpy: f64| {
+        (specific_enthalpy - specific_enthalpy_dry_air_si(t_dry_bulb, model))
+            / specific_enthalpy_water_vapor_si(t_dry_bulb, model)
+    };
+    fixed_point(ideal(specific_enthalpy), |humidity_ratio| {
+        ideal(specific_enthalpy - residual_enthalpy(t_dry_bulb, humidity_ratio, pressure, options))
+    })
+}
+
+/// Calculate the deviation of the specific enthalpy of the selected enthalpy model from the linear model,
+/// which corrects the closed-form solutions of the linear model. This is zero for the linear model.
+fn enthalpy_model_deviation(t_dry_bulb: f64, humidity_ratio: f64, options: &ModelOptions) -> f64 {
+    match options.enthalpy_model {
+        EnthalpyModel::Linear => 0.0,
+        model => {
+            let linear = EnthalpyModel::Linear;
+            specific_enthalpy_dry_air_si(t_dry_bulb, model)
+                - specific_enthalpy_dry_air_si(t_dry_bulb, linear)
+                + humidity_ratio
+                    * (specific_enthalpy_water_vapor_si(t_dry_bulb, model)
+                        - specific_enthalpy_water_vapor_si(t_dry_bulb, linear))
+        }
+    }
+}
+
+/// Calculate the dry-bulb temperature from specific enthalpy and humidity ratio
+///
+/// ASHRAE Handbook - Fundamentals (2017) SI Ch. 1 Eq. (30)
+///
+/// If the humidity ratio exceeds saturation at the resulting temperature (fog or ice fog),
+/// the temperature is searched by Brent's method between this temperature and the dew point,
+/// as the enthalpy including the condensed water is discontinuous at the triple point.
+pub(crate) fn t_dry_bulb_from_specific_enthalpy_humidity_ratio(
+    specific_enthalpy: f64,
+    humidity_ratio: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    let ideal = |specific_enthalpy: f64| {
+        (specific_enthalpy - humidity_ratio * 2501.0) / (1.006 + humidity_ratio * 1.860)
+    };
+    let t_dry_bulb = fixed_point(ideal(specific_enthalpy), |t_dry_bulb| {
+        ideal(
+            specific_enthalpy
+                - residual_enthalpy(t_dry_bulb, humidity_ratio, pressure, options)
+                - enthalpy_model_deviation(t_dry_bulb, humidity_ratio, options),
+        )
+    });
+    if humidity_ratio <= saturation_humidity_ratio(t_dry_bulb, pressure, options) {
+        return Ok(t_dry_bulb);
+    }
+    // the enthalpy of condensed water is lower than that of water vapor,
+    // so that the solution lies between the temperature above and the dew point
+    let t_dew_point = t_dew_point_from_humidity_ratio(humidity_ratio, pressure, options)?;
+    let f = |t: f64| {
+        specific_enthalpy_from_humidity_ratio(t, humidity_ratio, pressure, options)
+            - specific_enthalpy
+    };
+    let root = brent(t_dry_bulb, t_dew_point, f, &options.solver)?;
+    Ok(root)
+}
+
+/// Calculate the specific volume from dry-bulb temperature and humidity ratio
+///
+/// ASHRAE Handbook - Fundamentals (2017) SI Ch. 1 Eq. (26)
+pub(crate) fn specific_volume_from_humidity_ratio<F: Float>(
+    t_dry_bulb: F,
+    humidity_ratio: F,
+    pressure: F,
+    options: &ModelOptions,
+) -> F {
+    let c = F::of;
+    // specific volume in m³/kg_da, pressure in kPa
+    let specific_volume =
+        c(0.287042) * (t_dry_bulb + c(273.15)) * (c(1.0) + c(1.607858) * humidity_ratio)
+            / (pressure * c(0.001));
+    specific_volume * compressibility_factor(t_dry_bulb, humidity_ratio, pressure, options)
+}
+
+/// Calculate the humidity ratio from specific volume and dry-bulb temperature
+///
+/// ASHRAE Handbook - Fundamentals (2017) SI Ch. 1 Eq. (26)
+pub(crate) fn humidity_ratio_from_specific_volume(
+    t_dry_bulb: f64,
+    specific_volume: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> f64 {
+    let specific_volume_dry_air =
+        specific_volume_from_humidity_ratio(t_dry_bulb, 0.0, pressure, options)
+            / compressibility_factor(t_dry_bulb, 0.0, pressure, options);
+    let ideal = |specific_volume: f64| (specific_volume / specific_volume_dry_air - 1.0) / 1.607858;
+    fixed_point(ideal(specific_volume), |humidity_ratio| {
+        ideal(
+            specific_volume / compressibility_factor(t_dry_bulb, humidity_ratio, pressure, options),
+        )
+    })
+}
+
+/// Calculate the dry-bulb temperature from specific volume and humidity ratio
+///
+/// ASHRAE Handbook - Fundamentals (2017) SI Ch. 1 Eq. (26)
+pub(crate) fn t_dry_bulb_from_specific_volume_humidity_ratio(
+    specific_volume: f64,
+    humidity_ratio: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> f64 {
+    let ideal = |specific_volume: f64| {
+        specific_volume * (pressure * 0.001) / (0.287042 * (1.0 + 1.607858 * humidity_ratio))
+            - 273.15
+    };
+    fixed_point(ideal(specific_volume), |t_dry_bulb| {
+        ideal(
+            specific_volume / compressibility_factor(t_dry_bulb, humidity_ratio, pressure, options),
+        )
+    })
+}
+
+/// Calculate the specific entropy in \\(\\mathrm{kJ/(kg_{da} \\cdot K)}\\) of the gas phase of moist air,
+/// i.e. of dry air and water vapor each at its partial pressure, see [`MoistAir::specific_entropy`].
+///
+/// The heat capacities follow [`ModelOptions::enthalpy_model`] and the residual entropy [`ModelOptions::gas_model`],
+/// consistent with [`specific_enthalpy_from_humidity_ratio`]. The humidity ratio is that of the water vapor.
+pub(crate) fn specific_entropy_gas(
+    t_dry_bulb: f64,
+    humidity_ratio: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> f64 {
+    let model = options.enthalpy_model;
+    let p_w = humidity_ratio / (MASS_RATIO_WATER_DRY_AIR + humidity_ratio) * pressure;
+    let s_da = entropy_change_dry_air_si(t_dry_bulb, model)
+        - R_DA_SI * 0.001 * ((pressure - p_w) / STANDARD_ATMOSPHERE_SI).ln();
+    let s_w = match humidity_ratio > 0.0 {
+        true => {
+            SPECIFIC_ENTROPY_VAPOR_TRIPLE_POINT
+                + entropy_change_water_vapor_si(TRIPLE_POINT_WATER_SI, t_dry_bulb, model)
+                - R_W_SI * 0.001 * (p_w / TRIPLE_POINT_PRESSURE_SI).ln()
+        }
+        false => 0.0,
+    };
+    s_da + humidity_ratio * s_w + residual_entropy(t_dry_bulb, humidity_ratio, pressure, options)
+}
+
+/// Calculate the specific enthalpy in \\(\\mathrm{kJ/kg_{da}}\\) of the gas phase of moist air,
+/// i.e. [`specific_enthalpy_from_humidity_ratio`] without condensation, counterpart of [`specific_entropy_gas`]
+fn specific_enthalpy_gas(
+    t_dry_bulb: f64,
+    humidity_ratio: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> f64 {
+    let model = options.enthalpy_model;
+    specific_enthalpy_dry_air_si(t_dry_bulb, model)
+        + humidity_ratio * specific_enthalpy_water_vapor_si(t_dry_bulb, model)
+        + residual_enthalpy(t_dry_bulb, humidity_ratio, pressure, options)
+}
+
+/// Solve \\(x = g(x)\\) by fixed-point iteration, starting from `x`.
+/// Used to apply the real gas corrections to the closed-form solutions of the ideal gas model,
+/// for which the iteration terminates immediately.
+fn fixed_point(mut x: f64, g: impl Fn(f64) -> f64) -> f64 {
+    for _ in 0..MAX_ITER_COUNT {
+        let next = g(x);
+        if (next - x).abs() < TOLERANCE {
+            return next;
+        }
+        x = next;
+    }
+    x
+}
+
+/// Calculate the humidity ratio for a given dry-bulb temperature from another property
+fn humidity_ratio_from_property(
+    t_dry_bulb: f64,
+    property: Property,
+    pressure: f64,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    match property {
+        Property::TDryBulb(_) => Err(PsychroidError::DependentProperties(property, property)),
+        Property::HumidityRatio(humidity_ratio) => Ok(humidity_ratio),
+        Property::RelativeHumidity(relative_humidity) => {
+            if !(0.0..=1.0 + TOLERANCE).contains(&relative_humidity) {
+                return Err(PsychroidError::InvalidRelativeHumidity(relative_humidity));
+            }
+            humidity_ratio_from_relative_humidity(t_dry_bulb, relative_humidity, pressure, options)
+        }
+        Property::TWetBulb(t_wet_bulb) => {
+            humidity_ratio_from_t_wet_bulb(t_dry_bulb, t_wet_bulb, pressure, options)
+        }
+        Property::TDewPoint(t_dew_point) => {
+            humidity_ratio_from_t_dew_point(t_dew_point, pressure, options)
+        }
+        Property::SpecificEnthalpy(specific_enthalpy) => Ok(humidity_ratio_from_specific_enthalpy(
+            t_dry_bulb,
+            specific_enthalpy,
+            pressure,
+            options,
+        )),
+        Property::SpecificVolume(specific_volume) => Ok(humidity_ratio_from_specific_volume(
+            t_dry_bulb,
+            specific_volume,
+            pressure,
+            options,
+        )),
+    }
+}
+
+/// Calculate the dry-bulb temperature from a pair of properties, neither of which is the dry-bulb temperature.
+///
+/// The `provider` determines the humidity ratio for a given dry-bulb temperature,
+/// and the `target` is used to evaluate the residual.
+/// Closed-form solutions are used where available, and otherwise Newton-Raphson method safeguarded by bisection
+/// in the bracket of physically possible dry-bulb temperatures.
+fn t_dry_bulb_from_property_pair(
+    provider: Property,
+    target: Property,
+    pressure: f64,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    if provider.is_humidity_only() {
+        // humidity ratio is known, closed-form or one-dimensional inversion
+        let humidity_ratio = humidity_ratio_from_property(f64::NAN, provider, pressure, options)?;
+        match target {
+            Property::RelativeHumidity(relative_humidity) => {
+                if !(f64::EPSILON..=1.0 + TOLERANCE).contains(&relative_humidity) {
+                    return Err(PsychroidError::InvalidRelativeHumidity(relative_humidity));
+                }
+                let partial_water_vapor_pressure =
+                    pressure * humidity_ratio / (MASS_RATIO_WATER_DRY_AIR + humidity_ratio);
+                return t_dew_point_from_partial_pressure(
+                    partial_water_vapor_pressure / relative_humidity,
+                    pressure,
+                    options,
+                );
+            }
+            Property::SpecificEnthalpy(specific_enthalpy) => {
+                return t_dry_bulb_from_specific_enthalpy_humidity_ratio(
+                    specific_enthalpy,
+                    humidity_ratio,
+                    pressure,
+                    options,
+                );
+            }
+            Property::SpecificVolume(specific_volume) => {
+                return Ok(t_dry_bulb_from_specific_volume_humidity_ratio(
+                    specific_volume,
+                    humidity_ratio,
+                    pressure,
+                    options,
+                ));
             }
+            _ => {}
         }
+    }
+    if let (Property::RelativeHumidity(relative_humidity), Property::SpecificEnthalpy(h)) =
+        (provider, target)
+    {
+        return t_dry_bulb_from_specific_enthalpy_relative_humidity(
+            h,
+            relative_humidity,
+            pressure,
+            options,
+        );
+    }
+
+    // target humidity ratio for the pairs (wet-bulb, humidity ratio) and (wet-bulb, dew point)
+    let target_humidity_ratio = match target.is_humidity_only() {
+        true => humidity_ratio_from_property(f64::NAN, target, pressure, options)?,
+        false => f64::NAN,
     };
-    let d = |t_wet_bulb: f64| {
-        let saturation_water_vapor = SaturatedWaterVapor::new_relaxed(t_wet_bulb, UnitSystem::SI);
-        let saturation_pressure = saturation_water_vapor.saturation_pressure();
-        let saturation_humidity_ratio =
-            MASS_RATIO_WATER_DRY_AIR * saturation_pressure / (pressure - saturation_pressure);
-        let deriv_saturation_humidity_ratio = MASS_RATIO_WATER_DRY_AIR
-            * pressure
-            * saturation_water_vapor.deriv_saturation_pressure()
-            / (pressure - saturation_pressure).powi(2);
-        match t_wet_bulb >= FREEZING_POINT_WATER_SI {
-            true => {
-                -4.186 * humidity_ratio - 2501.0 * deriv_saturation_humidity_ratio
-                    + 2.326 * saturation_humidity_ratio
-                    + 2.326 * t_wet_bulb * deriv_saturation_humidity_ratio
-                    - 1.006
+    let residual = |t_dry_bulb: f64| -> Result<f64, PsychroidError> {
+        let humidity_ratio = humidity_ratio_from_property(t_dry_bulb, provider, pressure, options)?;
+        Ok(match target {
+            Property::HumidityRatio(_) | Property::TDewPoint(_) => {
+                humidity_ratio - target_humidity_ratio
             }
-            false => {
-                -2.100 * humidity_ratio - 2830.0 * deriv_saturation_humidity_ratio
-                    + 0.240 * saturation_humidity_ratio
-                    + 0.240 * t_wet_bulb * deriv_saturation_humidity_ratio
-                    - 1.006
+            Property::RelativeHumidity(relative_humidity) => {
+                relative_humidity_from_humidity_ratio(
+                    t_dry_bulb,
+                    humidity_ratio,
+                    pressure,
+                    options,
+                )? - relative_humidity
             }
-        }
+            Property::SpecificEnthalpy(specific_enthalpy) => {
+                specific_enthalpy_from_humidity_ratio(t_dry_bulb, humidity_ratio, pressure, options)
+                    - specific_enthalpy
+            }
+            Property::SpecificVolume(specific_volume) => {
+                specific_volume_from_humidity_ratio(t_dry_bulb, humidity_ratio, pressure, options)
+                    - specific_volume
+            }
+            // wet-bulb temperature is always the provider, and dry-bulb temperature never reaches here
+            Property::TDryBulb(_) | Property::TWetBulb(_) => f64::NAN,
+        })
+    };
+    // the last error of the residual is reported instead of the NaN residual passed to the solver
+    let error = Cell::new(None);
+    let f = |t_dry_bulb: f64| {
+        residual(t_dry_bulb).unwrap_or_else(|e| {
+            error.set(Some(e));
+            f64::NAN
+        })
     };
-    let mut convergency = SimpleConvergency {
-        eps: 1e-6f64,
-        max_iter: 50,
+    // central difference, as the residual may involve any pair of properties,
+    // evaluated before the residual itself so that the error of the residual is the last one
+    let step = 1e-4;
+    let fd = |t_dry_bulb: f64| {
+        let deriv = (f(t_dry_bulb + step) - f(t_dry_bulb - step)) / (2.0 * step);
+        (f(t_dry_bulb), deriv)
     };
-    let root = find_root_newton_raphson(t_dry_bulb, &f, &d, &mut convergency)?;
-    Ok(root)
+    // the dry-bulb temperature is not below the wet-bulb temperature, where moist air is saturated,
+    // and the bracket starts slightly below it so that the residual changes its sign for saturated moist air.
+    // The partial pressure of water vapor for a given relative humidity reaches the total pressure
+    // at the upper end, and the residual is finite up to the critical point otherwise.
+    let t_lower = t_dry_bulb_range(UnitSystem::SI).0;
+    let (bracket, t_init) = match (provider, target) {
+        (Property::TWetBulb(t_wet_bulb), _) => (
+            (
+                t_wet_bulb - 1.0E-06,
+                t_dry_bulb_upper_limit(0.0, pressure, options)?,
+            ),
+            t_wet_bulb,
+        ),
+        (Property::RelativeHumidity(relative_humidity), _) => (
+            (
+                t_lower,
+                t_dry_bulb_upper_limit(relative_humidity, pressure, options)?,
+            ),
+            20.0,
+        ),
+        (_, Property::SpecificVolume(specific_volume)) => (
+            (t_lower, t_dry_bulb_upper_limit(0.0, pressure, options)?),
+            t_dry_bulb_from_specific_volume_humidity_ratio(specific_volume, 0.0, pressure, options),
+        ),
+        _ => (
+            (t_lower, t_dry_bulb_upper_limit(0.0, pressure, options)?),
+            20.0,
+        ),
+    };
+    let solution =
+        find_root_bracketed(t_init, bracket, fd, &options.solver).map_err(|e| {
+            match e.failure_reason() {
+                Some(SolverFailureReason::InvalidResidual) => error.take().unwrap_or(e),
+                _ => e,
+            }
+        })?;
+    // within the bracket below the wet-bulb temperature for saturated moist air
+    Ok(match provider {
+        Property::TWetBulb(t_wet_bulb) => solution.root.max(t_wet_bulb),
+        _ => solution.root,
+    })
 }
 
-/// Calculate wet-bulb temperature from dry-bulb temperature and humidity ratio
+/// Returns the upper limit of the dry-bulb temperature for a given relative humidity, slightly below
+/// the temperature where the partial pressure of water vapor reaches the total pressure,
+/// or below the critical point of water where the saturation pressure diverges for dry air,
+/// so that the humidity ratio is finite up to the limit
+fn t_dry_bulb_upper_limit(
+    relative_humidity: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> Result<f64, PsychroidError> {
+    let t_upper = match relative_humidity > 0.0 {
+        true => t_dew_point_from_partial_pressure(pressure / relative_humidity, pressure, options)?,
+        false => CRITICAL_TEMPERATURE_WATER - ZERO_CELSIUS_AS_KELVIN,
+    };
+    Ok(t_upper - 1.0E-06)
+}
+
+/// Calculate the dry-bulb temperature from specific enthalpy and relative humidity.
 /// <script src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 ///
 /// # Formula
-/// The wet-bulb temperature for given dry-bulb temperature and humidity ratio shall satisfy the equation:
+/// The dry-bulb temeprature can be determined by solving the equation:
 /// $$
-/// \\begin{align}
-/// f &= W(1093 + 0.444t - 1.00t^\*) - (1093 - 0.556t^\*) W_s^\* + 0.240(t - t^\*) = 0,\\quad t^\* \geq 32 \\\\
-/// f &= W(1220 + 0.444t - 0.48t^\*) - (1220 - 0.040t^\*) W_s^\* + 0.240(t - t^\*) = 0,\quad t^\* < 32
-/// \\end{align}
+/// \begin{equation}
+/// h = 1.006 t_{db} + W (2501.0 + 1.860 t_{db}), \quad W = 0.621945 \frac{p_{w}}{p - p_{w}}
+/// \end{equation}
+/// $$
+/// By multiplying the equation by \\(p - p_{w}\\), the equation to solve becomes:
+/// $$
+/// \begin{gather}
+/// f(t_{db}) = (2501.0 \times 0.621945 + h) p_{w} + (1.860 \times 0.621945 - 1.006) t_{db}~p_{w} + 1.006 t_{db}~p - h p = 0
+/// \end{gather}
 /// $$
-/// The corresponding root of this equation is searched using Newton-Raphson method.
 /// The derivative of the function is:
 /// $$
-/// \\begin{align}
-/// f' = -1.00W - 1093 \frac{dW_s^\*}{dt^\*} + 0.556W_s^\* + 0.556 t^\* \frac{dW_s^\*}{dt^\*} - 0.240, \quad t \geq 0 \\\\
-/// f' = -0.48W - 1220 \frac{dW_s^\*}{dt^\*} + 0.040W_s^\* + 0.040 t^\* \frac{dW_s^\*}{dt^\*} - 0.240, \quad t < 0
-/// \\end{align}
+/// \begin{gather}
+/// \frac{df}{dt_{db}} = (2501.0 \times 0.621945 + h) \frac{dp_{w}}{dt_{db}} + (1.860 \times 0.621945 - 1.006) p_{w} + (1.860 \times 0.621945 - 1.006) t_{db} \frac{dp_{w}}{dt_{db}} + 1.006 p
+/// \end{gather}
 /// $$
 ///
-fn t_wet_bulb_from_humidity_ratio_ip(
-    t_dry_bulb: f64,
-    humidity_ratio: f64,
+pub(crate) fn t_dry_bulb_from_specific_enthalpy_relative_humidity(
+    specific_enthalpy: f64,
+    relative_humidity: f64,
     pressure: f64,
+    options: &ModelOptions,
 ) -> Result<f64, PsychroidError> {
-    let f = |t_wet_bulb: f64| {
-        let saturation_water_vapor = SaturatedWaterVapor::new_relaxed(t_wet_bulb, UnitSystem::IP);
-        let saturation_pressure = saturation_water_vapor.saturation_pressure();
-        let saturation_humidity_ratio =
-            MASS_RATIO_WATER_DRY_AIR * saturation_pressure / (pressure - saturation_pressure);
-        match t_wet_bulb >= FREEZING_POINT_WATER_IP {
-            true => {
-                humidity_ratio * (1093.0 + 0.444 * t_dry_bulb - t_wet_bulb)
-                    - (1093.0 - 0.556 * t_wet_bulb) * saturation_humidity_ratio
-                    + 0.240 * (t_dry_bulb - t_wet_bulb)
-            }
-            false => {
-                humidity_ratio * (1220.0 + 0.444 * t_dry_bulb - 0.480 * t_wet_bulb)
-                    - (1220.0 - 0.040 * t_wet_bulb) * saturation_humidity_ratio
-                    + 0.240 * (t_dry_bulb - t_wet_bulb)
-            }
-        }
+    // partial pressure of water vapor, its derivative, and the specific enthalpy without the residual part
+    // and the deviation from the linear enthalpy model, of which the derivatives are neglected
+    let state = |t_dry_bulb: f64| {
+        let (pws, deriv_pws) =
+            saturation_pressure_and_deriv_si(t_dry_bulb, options.saturation_reference);
+        let factor = relative_humidity * enhancement_factor(t_dry_bulb, pws, pressure, options);
+        let partial_water_vapor_pressure = factor * pws;
+        let humidity_ratio = MASS_RATIO_WATER_DRY_AIR * partial_water_vapor_pressure
+            / (pressure - partial_water_vapor_pressure);
+        (
+            partial_water_vapor_pressure,
+            factor * deriv_pws,
+            specific_enthalpy
+                - residual_enthalpy(t_dry_bulb, humidity_ratio, pressure, options)
+                - enthalpy_model_deviation(t_dry_bulb, humidity_ratio, options),
+        )
     };
+    let fd = |t_dry_bulb: f64| {
+        let (partial_water_vapor_pressure, deriv_partial_water_vapor_pressure, specific_enthalpy) =
+            state(t_dry_bulb);
+        (
+            (2501.0 * MASS_RATIO_WATER_DRY_AIR + specific_enthalpy) * partial_water_vapor_pressure
+                + (1.860 * MASS_RATIO_WATER_DRY_AIR - 1.006)
+                    * t_dry_bulb
+                    * partial_water_vapor_pressure
+                + 1.006 * pressure * t_dry_bulb
+                - specific_enthalpy * pressure,
+            (2501.0 * MASS_RATIO_WATER_DRY_AIR + specific_enthalpy)
+                * deriv_partial_water_vapor_pressure
+                + (1.860 * MASS_RATIO_WATER_DRY_AIR - 1.006)
+                    * (partial_water_vapor_pressure
+                        + t_dry_bulb * deriv_partial_water_vapor_pressure)
+                + 1.006 * pressure,
+        )
+    };
+    // humidity_ratio = 0.0
+    let t_init = specific_enthalpy / 1.006;
+    let bracket = (
+        t_dry_bulb_range(UnitSystem::SI).0,
+        t_dry_bulb_upper_limit(relative_humidity, pressure, options)?,
+    );
+    find_root_bracketed(t_init, bracket, fd, &options.solver).map(|solution| solution.root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{t_celsius_to_t_fahrenheit, PA_PER_PSI};
+    use approx::assert_abs_diff_eq;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_saturated_moist_air_si() {
+        let moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(-50.0, 1.0, 101325.0, UnitSystem::SI)
+                .unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 0.0000243, max_relative = 0.01);
+        assert_relative_eq!(moist_air.specific_enthalpy(), -50.2220, max_relative = 0.01);
+
+        let moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(-20.0, 1.0, 101325.0, UnitSystem::SI)
+                .unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 0.0006373, max_relative = 0.01);
+        assert_relative_eq!(moist_air.specific_enthalpy(), -18.5420, max_relative = 0.01);
+
+        let moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(-5.0, 1.0, 101325.0, UnitSystem::SI)
+                .unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 0.0024863, max_relative = 0.005);
+        assert_relative_eq!(moist_air.specific_enthalpy(), 1.164, max_relative = 0.03);
+
+        let moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(5.0, 1.0, 101325.0, UnitSystem::SI)
+                .unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 0.005425, max_relative = 0.005);
+        assert_relative_eq!(moist_air.specific_enthalpy(), 18.639, max_relative = 0.01);
+
+        let moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(25.0, 1.0, 101325.0, UnitSystem::SI)
+                .unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 0.020173, max_relative = 0.005);
+        assert_relative_eq!(moist_air.specific_enthalpy(), 76.504, max_relative = 0.01);
+
+        let moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(50.0, 1.0, 101325.0, UnitSystem::SI)
+                .unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 0.086863, max_relative = 0.01);
+        assert_relative_eq!(moist_air.specific_enthalpy(), 275.353, max_relative = 0.01);
+
+        let moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(85.0, 1.0, 101325.0, UnitSystem::SI)
+                .unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 0.838105, max_relative = 0.02);
+        assert_relative_eq!(moist_air.specific_enthalpy(), 2307.539, max_relative = 0.01);
+    }
+
+    #[test]
+    fn test_saturated_moist_air_ip() {
+        let moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(-58.0, 1.0, 14.696, UnitSystem::IP)
+                .unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 0.0000243, max_relative = 0.01);
+        assert_relative_eq!(moist_air.specific_enthalpy(), -13.906, max_relative = 0.01);
+
+        let moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(-4.0, 1.0, 14.696, UnitSystem::IP).unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 0.0006373, max_relative = 0.01);
+        assert_relative_eq!(moist_air.specific_enthalpy(), -0.286, max_relative = 0.01);
+
+        let moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(23.0, 1.0, 14.696, UnitSystem::IP).unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 0.0024863, max_relative = 0.005);
+        assert_relative_eq!(moist_air.specific_enthalpy(), 8.186, max_relative = 0.01);
+
+        let moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(41.0, 1.0, 14.696, UnitSystem::IP).unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 0.005425, max_relative = 0.005);
+        assert_relative_eq!(moist_air.specific_enthalpy(), 15.699, max_relative = 0.01);
+
+        let moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(77.0, 1.0, 14.696, UnitSystem::IP).unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 0.020173, max_relative = 0.005);
+        assert_relative_eq!(moist_air.specific_enthalpy(), 40.576, max_relative = 0.01);
+
+        let moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(122.0, 1.0, 14.696, UnitSystem::IP)
+                .unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 0.086863, max_relative = 0.01);
+        assert_relative_eq!(moist_air.specific_enthalpy(), 126.066, max_relative = 0.01);
+
+        let moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(185.0, 1.0, 14.696, UnitSystem::IP)
+                .unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 0.838105, max_relative = 0.015);
+        assert_relative_eq!(moist_air.specific_enthalpy(), 999.749, max_relative = 0.01);
+    }
+
+    #[test]
+    fn test_relative_humidity_humidity_ratio() {
+        let relative_humidity_array = [0.0, 0.2, 0.4, 0.6, 0.8, 1.0];
+        // SI units
+        relative_humidity_array.iter().for_each(|&rh| {
+            let moist_air =
+                MoistAir::from_t_dry_bulb_relative_humidity(25.0, rh, 101325.0, UnitSystem::SI)
+                    .unwrap();
+            let humidity_ratio = moist_air.humidity_ratio();
+            let moist_air = MoistAir::from_t_dry_bulb_humidity_ratio(
+                25.0,
+                humidity_ratio,
+                101325.0,
+                UnitSystem::SI,
+            )
+            .unwrap();
+            assert_abs_diff_eq!(moist_air.relative_humidity().unwrap(), rh, epsilon = 1.0E-8);
+        });
+        // IP units
+        relative_humidity_array.iter().for_each(|&rh| {
+            let moist_air =
+                MoistAir::from_t_dry_bulb_relative_humidity(77.0, rh, 14.696, UnitSystem::IP)
+                    .unwrap();
+            let humidity_ratio = moist_air.humidity_ratio();
+            let moist_air = MoistAir::from_t_dry_bulb_humidity_ratio(
+                77.0,
+                humidity_ratio,
+                14.696,
+                UnitSystem::IP,
+            )
+            .unwrap();
+            assert_abs_diff_eq!(moist_air.relative_humidity().unwrap(), rh, epsilon = 1.0E-8);
+        });
+    }
+
+    #[test]
+    fn test_example1_si() {
+        // Test case from ASHRAE Handbook - Fundamentals (2017) SI Ch. 1, Example 1
+        let moist_air =
+            MoistAir::from_t_dry_bulb_t_wet_bulb(40.0, 20.0, 101325.0, UnitSystem::SI).unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 0.0065, max_relative = 0.02);
+        assert_relative_eq!(moist_air.specific_enthalpy(), 56.7, max_relative = 0.01);
+        assert_relative_eq!(moist_air.t_dew_point().unwrap(), 7.0, max_relative = 0.06);
+        assert_relative_eq!(
+            moist_air.relative_humidity().unwrap(),
+            0.14,
+            max_relative = 0.01
+        );
+    }
+
+    #[test]
+    fn test_example1_ip() {
+        // Test case from ASHRAE Handbook - Fundamentals (2017) IP Ch. 1, Example 1
+        let moist_air =
+            MoistAir::from_t_dry_bulb_t_wet_bulb(100.0, 65.0, 14.696, UnitSystem::IP).unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 0.00523, max_relative = 0.01);
+        assert_relative_eq!(moist_air.specific_enthalpy(), 29.80, max_relative = 0.01);
+        assert_relative_eq!(moist_air.t_dew_point().unwrap(), 40.0, max_relative = 0.01);
+        assert_relative_eq!(
+            moist_air.relative_humidity().unwrap(),
+            0.13,
+            max_relative = 0.02
+        );
+    }
+
+    #[test]
+    fn test_example2_si() {
+        // Test case from ASHRAE Handbook - Fundamentals (2017) SI Ch. 1, Example 2
+        let mut moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(2.0, 1.0, 101325.0, UnitSystem::SI)
+                .unwrap();
+        let volumetric_flow_rate = 10.0; // m3/s
+        let mass_flow_rate =
+            volumetric_flow_rate * moist_air.density() / (1.0 + moist_air.humidity_ratio());
+        assert_relative_eq!(mass_flow_rate, 12.74, max_relative = 0.00001);
+        let q = moist_air.heating_t1(mass_flow_rate, 40.0).unwrap();
+        assert_relative_eq!(q, 490.0, max_relative = 0.002);
+    }
+
+    #[test]
+    fn test_example2_ip() {
+        // Test case from ASHRAE Handbook - Fundamentals (2017) IP Ch. 1, Example 2
+        let mut moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(35.0, 1.0, 14.696, UnitSystem::IP).unwrap();
+        let volumetric_flow_rate = 20000.0; // cfm
+        let mass_flow_rate =
+            volumetric_flow_rate * 60.0 * moist_air.density() / (1.0 + moist_air.humidity_ratio());
+        assert_relative_eq!(mass_flow_rate, 95620.0, max_relative = 0.0006);
+        let q = moist_air.heating_t1(mass_flow_rate, 100.0).unwrap();
+        assert_relative_eq!(q, 1507000.0, max_relative = 0.003);
+    }
+
+    #[test]
+    fn test_relative_humidity_100_si() {
+        let t_dry_bulb: Vec<f64> = (-100..=-5).step_by(5).map(|x| x as f64).collect();
+        let unit = UnitSystem::SI;
+
+        t_dry_bulb.iter().for_each(|&t| {
+            let moist_air =
+                MoistAir::from_t_dry_bulb_relative_humidity(t, 1.0, 101325.0, unit).unwrap();
+            assert_relative_eq!(moist_air.t_dew_point().unwrap(), t, max_relative = 5.0E-5);
+            assert_relative_eq!(moist_air.t_wet_bulb().unwrap(), t, max_relative = 5.0E-5);
+        });
 
-    let d = |t_wet_bulb: f64| {
-        let saturation_water_vapor = SaturatedWaterVapor::new_relaxed(t_wet_bulb, UnitSystem::IP);
-        let saturation_pressure = saturation_water_vapor.saturation_pressure();
-        let saturation_humidity_ratio =
-            MASS_RATIO_WATER_DRY_AIR * saturation_pressure / (pressure - saturation_pressure);
-        let deriv_saturation_humidity_ratio = MASS_RATIO_WATER_DRY_AIR
-            * pressure
-            * saturation_water_vapor.deriv_saturation_pressure()
-            / (pressure - saturation_pressure).powi(2);
-
-        match t_wet_bulb >= FREEZING_POINT_WATER_IP {
-            true => {
-                -humidity_ratio - 1093.0 * deriv_saturation_humidity_ratio
-                    + 0.556 * saturation_humidity_ratio
-                    + 0.556 * t_wet_bulb * deriv_saturation_humidity_ratio
-                    - 0.240
-            }
-            false => {
-                -0.480 * humidity_ratio - 1220.0 * deriv_saturation_humidity_ratio
-                    + 0.040 * saturation_humidity_ratio
-                    + 0.040 * t_wet_bulb * deriv_saturation_humidity_ratio
-                    - 0.240
+        let t_dry_bulb: Vec<f64> = (5..=195).step_by(5).map(|x| x as f64).collect();
+        let unit = UnitSystem::SI;
+
+        t_dry_bulb.iter().for_each(|&t| {
+            let result = MoistAir::from_t_dry_bulb_relative_humidity(t, 1.0, 101325.0, unit);
+            // saturated state is not defined above the boiling point at 101325 Pa,
+            // where the relative humidity is limited by the total pressure
+            if t >= 100.0 {
+                let error = result.unwrap_err();
+                assert_eq!(error.quantity(), Some("relative humidity"));
+                let pws = SaturatedWaterVapor::new(t, unit)
+                    .unwrap()
+                    .saturation_pressure();
+                let (_, max) = error.valid_range().unwrap();
+                assert_relative_eq!(max, 101325.0 / pws, max_relative = 0.01);
+                assert!(max < 1.0);
+                return;
             }
-        }
-    };
+            let moist_air = result.unwrap();
+            assert_relative_eq!(moist_air.t_dew_point().unwrap(), t, max_relative = 5.0E-5);
+            assert_relative_eq!(moist_air.t_wet_bulb().unwrap(), t, max_relative = 5.0E-5);
+        });
 
-    let mut convergency = SimpleConvergency {
-        eps: 1e-6f64,
-        max_iter: 50,
-    };
+        let moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(0.0, 1.0, 101325.0, unit).unwrap();
+        assert_abs_diff_eq!(moist_air.t_dew_point().unwrap(), 0.0, epsilon = 1.0E-8);
+        assert_abs_diff_eq!(moist_air.t_wet_bulb().unwrap(), 0.0, epsilon = 1.0E-8);
+    }
 
-    let root = find_root_newton_raphson(t_dry_bulb, &f, &d, &mut convergency)?;
-    Ok(root)
-}
+    #[test]
+    fn test_from_pair_si() {
+        let unit = UnitSystem::SI;
+        let pressure = 101325.0;
+        let reference =
+            MoistAir::from_t_dry_bulb_relative_humidity(30.0, 0.4, pressure, unit).unwrap();
+        let w = reference.humidity_ratio();
+        let rh = reference.relative_humidity().unwrap();
+        let t_wb = reference.t_wet_bulb().unwrap();
+        let t_dp = reference.t_dew_point().unwrap();
+        let h = reference.specific_enthalpy();
+        let v = specific_volume_from_humidity_ratio(30.0, w, pressure, &ModelOptions::default());
 
-/// Calculates the humidity ratio from dry-bulb temperature and relative humidity
-fn humidity_ratio_from_relative_humidity(
-    t_dry_bulb: f64,
-    relative_humidity: f64,
-    pressure: f64,
-    unit: UnitSystem,
-) -> Result<f64, PsychroidError> {
-    // calculate vapor pressure from relative humidity
-    let vapor = SaturatedWaterVapor::new(t_dry_bulb, unit)?;
-    let pws = vapor.saturation_pressure();
-    let pw = relative_humidity * pws;
-    Ok(MASS_RATIO_WATER_DRY_AIR * pw / (pressure - pw))
-}
+        let pairs = [
+            PropertyPair(Property::TDryBulb(30.0), Property::TWetBulb(t_wb)),
+            PropertyPair(Property::SpecificVolume(v), Property::TDryBulb(30.0)),
+            PropertyPair(Property::TWetBulb(t_wb), Property::RelativeHumidity(rh)),
+            PropertyPair(Property::TDewPoint(t_dp), Property::SpecificEnthalpy(h)),
+            PropertyPair(Property::TWetBulb(t_wb), Property::TDewPoint(t_dp)),
+            PropertyPair(Property::SpecificVolume(v), Property::HumidityRatio(w)),
+            PropertyPair(Property::RelativeHumidity(rh), Property::HumidityRatio(w)),
+            PropertyPair(
+                Property::SpecificEnthalpy(h),
+                Property::RelativeHumidity(rh),
+            ),
+            PropertyPair(Property::RelativeHumidity(rh), Property::SpecificVolume(v)),
+            PropertyPair(Property::SpecificEnthalpy(h), Property::SpecificVolume(v)),
+        ];
+        pairs.iter().for_each(|&pair| {
+            let moist_air = MoistAir::from_pair(pair, pressure, unit).unwrap();
+            assert_abs_diff_eq!(moist_air.t_dry_bulb(), 30.0, epsilon = 1.0E-4);
+            assert_relative_eq!(moist_air.humidity_ratio(), w, max_relative = 1.0E-5);
+        });
+    }
 
-/// Calculates the relative humidity from dry-bulb temperature and humidity ratio
-fn relative_humidity_from_humidity_ratio(
-    t_dry_bulb: f64,
-    humidity_ratio: f64,
-    pressure: f64,
-    unit: UnitSystem,
-) -> Result<f64, PsychroidError> {
-    let water_pressure = pressure * humidity_ratio / (MASS_RATIO_WATER_DRY_AIR + humidity_ratio);
-    let saturated_water_vapor = SaturatedWaterVapor::new(t_dry_bulb, unit)?;
-    Ok(water_pressure / saturated_water_vapor.saturation_pressure())
-}
+    #[test]
+    fn test_from_pair_ip() {
+        let unit = UnitSystem::IP;
+        let pressure = 14.696;
+        let reference =
+            MoistAir::from_t_dry_bulb_relative_humidity(86.0, 0.4, pressure, unit).unwrap();
+        let t_wb = reference.t_wet_bulb().unwrap();
+        let t_dp = reference.t_dew_point().unwrap();
 
-/// Calculate the dew point temperature from dry-bulb temperature and relative humidity
-/// If the relative humidity is 0 or very close to 0, NaN is returned as the dew point temperature
-fn t_dew_point_from_humidity_ratio(
-    humidity_ratio: f64,
-    pressure: f64,
-    unit: UnitSystem,
-) -> Result<f64, PsychroidError> {
-    if humidity_ratio <= f64::EPSILON {
-        return Ok(f64::NAN);
+        let moist_air = MoistAir::from_pair(
+            PropertyPair(Property::TWetBulb(t_wb), Property::TDewPoint(t_dp)),
+            pressure,
+            unit,
+        )
+        .unwrap();
+        assert_abs_diff_eq!(moist_air.t_dry_bulb(), 86.0, epsilon = 1.0E-4);
+        assert_relative_eq!(
+            moist_air.humidity_ratio(),
+            reference.humidity_ratio(),
+            max_relative = 1.0E-5
+        );
     }
 
-    let saturation_pressure =
-        pressure * humidity_ratio / (MASS_RATIO_WATER_DRY_AIR + humidity_ratio);
-    let f = |t: f64| {
-        let saturated_water_vapor = SaturatedWaterVapor::new_relaxed(t, unit);
-        saturated_water_vapor.saturation_pressure() - saturation_pressure
-    };
-    let d = |t: f64| {
-        let saturated_water_vapor = SaturatedWaterVapor::new_relaxed(t, unit);
-        saturated_water_vapor.deriv_saturation_pressure()
-    };
-    let mut convergency = SimpleConvergency {
-        eps: 1e-6f64,
-        max_iter: 50,
-    };
+    #[test]
+    fn test_constructors_with_options() {
+        let unit = UnitSystem::SI;
+        let pressure = 1.0E+06;
+        let options = ModelOptions {
+            gas_model: GasModel::Real,
+            ..Default::default()
+        };
+        let reference = MoistAir::from_t_dry_bulb_relative_humidity_with_options(
+            40.0, 0.5, pressure, unit, options,
+        )
+        .unwrap();
+        let (t, w) = (40.0, reference.humidity_ratio());
+        let t_wb = reference.t_wet_bulb().unwrap();
+        let t_dp = reference.t_dew_point().unwrap();
+        let h = reference.specific_enthalpy();
+        let states = [
+            MoistAir::from_t_dry_bulb_humidity_ratio_with_options(t, w, pressure, unit, options),
+            MoistAir::from_t_dry_bulb_t_wet_bulb_with_options(t, t_wb, pressure, unit, options),
+            MoistAir::from_t_dry_bulb_t_dew_point_with_options(t, t_dp, pressure, unit, options),
+            MoistAir::from_t_dry_bulb_enthalpy_with_options(t, h, pressure, unit, options),
+            MoistAir::from_specific_enthalpy_relative_humidity_with_options(
+                h, 0.5, pressure, unit, options,
+            ),
+        ];
+        states.into_iter().for_each(|state| {
+            let state = state.unwrap();
+            assert_eq!(state.options(), reference.options());
+            assert_abs_diff_eq!(state.t_dry_bulb(), t, epsilon = 1.0E-6);
+            assert_relative_eq!(state.humidity_ratio(), w, max_relative = 1.0E-6);
+        });
+        // the real gas model gives a different state than the default options
+        let ideal = MoistAir::from_t_dry_bulb_relative_humidity(40.0, 0.5, pressure, unit).unwrap();
+        assert!((ideal.humidity_ratio() - w).abs() > 1.0E-6);
+    }
 
-    let partial_water_vapor_pressure = match unit {
-        UnitSystem::SI => {
-            // pressure in kPa
-            0.001 * humidity_ratio * pressure / (MASS_RATIO_WATER_DRY_AIR + humidity_ratio)
+    #[test]
+    fn test_from_pair_round_trip() {
+        let unit = UnitSystem::SI;
+        let pressure = 101325.0;
+        let options = ModelOptions::default();
+        for t_dry_bulb in [-20.0, 0.5, 20.0, 30.0, 60.0, 95.0, 150.0] {
+            for relative_humidity in [0.01, 0.02, 0.05, 0.2, 0.5, 0.8, 0.95] {
+                let Ok(reference) = MoistAir::from_t_dry_bulb_relative_humidity(
+                    t_dry_bulb,
+                    relative_humidity,
+                    pressure,
+                    unit,
+                ) else {
+                    // the partial pressure of water vapor above the total pressure
+                    assert!(t_dry_bulb > 100.0);
+                    continue;
+                };
+                let w = reference.humidity_ratio();
+                let properties = [
+                    Property::TDryBulb(t_dry_bulb),
+                    Property::HumidityRatio(w),
+                    Property::RelativeHumidity(relative_humidity),
+                    Property::TWetBulb(reference.t_wet_bulb().unwrap()),
+                    Property::TDewPoint(reference.t_dew_point().unwrap()),
+                    Property::SpecificEnthalpy(reference.specific_enthalpy()),
+                    Property::SpecificVolume(specific_volume_from_humidity_ratio(
+                        t_dry_bulb, w, pressure, &options,
+                    )),
+                ];
+                for (i, &first) in properties.iter().enumerate() {
+                    for &second in properties.iter().skip(i + 1) {
+                        if !first.is_independent_of(&second) {
+                            continue;
+                        }
+                        let pair = PropertyPair(first, second);
+                        let moist_air =
+                            MoistAir::from_pair(pair, pressure, unit).unwrap_or_else(|e| {
+                                panic!("{pair:?} at {t_dry_bulb}, {relative_humidity}: {e}")
+                            });
+                        assert_abs_diff_eq!(moist_air.t_dry_bulb(), t_dry_bulb, epsilon = 1.0E-6);
+                        assert_relative_eq!(moist_air.humidity_ratio(), w, max_relative = 1.0E-6);
+                    }
+                }
+            }
         }
-        UnitSystem::IP => humidity_ratio * pressure / (MASS_RATIO_WATER_DRY_AIR + humidity_ratio),
-    };
+    }
 
-    let alpha = partial_water_vapor_pressure.ln();
-    let t_above = match unit {
-        UnitSystem::IP => {
-            C14_IP
-                + C15_IP * alpha
-                + C16_IP * alpha.powi(2)
-                + C17_IP * alpha.powi(3)
-                + C18_IP * partial_water_vapor_pressure.powf(0.1984)
-        }
-        UnitSystem::SI => {
-            C14_SI
-                + C15_SI * alpha
-                + C16_SI * alpha.powi(2)
-                + C17_SI * alpha.powi(3)
-                + C18_SI * partial_water_vapor_pressure.powf(0.1984)
-        }
-    };
-    let t_below = match unit {
-        UnitSystem::IP => 90.12 + 26.142 * alpha + 0.8927 * alpha.powi(2),
-        UnitSystem::SI => 6.09 + 12.608 * alpha + 0.4959 * alpha.powi(2),
-    };
-    let t_init = match (t_above >= 0.0, t_below >= 0.0) {
-        (true, true) => t_above,
-        (false, false) => t_below,
-        _ => (t_above + t_below) / 2.0,
-    };
-    let root = find_root_newton_raphson(t_init, &f, &d, &mut convergency)?;
-    Ok(root)
-}
+    #[test]
+    fn test_from_pair_invalid() {
+        let unit = UnitSystem::SI;
+        // dependent pairs
+        let pair = PropertyPair(Property::HumidityRatio(0.01), Property::TDewPoint(10.0));
+        assert!(matches!(
+            MoistAir::from_pair(pair, 101325.0, unit),
+            Err(PsychroidError::DependentProperties(..))
+        ));
+        let pair = PropertyPair(Property::TDryBulb(20.0), Property::TDryBulb(25.0));
+        assert!(MoistAir::from_pair(pair, 101325.0, unit).is_err());
+        // dew point above dry-bulb temperature
+        let pair = PropertyPair(Property::TDryBulb(20.0), Property::TDewPoint(25.0));
+        assert!(MoistAir::from_pair(pair, 101325.0, unit).is_err());
+    }
 
-fn humidity_ratio_from_t_dew_point(
-    t_dew_point: f64,
-    pressure: f64,
-    unit: UnitSystem,
-) -> Result<f64, PsychroidError> {
-    let saturated_water_vapor = SaturatedWaterVapor::new(t_dew_point, unit)?;
-    let saturation_pressure = saturated_water_vapor.saturation_pressure();
-    Ok(MASS_RATIO_WATER_DRY_AIR * saturation_pressure / (pressure - saturation_pressure))
-}
+    #[test]
+    fn test_property_accessors_si() {
+        let moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(20.0, 0.5, 101325.0, UnitSystem::SI)
+                .unwrap();
+        assert_relative_eq!(moist_air.specific_volume(), 0.8402, max_relative = 0.001);
+        assert_relative_eq!(
+            moist_air.density(),
+            (1.0 + moist_air.humidity_ratio()) / moist_air.specific_volume()
+        );
+        assert_relative_eq!(
+            moist_air.partial_water_vapor_pressure(),
+            0.5 * 2339.3,
+            max_relative = 0.001
+        );
+        assert_relative_eq!(
+            moist_air.mole_fraction_water_vapor(),
+            moist_air.partial_water_vapor_pressure() / 101325.0
+        );
+        assert_relative_eq!(
+            moist_air.degree_of_saturation().unwrap(),
+            0.4942,
+            max_relative = 0.001
+        );
+        assert_relative_eq!(moist_air.absolute_humidity(), 8.64, max_relative = 0.002);
+        assert_relative_eq!(
+            moist_air.specific_humidity(),
+            0.007209,
+            max_relative = 0.002
+        );
+        assert_relative_eq!(moist_air.molar_mass(), 28.84, max_relative = 0.001);
+        assert_relative_eq!(moist_air.gas_constant(), 288.3, max_relative = 0.001);
+        assert_relative_eq!(moist_air.specific_heat(), 1.0121, max_relative = 0.001);
+        assert_relative_eq!(moist_air.heat_capacity_ratio(), 1.398, max_relative = 0.001);
+    }
 
-/// Calculate the specific enthalpy from dry-bulb temperature and humidity ratio
-///
-/// ASHRAE Handbook - Fundamentals (2017) SI Ch. 1 Eq. (30)
-/// ASHRAE Handbook - Fundamentals (2017) IP Ch. 1 Eq. (30)
-fn specific_enthalpy_from_humidity_ratio(
-    t_dry_bulb: f64,
-    humidity_ratio: f64,
-    unit: UnitSystem,
-) -> f64 {
-    match unit {
-        UnitSystem::SI => 1.006 * t_dry_bulb + humidity_ratio * (2501.0 + 1.860 * t_dry_bulb),
-        UnitSystem::IP => 0.240 * t_dry_bulb + humidity_ratio * (1061.0 + 0.444 * t_dry_bulb),
+    #[test]
+    fn test_property_accessors_ip() {
+        let moist_air =
+            MoistAir::from_t_dry_bulb_humidity_ratio(68.0, 0.0, 14.696, UnitSystem::IP).unwrap();
+        assert_relative_eq!(moist_air.specific_volume(), 13.30, max_relative = 0.001);
+        assert_relative_eq!(moist_air.molar_mass(), MOLAR_MASS_DRY_AIR);
+        assert_relative_eq!(moist_air.gas_constant(), 53.35, max_relative = 0.001);
+        assert_relative_eq!(moist_air.specific_heat(), 0.240);
+        assert_relative_eq!(moist_air.heat_capacity_ratio(), 1.4, max_relative = 0.002);
+        assert_eq!(moist_air.absolute_humidity(), 0.0);
     }
-}
 
-/// Calculate the humidity ratio from specific enthalpy and dry-bulb temperature
-///
-/// ASHRAE Handbook - Fundamentals (2017) SI Ch. 1 Eq. (30)
-/// ASHRAE Handbook - Fundamentals (2017) IP Ch. 1 Eq. (30)
-fn humidity_ratio_from_specific_enthalpy(
-    t_dry_bulb: f64,
-    specific_enthalpy: f64,
-    unit: UnitSystem,
-) -> f64 {
-    match unit {
-        UnitSystem::SI => (specific_enthalpy - 1.006 * t_dry_bulb) / (2501.0 + 1.860 * t_dry_bulb),
-        UnitSystem::IP => (specific_enthalpy - 0.240 * t_dry_bulb) / (1061.0 + 0.444 * t_dry_bulb),
+    #[test]
+    fn test_transport_properties() {
+        let moist_air_si =
+            MoistAir::from_t_dry_bulb_relative_humidity(25.0, 0.5, 101325.0, UnitSystem::SI)
+                .unwrap();
+        assert_relative_eq!(
+            moist_air_si.dynamic_viscosity(),
+            18.4E-06,
+            max_relative = 0.01
+        );
+        assert_relative_eq!(
+            moist_air_si.thermal_conductivity(),
+            0.0259,
+            max_relative = 0.01
+        );
+        assert_relative_eq!(moist_air_si.prandtl_number(), 0.71, max_relative = 0.02);
+        assert_relative_eq!(moist_air_si.schmidt_number(), 0.61, max_relative = 0.02);
+        assert_relative_eq!(
+            moist_air_si.lewis_number(),
+            moist_air_si.schmidt_number() / moist_air_si.prandtl_number()
+        );
+
+        // dimensionless numbers do not depend on the unit system
+        let mut moist_air_ip =
+            MoistAir::from_t_dry_bulb_relative_humidity(25.0, 0.5, 101325.0, UnitSystem::SI)
+                .unwrap();
+        moist_air_ip.set_unit(UnitSystem::IP);
+        assert_relative_eq!(
+            moist_air_ip.dynamic_viscosity(),
+            moist_air_si.dynamic_viscosity() * LB_PER_FT_H_PER_PA_S,
+            max_relative = 1.0E-9
+        );
+        assert_relative_eq!(
+            moist_air_ip.prandtl_number(),
+            moist_air_si.prandtl_number(),
+            max_relative = 2.0E-3
+        );
+        assert_relative_eq!(
+            moist_air_ip.schmidt_number(),
+            moist_air_si.schmidt_number(),
+            max_relative = 1.0E-3
+        );
     }
-}
 
-/// Calculate the dry-bulb temperature from specific enthalpy and humidity ratio
-///
-/// ASHRAE Handbook - Fundamentals (2017) SI Ch. 1 Eq. (30)
-/// ASHRAE Handbook - Fundamentals (2017) IP Ch. 1 Eq. (30)
-fn t_dry_bulb_from_specific_enthalpy_humidity_ratio(
-    specific_enthalpy: f64,
-    humidity_ratio: f64,
-    unit: UnitSystem,
-) -> f64 {
-    match unit {
-        UnitSystem::SI => {
-            (specific_enthalpy - humidity_ratio * 2501.0) / (1.006 + humidity_ratio * 1.860)
+    #[test]
+    fn test_specific_entropy() {
+        // dry air at the reference state
+        let moist_air =
+            MoistAir::from_t_dry_bulb_humidity_ratio(0.0, 0.0, 101325.0, UnitSystem::SI).unwrap();
+        assert_abs_diff_eq!(moist_air.specific_entropy(), 0.0, epsilon = 1.0E-12);
+        let moist_air =
+            MoistAir::from_t_dry_bulb_humidity_ratio(0.0, 0.0, 14.696, UnitSystem::IP).unwrap();
+        assert_abs_diff_eq!(moist_air.specific_entropy(), 0.0, epsilon = 1.0E-12);
+
+        // entropy of water vapor in saturated air equals that of saturated steam, 8.6661 kJ/(kg·K) at 20 °C
+        let pressure = 101325.0;
+        let saturated =
+            MoistAir::from_t_dry_bulb_relative_humidity(20.0, 1.0, pressure, UnitSystem::SI)
+                .unwrap();
+        let p_da = pressure - saturated.partial_water_vapor_pressure();
+        let s_da = 1.006 * (293.15f64 / 273.15).ln() - 0.287042 * (p_da / 101325.0).ln();
+        let s_w = (saturated.specific_entropy() - s_da) / saturated.humidity_ratio();
+        assert_relative_eq!(s_w, 8.6661, max_relative = 0.001);
+
+        // ASHRAE Handbook - Fundamentals (2017) Ch. 1 Table 2 and 3, with the real gas model:
+        // saturation humidity ratio and entropy of saturated water vapor at 0, 20 and 40 °C
+        let options = ModelOptions {
+            gas_model: GasModel::Real,
+            enthalpy_model: EnthalpyModel::VariableHeatCapacity,
+            ..Default::default()
+        };
+        for (t, humidity_ratio, s_g) in [
+            (0.0, 0.003789, 9.1562),
+            (20.0, 0.014758, 8.6661),
+            (40.0, 0.049141, 8.2556),
+        ] {
+            let saturated = MoistAir::from_t_dry_bulb_relative_humidity_with_options(
+                t,
+                1.0,
+                pressure,
+                UnitSystem::SI,
+                options,
+            )
+            .unwrap();
+            assert_relative_eq!(
+                saturated.humidity_ratio(),
+                humidity_ratio,
+                max_relative = 0.001
+            );
+            let dry_air = MoistAir::from_t_dry_bulb_humidity_ratio_with_options(
+                t,
+                0.0,
+                pressure,
+                UnitSystem::SI,
+                options,
+            )
+            .unwrap();
+            // dry air at its partial pressure and water vapor at saturation
+            let p_da = pressure - saturated.partial_water_vapor_pressure();
+            let s_s = dry_air.specific_entropy() - 0.287042 * (p_da / pressure).ln()
+                + saturated.humidity_ratio() * s_g;
+            assert_relative_eq!(saturated.specific_entropy(), s_s, max_relative = 0.001);
         }
-        UnitSystem::IP => {
-            (specific_enthalpy - humidity_ratio * 1061.0) / (0.240 + humidity_ratio * 0.444)
+
+        // T ds = dh at constant pressure and humidity ratio, and T ds = dh - v dp at constant temperature,
+        // for all the models of the specific enthalpy
+        for gas_model in [GasModel::Ideal, GasModel::Real] {
+            for enthalpy_model in [EnthalpyModel::Linear, EnthalpyModel::VariableHeatCapacity] {
+                let options = ModelOptions {
+                    gas_model,
+                    enthalpy_model,
+                    ..Default::default()
+                };
+                let state = |t, p| {
+                    MoistAir::from_t_dry_bulb_humidity_ratio_with_options(
+                        t,
+                        0.01,
+                        p,
+                        UnitSystem::SI,
+                        options,
+                    )
+                    .unwrap()
+                };
+                let (t, p, dt, dp) = (60.0, 500000.0, 1.0E-03, 1.0);
+                let ds = state(t + dt, p).specific_entropy() - state(t - dt, p).specific_entropy();
+                let dh =
+                    state(t + dt, p).specific_enthalpy() - state(t - dt, p).specific_enthalpy();
+                assert_relative_eq!(333.15 * ds, dh, max_relative = 1.0E-6);
+                let ds = state(t, p + dp).specific_entropy() - state(t, p - dp).specific_entropy();
+                let dh =
+                    state(t, p + dp).specific_enthalpy() - state(t, p - dp).specific_enthalpy();
+                let v_dp = state(t, p).specific_volume() * 2.0 * dp * 0.001;
+                assert_relative_eq!(333.15 * ds, dh - v_dp, max_relative = 1.0E-4);
+            }
         }
     }
-}
 
-/// Calculate the dry-bulb temperature from specific enthalpy and relative humidity.
-/// <script src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
-///
-/// # Formula
-/// For SI unit system, the dry-bulb temeprature can be determined by solving the equation:
-/// $$
-/// \begin{equation}
-/// h = 1.006 t_{db} + W (2501.0 + 1.860 t_{db}), \quad W = 0.621945 \frac{p_{w}}{p - p_{w}}
-/// \end{equation}
-/// $$
-/// By multiplying the equation by \\(p - p_{w}\\), the equation to solve becomes:
-/// $$
-/// \begin{gather}
-/// f(t_{db}) = (2501.0 \times 0.621945 + h) p_{w} + (1.860 \times 0.621945 - 1.006) t_{db}~p_{w} + 1.006 t_{db}~p - h p = 0
-/// \end{gather}
-/// $$
-/// The derivative of the function is:
-/// $$
-/// \begin{gather}
-/// \frac{df}{dt_{db}} = (2501.0 \times 0.621945 + h) \frac{dp_{w}}{dt_{db}} + (1.860 \times 0.621945 - 1.006) p_{w} + (1.860 \times 0.621945 - 1.006) t_{db} \frac{dp_{w}}{dt_{db}} + 1.006 p
-/// \end{gather}
-/// $$
-///
-/// For IP unit system, the dry-bulb temeprature can be determined by solving the equation:
-/// $$
-/// \begin{equation}
-/// h = 0.240 t_{db} + W (1061.0 + 0.444 t_{db}), \quad W = 0.621945 \frac{p_{w}}{p - p_{w}}
-/// \end{equation}
-/// $$
-/// By multiplying the equation by \\(p - p_{w}\\), the equation to solve becomes:
-/// $$
-/// \begin{gather}
-/// f(t_{db}) = (1061.0 \times 0.621945 + h) p_{w} + (0.444 \times 0.621945 - 0.240) t_{db}~p_{w} + 0.240 t_{db}~p - h p = 0
-/// \end{gather}
-/// $$
-/// The derivative of the function is:
-/// $$
-/// \begin{gather}
-/// \frac{df}{dt_{db}} = (1061.0 \times 0.621945 + h) \frac{dp_{w}}{dt_{db}} + (0.444 \times 0.621945 - 0.240) p_{w} + (0.444 \times 0.621945 - 0.240) t_{db} \frac{dp_{w}}{dt_{db}} + 0.240 p
-/// \end{gather}
-/// $$
-///
-fn t_dry_bulb_from_specific_enthalpy_relative_humidity(
-    specific_enthalpy: f64,
-    relative_humidity: f64,
-    pressure: f64,
-    unit: UnitSystem,
-) -> Result<f64, PsychroidError> {
-    let f = |t_dry_bulb: f64| {
-        let saturation_water_vapor = SaturatedWaterVapor::new_relaxed(t_dry_bulb, unit);
-        let partial_water_vapor_pressure =
-            relative_humidity * saturation_water_vapor.saturation_pressure();
-        match unit {
-            UnitSystem::SI => {
-                (2501.0 * MASS_RATIO_WATER_DRY_AIR + specific_enthalpy)
-                    * partial_water_vapor_pressure
-                    + (1.860 * MASS_RATIO_WATER_DRY_AIR - 1.006)
-                        * t_dry_bulb
-                        * partial_water_vapor_pressure
-                    + 1.006 * pressure * t_dry_bulb
-                    - specific_enthalpy * pressure
-            }
-            UnitSystem::IP => {
-                (1061.0 * MASS_RATIO_WATER_DRY_AIR + specific_enthalpy)
-                    * partial_water_vapor_pressure
-                    + (0.444 * MASS_RATIO_WATER_DRY_AIR - 0.240)
-                        * t_dry_bulb
-                        * partial_water_vapor_pressure
-                    + 0.240 * pressure * t_dry_bulb
-                    - specific_enthalpy * pressure
+    #[test]
+    fn test_exergy() {
+        let dead_state =
+            MoistAir::from_t_dry_bulb_relative_humidity(25.0, 0.5, 101325.0, UnitSystem::SI)
+                .unwrap();
+        let exergy = dead_state.exergy(&dead_state).unwrap();
+        assert_abs_diff_eq!(exergy.total(), 0.0, epsilon = 1.0E-12);
+
+        // heating at constant humidity ratio increases thermal exergy only
+        let mut moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(25.0, 0.5, 101325.0, UnitSystem::SI)
+                .unwrap();
+        moist_air.heating_t1(1.0, 60.0).unwrap();
+        let exergy = moist_air.exergy(&dead_state).unwrap();
+        assert!(exergy.thermal > 0.0);
+        assert_abs_diff_eq!(exergy.mechanical, 0.0, epsilon = 1.0E-12);
+        assert_abs_diff_eq!(exergy.chemical, 0.0, epsilon = 1.0E-12);
+        // (cp_da + W cp_w) T0 (T/T0 - 1 - ln(T/T0))
+        let cp = 1.006 + 1.860 * moist_air.humidity_ratio();
+        let ratio: f64 = 333.15 / 298.15;
+        // evaluated as h - T0 s, which only differs by rounding
+        assert_relative_eq!(
+            exergy.thermal,
+            cp * 298.15 * (ratio - 1.0 - ratio.ln()),
+            max_relative = 1.0E-12
+        );
+
+        // chemical and mechanical exergy are non-negative
+        let dry_air =
+            MoistAir::from_t_dry_bulb_humidity_ratio(25.0, 0.001, 120000.0, UnitSystem::SI)
+                .unwrap();
+        let exergy = dry_air.exergy(&dead_state).unwrap();
+        assert!(exergy.chemical > 0.0);
+        assert!(exergy.mechanical > 0.0);
+        let difference = exergy - exergy;
+        assert_eq!(difference.total(), 0.0);
+
+        // dead state in another unit system
+        let mut dead_state_ip = dead_state;
+        dead_state_ip.set_unit(UnitSystem::IP);
+        assert!(dry_air.exergy(&dead_state_ip).is_err());
+
+        // the exergy is h - T0 s relative to the dead state for every model,
+        // here at the humidity ratio of the dead state where the chemical exergy vanishes
+        for gas_model in [GasModel::Ideal, GasModel::Real] {
+            for enthalpy_model in [EnthalpyModel::Linear, EnthalpyModel::VariableHeatCapacity] {
+                let options = ModelOptions {
+                    gas_model,
+                    enthalpy_model,
+                    ..Default::default()
+                };
+                let state = |t, p| {
+                    MoistAir::from_t_dry_bulb_humidity_ratio_with_options(
+                        t,
+                        0.01,
+                        p,
+                        UnitSystem::SI,
+                        options,
+                    )
+                    .unwrap()
+                };
+                let (dead_state, moist_air) = (state(25.0, 101325.0), state(80.0, 500000.0));
+                let exergy = moist_air.exergy(&dead_state).unwrap();
+                assert_abs_diff_eq!(exergy.chemical, 0.0, epsilon = 1.0E-12);
+                assert_relative_eq!(
+                    exergy.total(),
+                    moist_air.specific_enthalpy()
+                        - dead_state.specific_enthalpy()
+                        - 298.15 * (moist_air.specific_entropy() - dead_state.specific_entropy()),
+                    max_relative = 1.0E-9
+                );
             }
         }
-    };
-    let d = |t_dry_bulb: f64| {
-        let saturation_water_vapor = SaturatedWaterVapor::new_relaxed(t_dry_bulb, unit);
-        let partial_water_vapor_pressure =
-            relative_humidity * saturation_water_vapor.saturation_pressure();
-        let deriv_partial_water_vapor_pressure =
-            relative_humidity * saturation_water_vapor.deriv_saturation_pressure();
-        match unit {
-            UnitSystem::SI => {
-                (2501.0 * MASS_RATIO_WATER_DRY_AIR + specific_enthalpy)
-                    * deriv_partial_water_vapor_pressure
-                    + (1.860 * MASS_RATIO_WATER_DRY_AIR - 1.006)
-                        * (partial_water_vapor_pressure
-                            + t_dry_bulb * deriv_partial_water_vapor_pressure)
-                    + 1.006 * pressure
-            }
-            UnitSystem::IP => {
-                (1061.0 * MASS_RATIO_WATER_DRY_AIR + specific_enthalpy)
-                    * deriv_partial_water_vapor_pressure
-                    + (0.444 * MASS_RATIO_WATER_DRY_AIR - 0.240)
-                        * (partial_water_vapor_pressure
-                            + t_dry_bulb * deriv_partial_water_vapor_pressure)
-                    + 0.240 * pressure
-            }
+    }
+
+    #[test]
+    fn test_real_gas() {
+        let real = ModelOptions {
+            gas_model: GasModel::Real,
+            ..Default::default()
+        };
+        let state = |pair: PropertyPair, pressure: f64, unit: UnitSystem| {
+            MoistAir::from_pair_with_options(pair, pressure, unit, real).unwrap()
+        };
+
+        // the ideal gas model is the default
+        let pair = PropertyPair(Property::TDryBulb(25.0), Property::RelativeHumidity(0.5));
+        let ideal = MoistAir::from_pair(pair, 1.0E+06, UnitSystem::SI).unwrap();
+        let explicit = MoistAir::from_pair_with_options(
+            pair,
+            1.0E+06,
+            UnitSystem::SI,
+            ModelOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(ideal.humidity_ratio(), explicit.humidity_ratio());
+        assert_eq!(ideal.specific_enthalpy(), explicit.specific_enthalpy());
+
+        // the humidity ratio at saturation increases by the enhancement factor
+        let moist_air = state(pair, 1.0E+06, UnitSystem::SI);
+        let pws = SaturatedWaterVapor::new(25.0, UnitSystem::SI)
+            .unwrap()
+            .saturation_pressure();
+        let factor = real_gas::enhancement_factor(25.0, 1.0E+06, pws);
+        assert!(factor > 1.02);
+        assert_relative_eq!(
+            moist_air.humidity_ratio() / ideal.humidity_ratio(),
+            factor,
+            max_relative = 0.002
+        );
+        assert_relative_eq!(moist_air.relative_humidity().unwrap(), 0.5);
+        // compressed air occupies less volume than the ideal gas
+        assert!(moist_air.specific_volume() < ideal.specific_volume());
+        assert!(moist_air.specific_volume() > 0.99 * ideal.specific_volume());
+
+        // consistency of the corrections with the inverse functions
+        let t_dew_point = moist_air.t_dew_point().unwrap();
+        let pairs = [
+            PropertyPair(Property::TDryBulb(25.0), Property::TDewPoint(t_dew_point)),
+            PropertyPair(
+                Property::SpecificEnthalpy(moist_air.specific_enthalpy()),
+                Property::HumidityRatio(moist_air.humidity_ratio()),
+            ),
+            PropertyPair(
+                Property::SpecificVolume(moist_air.specific_volume()),
+                Property::HumidityRatio(moist_air.humidity_ratio()),
+            ),
+            PropertyPair(
+                Property::SpecificEnthalpy(moist_air.specific_enthalpy()),
+                Property::RelativeHumidity(0.5),
+            ),
+            PropertyPair(
+                Property::TDryBulb(25.0),
+                Property::SpecificEnthalpy(moist_air.specific_enthalpy()),
+            ),
+            PropertyPair(
+                Property::TDryBulb(25.0),
+                Property::TWetBulb(moist_air.t_wet_bulb().unwrap()),
+            ),
+        ];
+        for pair in pairs {
+            let result = state(pair, 1.0E+06, UnitSystem::SI);
+            assert_relative_eq!(result.t_dry_bulb(), 25.0, max_relative = 1.0E-6);
+            assert_relative_eq!(
+                result.humidity_ratio(),
+                moist_air.humidity_ratio(),
+                max_relative = 1.0E-6
+            );
         }
-    };
-    let mut convergency = SimpleConvergency {
-        eps: 1e-6f64,
-        max_iter: 50,
-    };
-    let t_init = specific_enthalpy / 1.006; // humidity_ratio = 0.0
-    let root = find_root_newton_raphson(t_init, &f, &d, &mut convergency)?;
 
-    Ok(root)
-}
+        // reference state of the enthalpy is dry air at 0 °C and 101.325 kPa
+        let dry_air = MoistAir::from_t_dry_bulb_humidity_ratio_with_options(
+            0.0,
+            0.0,
+            101325.0,
+            UnitSystem::SI,
+            real,
+        )
+        .unwrap();
+        assert_abs_diff_eq!(dry_air.specific_enthalpy(), 0.0, epsilon = 1.0E-12);
+
+        // IP units give the same state
+        let pair_ip = PropertyPair(Property::TDryBulb(77.0), Property::RelativeHumidity(0.5));
+        let moist_air_ip = state(pair_ip, 1.0E+06 / PA_PER_PSI, UnitSystem::IP);
+        assert_relative_eq!(
+            moist_air_ip.humidity_ratio(),
+            moist_air.humidity_ratio(),
+            max_relative = 1.0E-4
+        );
+        assert_relative_eq!(
+            moist_air_ip.t_dew_point().unwrap(),
+            t_celsius_to_t_fahrenheit(t_dew_point),
+            max_relative = 1.0E-4
+        );
+    }
+
+    #[test]
+    fn test_fog() {
+        let pressure = 101325.0;
+        let unit = UnitSystem::SI;
+        let saturated =
+            MoistAir::from_t_dry_bulb_relative_humidity(10.0, 1.0, pressure, unit).unwrap();
+        let saturation_humidity_ratio = saturated.humidity_ratio();
+        let fog = MoistAir::from_t_dry_bulb_humidity_ratio(
+            10.0,
+            saturation_humidity_ratio + 0.002,
+            pressure,
+            unit,
+        )
+        .unwrap();
+        assert_eq!(saturated.condensed_phase(), None);
+        assert_eq!(fog.condensed_phase(), Some(CondensedPhase::Liquid));
+        assert_relative_eq!(fog.vapor_humidity_ratio(), saturation_humidity_ratio);
+        assert_relative_eq!(fog.condensed_water_content(), 0.002, max_relative = 1.0E-9);
+        assert_relative_eq!(fog.relative_humidity().unwrap(), 1.0, max_relative = 1.0E-9);
+        assert_relative_eq!(fog.t_dew_point().unwrap(), 10.0, max_relative = 1.0E-6);
+        assert_relative_eq!(fog.specific_volume(), saturated.specific_volume());
+        // h = h_s + (W - W_s) h_w
+        assert_relative_eq!(
+            fog.specific_enthalpy(),
+            saturated.specific_enthalpy() + 0.002 * 4.186 * 10.0,
+            max_relative = 1.0E-9
+        );
+        assert_relative_eq!(
+            fog.density(),
+            saturated.density() + 0.002 / saturated.specific_volume(),
+            max_relative = 1.0E-9
+        );
+        assert!(fog.specific_entropy() > saturated.specific_entropy());
+        assert!(fog.exergy(&saturated).is_err());
+
+        // ice fog
+        let ice_fog =
+            MoistAir::from_t_dry_bulb_humidity_ratio(-10.0, 0.003, pressure, unit).unwrap();
+        assert_eq!(ice_fog.condensed_phase(), Some(CondensedPhase::Ice));
+        let saturated =
+            MoistAir::from_t_dry_bulb_relative_humidity(-10.0, 1.0, pressure, unit).unwrap();
+        assert_relative_eq!(
+            ice_fog.specific_enthalpy(),
+            saturated.specific_enthalpy()
+                + ice_fog.condensed_water_content() * (-333.4 - 2.1 * 10.0),
+            max_relative = 1.0E-9
+        );
+
+        // enthalpy and humidity ratio determine the fog state
+        let pair = PropertyPair(
+            Property::SpecificEnthalpy(fog.specific_enthalpy()),
+            Property::HumidityRatio(fog.humidity_ratio()),
+        );
+        let result = MoistAir::from_pair(pair, pressure, unit).unwrap();
+        assert_relative_eq!(result.t_dry_bulb(), 10.0, max_relative = 1.0E-6);
+        // but the other pairs cannot exceed saturation
+        let pair = PropertyPair(
+            Property::SpecificVolume(fog.specific_volume()),
+            Property::HumidityRatio(fog.humidity_ratio()),
+        );
+        assert!(matches!(
+            MoistAir::from_pair(pair, pressure, unit),
+            Err(PsychroidError::InvalidRelativeHumidity(_))
+        ));
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use approx::assert_abs_diff_eq;
-    use approx::assert_relative_eq;
+        // over-spray of adiabatic humidification conserves the enthalpy
+        let mut moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(20.0, 0.5, pressure, unit).unwrap();
+        let specific_enthalpy = moist_air.specific_enthalpy();
+        moist_air.humidify_adiabatic(1.0, 0.01).unwrap();
+        assert_eq!(moist_air.condensed_phase(), Some(CondensedPhase::Liquid));
+        assert_relative_eq!(
+            moist_air.specific_enthalpy(),
+            specific_enthalpy,
+            max_relative = 1.0E-9
+        );
+        assert_relative_eq!(
+            moist_air.t_dry_bulb(),
+            moist_air.t_wet_bulb().unwrap(),
+            max_relative = 1.0E-6
+        );
+        // heating evaporates the fog
+        moist_air.heating_t1(1.0, 40.0).unwrap();
+        assert_eq!(moist_air.condensed_phase(), None);
+
+        // isothermal humidification beyond saturation
+        let mut moist_air =
+            MoistAir::from_t_dry_bulb_relative_humidity(20.0, 0.9, pressure, unit).unwrap();
+        moist_air.humidify_isothermal(1.0, 0.005).unwrap();
+        assert_eq!(moist_air.condensed_phase(), Some(CondensedPhase::Liquid));
+    }
 
     #[test]
-    fn test_saturated_moist_air_si() {
-        let moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(-50.0, 1.0, 101325.0, UnitSystem::SI)
+    fn test_saturation_reference() {
+        let pressure = 101325.0;
+        let unit = UnitSystem::SI;
+        let over_water = ModelOptions {
+            saturation_reference: SaturationReference::Water,
+            ..Default::default()
+        };
+        let pair = PropertyPair(Property::TDryBulb(-10.0), Property::RelativeHumidity(0.8));
+        let wmo = MoistAir::from_pair_with_options(pair, pressure, unit, over_water).unwrap();
+        let ashrae = MoistAir::from_pair(pair, pressure, unit).unwrap();
+        // relative humidity over ice is higher than over supercooled water for the same vapor content
+        assert_relative_eq!(
+            wmo.humidity_ratio() / ashrae.humidity_ratio(),
+            286.5 / 259.9,
+            max_relative = 0.002
+        );
+        let same_vapor =
+            MoistAir::from_t_dry_bulb_humidity_ratio(-10.0, wmo.humidity_ratio(), pressure, unit)
                 .unwrap();
-        assert_relative_eq!(moist_air.humidity_ratio(), 0.0000243, max_relative = 0.01);
-        assert_relative_eq!(moist_air.specific_enthalpy(), -50.2220, max_relative = 0.01);
+        assert!(same_vapor.relative_humidity().unwrap() > 0.8);
 
+        // frost point does not depend on the reference
+        assert_relative_eq!(
+            wmo.t_frost_point().unwrap(),
+            same_vapor.t_frost_point().unwrap()
+        );
+        assert_relative_eq!(
+            same_vapor.t_frost_point().unwrap(),
+            same_vapor.t_dew_point().unwrap()
+        );
+        assert!(wmo.t_dew_point().unwrap() < wmo.t_frost_point().unwrap());
+        // the dew point over supercooled water reproduces the vapor pressure
+        let pair = PropertyPair(
+            Property::TDryBulb(-10.0),
+            Property::TDewPoint(wmo.t_dew_point().unwrap()),
+        );
+        let result = MoistAir::from_pair_with_options(pair, pressure, unit, over_water).unwrap();
+        assert_relative_eq!(
+            result.humidity_ratio(),
+            wmo.humidity_ratio(),
+            max_relative = 1.0E-6
+        );
+        // above the triple point, frost point and dew point coincide
         let moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(-20.0, 1.0, 101325.0, UnitSystem::SI)
-                .unwrap();
-        assert_relative_eq!(moist_air.humidity_ratio(), 0.0006373, max_relative = 0.01);
-        assert_relative_eq!(moist_air.specific_enthalpy(), -18.5420, max_relative = 0.01);
+            MoistAir::from_t_dry_bulb_relative_humidity(20.0, 0.5, pressure, unit).unwrap();
+        assert_relative_eq!(
+            moist_air.t_frost_point().unwrap(),
+            moist_air.t_dew_point().unwrap()
+        );
 
-        let moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(-5.0, 1.0, 101325.0, UnitSystem::SI)
-                .unwrap();
-        assert_relative_eq!(moist_air.humidity_ratio(), 0.0024863, max_relative = 0.005);
-        assert_relative_eq!(moist_air.specific_enthalpy(), 1.164, max_relative = 0.03);
+        // wet-bulb temperature over supercooled water
+        let t_wet_bulb = wmo.t_wet_bulb().unwrap();
+        let pair = PropertyPair(Property::TDryBulb(-10.0), Property::TWetBulb(t_wet_bulb));
+        let result = MoistAir::from_pair_with_options(pair, pressure, unit, over_water).unwrap();
+        assert_relative_eq!(
+            result.humidity_ratio(),
+            wmo.humidity_ratio(),
+            max_relative = 1.0E-6
+        );
 
-        let moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(5.0, 1.0, 101325.0, UnitSystem::SI)
-                .unwrap();
-        assert_relative_eq!(moist_air.humidity_ratio(), 0.005425, max_relative = 0.005);
-        assert_relative_eq!(moist_air.specific_enthalpy(), 18.639, max_relative = 0.01);
+        // supercooled fog
+        let fog = MoistAir::from_t_dry_bulb_humidity_ratio_with_options(
+            -5.0, 0.005, pressure, unit, over_water,
+        )
+        .unwrap();
+        assert_eq!(fog.condensed_phase(), Some(CondensedPhase::Liquid));
+    }
 
-        let moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(25.0, 1.0, 101325.0, UnitSystem::SI)
-                .unwrap();
-        assert_relative_eq!(moist_air.humidity_ratio(), 0.020173, max_relative = 0.005);
-        assert_relative_eq!(moist_air.specific_enthalpy(), 76.504, max_relative = 0.01);
+    #[test]
+    fn test_enthalpy_model() {
+        let pressure = 101325.0;
+        let variable = ModelOptions {
+            enthalpy_model: EnthalpyModel::VariableHeatCapacity,
+            ..Default::default()
+        };
+        for (unit, t_dry_bulb, pressure) in [
+            (UnitSystem::SI, 25.0, pressure),
+            (UnitSystem::IP, 77.0, 14.696),
+        ] {
+            let pair = PropertyPair(
+                Property::TDryBulb(t_dry_bulb),
+                Property::RelativeHumidity(0.5),
+            );
+            let linear = MoistAir::from_pair(pair, pressure, unit).unwrap();
+            let moist_air =
+                MoistAir::from_pair_with_options(pair, pressure, unit, variable).unwrap();
+            // close to the linear model at room temperature
+            assert_relative_eq!(
+                moist_air.specific_enthalpy(),
+                linear.specific_enthalpy(),
+                max_relative = 0.005
+            );
+            // inverse solvers recover the dry-bulb temperature
+            let h = moist_air.specific_enthalpy();
+            let w = moist_air.humidity_ratio();
+            let twb = moist_air.t_wet_bulb().unwrap();
+            for pair in [
+                PropertyPair(Property::SpecificEnthalpy(h), Property::HumidityRatio(w)),
+                PropertyPair(
+                    Property::SpecificEnthalpy(h),
+                    Property::RelativeHumidity(0.5),
+                ),
+                PropertyPair(Property::TDryBulb(t_dry_bulb), Property::TWetBulb(twb)),
+            ] {
+                let result =
+                    MoistAir::from_pair_with_options(pair, pressure, unit, variable).unwrap();
+                assert_relative_eq!(result.t_dry_bulb(), t_dry_bulb, max_relative = 1.0E-6);
+                assert_relative_eq!(result.humidity_ratio(), w, max_relative = 1.0E-6);
+            }
+        }
 
-        let moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(50.0, 1.0, 101325.0, UnitSystem::SI)
-                .unwrap();
-        assert_relative_eq!(moist_air.humidity_ratio(), 0.086863, max_relative = 0.01);
-        assert_relative_eq!(moist_air.specific_enthalpy(), 275.353, max_relative = 0.01);
+        // deviates from the linear model at high temperature
+        let unit = UnitSystem::SI;
+        let linear = MoistAir::from_t_dry_bulb_humidity_ratio(200.0, 0.01, pressure, unit).unwrap();
+        let moist_air = MoistAir::from_t_dry_bulb_humidity_ratio_with_options(
+            200.0, 0.01, pressure, unit, variable,
+        )
+        .unwrap();
+        assert!(moist_air.specific_enthalpy() - linear.specific_enthalpy() > 1.0);
 
-        let moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(85.0, 1.0, 101325.0, UnitSystem::SI)
-                .unwrap();
-        assert_relative_eq!(moist_air.humidity_ratio(), 0.838105, max_relative = 0.02);
-        assert_relative_eq!(moist_air.specific_enthalpy(), 2307.539, max_relative = 0.01);
+        // heating conserves the energy
+        let mut moist_air = moist_air;
+        let h0 = moist_air.specific_enthalpy();
+        moist_air.heating_q(1.0, 50.0).unwrap();
+        assert_relative_eq!(
+            moist_air.specific_enthalpy() - h0,
+            50.0,
+            max_relative = 1.0E-6
+        );
+        assert!(moist_air.specific_heat() > linear.specific_heat());
     }
 
     #[test]
-    fn test_saturated_moist_air_ip() {
-        let moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(-58.0, 1.0, 14.696, UnitSystem::IP)
-                .unwrap();
-        assert_relative_eq!(moist_air.humidity_ratio(), 0.0000243, max_relative = 0.01);
-        assert_relative_eq!(moist_air.specific_enthalpy(), -13.906, max_relative = 0.01);
+    fn test_basis() {
+        for (unit, pressure) in [(UnitSystem::SI, 101325.0), (UnitSystem::IP, 14.696)] {
+            let moist_air = MoistAir::from_t_dry_bulb_relative_humidity(
+                match unit {
+                    UnitSystem::SI => 30.0,
+                    UnitSystem::IP => 86.0,
+                },
+                0.6,
+                pressure,
+                unit,
+            )
+            .unwrap();
+            let w = moist_air.humidity_ratio();
+            let h = moist_air.specific_enthalpy();
+            let v = moist_air.specific_volume();
+            assert_eq!(moist_air.specific_enthalpy_on(Basis::DryAir), h);
+            assert_eq!(moist_air.humidity_content(Basis::DryAir), w);
+            assert_relative_eq!(
+                moist_air.specific_enthalpy_on(Basis::MoistAir),
+                h / (1.0 + w)
+            );
+            assert_relative_eq!(
+                moist_air.humidity_content(Basis::MoistAir),
+                moist_air.specific_humidity()
+            );
+            assert_relative_eq!(
+                moist_air.specific_volume_on(Basis::MoistAir),
+                1.0 / moist_air.density()
+            );
+            assert_relative_eq!(moist_air.specific_enthalpy_on(Basis::Volume), h / v);
+            assert_eq!(moist_air.specific_volume_on(Basis::Volume), 1.0);
 
-        let moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(-4.0, 1.0, 14.696, UnitSystem::IP).unwrap();
-        assert_relative_eq!(moist_air.humidity_ratio(), 0.0006373, max_relative = 0.01);
-        assert_relative_eq!(moist_air.specific_enthalpy(), -0.286, max_relative = 0.01);
+            // round trip from the properties on each basis
+            for basis in [Basis::DryAir, Basis::MoistAir, Basis::Volume] {
+                let h = moist_air.specific_enthalpy_on(basis);
+                let w = moist_air.humidity_content(basis);
+                let rh = moist_air.relative_humidity().unwrap();
+                let mut pairs = vec![
+                    PropertyPair(
+                        Property::TDryBulb(moist_air.t_dry_bulb()),
+                        Property::SpecificEnthalpy(h),
+                    ),
+                    PropertyPair(Property::SpecificEnthalpy(h), Property::HumidityRatio(w)),
+                    PropertyPair(
+                        Property::SpecificEnthalpy(h),
+                        Property::RelativeHumidity(rh),
+                    ),
+                ];
+                if basis != Basis::Volume {
+                    let v = moist_air.specific_volume_on(basis);
+                    pairs.push(PropertyPair(
+                        Property::SpecificVolume(v),
+                        Property::HumidityRatio(w),
+                    ));
+                }
+                for pair in pairs {
+                    let result = MoistAir::from_pair_on_basis(pair, basis, pressure, unit).unwrap();
+                    assert_relative_eq!(
+                        result.t_dry_bulb(),
+                        moist_air.t_dry_bulb(),
+                        max_relative = 1.0E-6
+                    );
+                    assert_relative_eq!(
+                        result.humidity_ratio(),
+                        moist_air.humidity_ratio(),
+                        max_relative = 1.0E-6
+                    );
+                }
+            }
+        }
 
-        let moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(23.0, 1.0, 14.696, UnitSystem::IP).unwrap();
-        assert_relative_eq!(moist_air.humidity_ratio(), 0.0024863, max_relative = 0.005);
-        assert_relative_eq!(moist_air.specific_enthalpy(), 8.186, max_relative = 0.01);
+        // specific volume on the volume basis is not a property
+        let pair = PropertyPair(Property::TDryBulb(20.0), Property::SpecificVolume(1.0));
+        let result = MoistAir::from_pair_on_basis(pair, Basis::Volume, 101325.0, UnitSystem::SI);
+        assert!(matches!(
+            result,
+            Err(PsychroidError::UnsupportedBasis { .. })
+        ));
+    }
 
+    #[test]
+    fn test_above_boiling_point() {
+        let pressure = 101325.0;
+        let unit = UnitSystem::SI;
         let moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(41.0, 1.0, 14.696, UnitSystem::IP).unwrap();
-        assert_relative_eq!(moist_air.humidity_ratio(), 0.005425, max_relative = 0.005);
-        assert_relative_eq!(moist_air.specific_enthalpy(), 15.699, max_relative = 0.01);
+            MoistAir::from_t_dry_bulb_humidity_ratio(250.0, 2.0, pressure, unit).unwrap();
+        let t_boiling_point = moist_air.t_boiling_point().unwrap();
+        assert_abs_diff_eq!(t_boiling_point, 99.97, epsilon = 0.01);
+        assert_eq!(moist_air.condensed_phase(), None);
+        assert_eq!(moist_air.degree_of_saturation().unwrap(), 0.0);
 
-        let moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(77.0, 1.0, 14.696, UnitSystem::IP).unwrap();
-        assert_relative_eq!(moist_air.humidity_ratio(), 0.020173, max_relative = 0.005);
-        assert_relative_eq!(moist_air.specific_enthalpy(), 40.576, max_relative = 0.01);
+        // relative humidity is limited by the pressure ratio
+        let pws = SaturatedWaterVapor::new(250.0, unit)
+            .unwrap()
+            .saturation_pressure();
+        let relative_humidity = moist_air.relative_humidity().unwrap();
+        assert_relative_eq!(
+            relative_humidity,
+            moist_air.partial_water_vapor_pressure() / pws
+        );
+        let pair = PropertyPair(
+            Property::TDryBulb(250.0),
+            Property::RelativeHumidity(relative_humidity),
+        );
+        let result = MoistAir::from_pair(pair, pressure, unit).unwrap();
+        assert_relative_eq!(result.humidity_ratio(), 2.0, max_relative = 1.0E-9);
+        let pair = PropertyPair(
+            Property::TDryBulb(250.0),
+            Property::RelativeHumidity(pressure / pws),
+        );
+        let error = MoistAir::from_pair(pair, pressure, unit).unwrap_err();
+        assert_eq!(error.quantity(), Some("relative humidity"));
+        let (min, max) = error.valid_range().unwrap();
+        assert_eq!(min, 0.0);
+        assert_relative_eq!(max, pressure / pws, max_relative = 0.01);
+        assert!(max > relative_humidity);
 
+        // dew point and wet-bulb temperature are below the boiling point
+        let t_dew_point = moist_air.t_dew_point().unwrap();
+        let t_wet_bulb = moist_air.t_wet_bulb().unwrap();
+        assert!(t_dew_point < t_wet_bulb && t_wet_bulb < t_boiling_point);
+        for pair in [
+            PropertyPair(Property::TDryBulb(250.0), Property::TWetBulb(t_wet_bulb)),
+            PropertyPair(Property::TDryBulb(250.0), Property::TDewPoint(t_dew_point)),
+            PropertyPair(
+                Property::SpecificEnthalpy(moist_air.specific_enthalpy()),
+                Property::HumidityRatio(2.0),
+            ),
+        ] {
+            let result = MoistAir::from_pair(pair, pressure, unit).unwrap();
+            assert_relative_eq!(result.t_dry_bulb(), 250.0, max_relative = 1.0E-6);
+            assert_relative_eq!(result.humidity_ratio(), 2.0, max_relative = 1.0E-6);
+        }
+        // the dew point approaches the boiling point for nearly pure water vapor
+        let steam =
+            MoistAir::from_t_dry_bulb_humidity_ratio(250.0, 1.0E+04, pressure, unit).unwrap();
+        assert_abs_diff_eq!(
+            steam.t_dew_point().unwrap(),
+            t_boiling_point,
+            epsilon = 0.01
+        );
+
+        // vacuum dryer in IP units
+        let pressure = 3.0;
+        let unit = UnitSystem::IP;
         let moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(122.0, 1.0, 14.696, UnitSystem::IP)
-                .unwrap();
-        assert_relative_eq!(moist_air.humidity_ratio(), 0.086863, max_relative = 0.01);
-        assert_relative_eq!(moist_air.specific_enthalpy(), 126.066, max_relative = 0.01);
+            MoistAir::from_t_dry_bulb_humidity_ratio(200.0, 0.5, pressure, unit).unwrap();
+        let t_boiling_point = moist_air.t_boiling_point().unwrap();
+        assert!(t_boiling_point < 200.0);
+        let t_wet_bulb = moist_air.t_wet_bulb().unwrap();
+        assert!(t_wet_bulb < t_boiling_point);
+        let pair = PropertyPair(Property::TDryBulb(200.0), Property::TWetBulb(t_wet_bulb));
+        let result = MoistAir::from_pair(pair, pressure, unit).unwrap();
+        assert_relative_eq!(result.humidity_ratio(), 0.5, max_relative = 1.0E-6);
 
+        // water vapor cannot be saturated above the critical point
         let moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(185.0, 1.0, 14.696, UnitSystem::IP)
-                .unwrap();
-        assert_relative_eq!(moist_air.humidity_ratio(), 0.838105, max_relative = 0.015);
-        assert_relative_eq!(moist_air.specific_enthalpy(), 999.749, max_relative = 0.01);
+            MoistAir::from_t_dry_bulb_humidity_ratio(380.0, 0.1, 101325.0, UnitSystem::SI).unwrap();
+        assert_eq!(moist_air.relative_humidity().unwrap(), 0.0);
+        assert!(moist_air.specific_enthalpy().is_finite());
     }
 
     #[test]
-    fn test_relative_humidity_humidity_ratio() {
-        let relative_humidity_array = [0.0, 0.2, 0.4, 0.6, 0.8, 1.0];
-        // SI units
-        relative_humidity_array.iter().for_each(|&rh| {
-            let moist_air =
-                MoistAir::from_t_dry_bulb_relative_humidity(25.0, rh, 101325.0, UnitSystem::SI)
-                    .unwrap();
-            let humidity_ratio = moist_air.humidity_ratio();
-            let moist_air = MoistAir::from_t_dry_bulb_humidity_ratio(
-                25.0,
-                humidity_ratio,
-                101325.0,
-                UnitSystem::SI,
-            )
-            .unwrap();
-            assert_abs_diff_eq!(moist_air.relative_humidity().unwrap(), rh, epsilon = 1.0E-8);
-        });
-        // IP units
-        relative_humidity_array.iter().for_each(|&rh| {
-            let moist_air =
-                MoistAir::from_t_dry_bulb_relative_humidity(77.0, rh, 14.696, UnitSystem::IP)
-                    .unwrap();
-            let humidity_ratio = moist_air.humidity_ratio();
-            let moist_air = MoistAir::from_t_dry_bulb_humidity_ratio(
-                77.0,
-                humidity_ratio,
-                14.696,
-                UnitSystem::IP,
-            )
-            .unwrap();
-            assert_abs_diff_eq!(moist_air.relative_humidity().unwrap(), rh, epsilon = 1.0E-8);
-        });
-    }
+    fn test_process_output() {
+        let inlet =
+            MoistAir::from_t_dry_bulb_relative_humidity(30.0, 0.6, 101325.0, UnitSystem::SI)
+                .unwrap();
+        let copy = inlet;
+        assert_eq!(copy, inlet);
 
-    #[test]
-    fn test_example1_si() {
-        // Test case from ASHRAE Handbook - Fundamentals (2017) SI Ch. 1, Example 1
-        let moist_air =
-            MoistAir::from_t_dry_bulb_t_wet_bulb(40.0, 20.0, 101325.0, UnitSystem::SI).unwrap();
-        assert_relative_eq!(moist_air.humidity_ratio(), 0.0065, max_relative = 0.02);
-        assert_relative_eq!(moist_air.specific_enthalpy(), 56.7, max_relative = 0.01);
-        assert_relative_eq!(moist_air.t_dew_point().unwrap(), 7.0, max_relative = 0.06);
+        // same results as the mutating methods, and the inlet is not modified
+        let output = inlet.after_cooling_t1(2.0, 15.0).unwrap();
+        let mut outlet = inlet;
+        let q = outlet.cooling_t1(2.0, 15.0).unwrap();
+        assert_eq!(output.outlet, outlet);
+        assert_relative_eq!(output.q, -q);
+        assert_eq!(inlet, copy);
+
+        let output = inlet.after_heating_q(2.0, 10.0).unwrap();
+        assert_relative_eq!(output.q, 10.0, max_relative = 1.0E-6);
+        let output = inlet.after_heating_dt(2.0, 5.0).unwrap();
+        assert_relative_eq!(output.outlet.t_dry_bulb(), 35.0);
+        // outlet states out of range are rejected like on construction
+        assert!(matches!(
+            inlet.after_heating_t1(2.0, 1000.0),
+            Err(PsychroidError::InvalidTDryBulb { .. })
+        ));
+        assert!(matches!(
+            inlet.after_heating_dt(2.0, f64::NAN),
+            Err(PsychroidError::NonFiniteInput { .. })
+        ));
+        let mut heated = inlet;
+        assert!(heated.heating_dt(2.0, 500.0).is_err());
+        assert_eq!(heated, inlet);
+        assert!(inlet.after_cooling_t1(2.0, -150.0).is_err());
+        let output = inlet.after_cooling_q(2.0, 10.0).unwrap();
+        assert_relative_eq!(output.q, -10.0, max_relative = 1.0E-6);
+        let output = inlet.after_cooling_dt(2.0, 5.0).unwrap();
+        assert_relative_eq!(output.outlet.t_dry_bulb(), 25.0);
+        let output = inlet.after_humidify_adiabatic(2.0, 0.002).unwrap();
+        assert_abs_diff_eq!(output.q, 0.0, epsilon = 1.0E-9);
+        assert!(output.outlet.t_dry_bulb() < inlet.t_dry_bulb());
+        let output = inlet.after_humidify_isothermal(2.0, 0.002).unwrap();
+        assert!(output.q > 0.0);
+        let output = inlet.after_cooling_saturation(2.0).unwrap();
         assert_relative_eq!(
-            moist_air.relative_humidity().unwrap(),
-            0.14,
-            max_relative = 0.01
+            output.outlet.relative_humidity().unwrap(),
+            1.0,
+            max_relative = 1.0E-6
         );
-    }
 
-    #[test]
-    fn test_example1_ip() {
-        // Test case from ASHRAE Handbook - Fundamentals (2017) IP Ch. 1, Example 1
-        let moist_air =
-            MoistAir::from_t_dry_bulb_t_wet_bulb(100.0, 65.0, 14.696, UnitSystem::IP).unwrap();
-        assert_relative_eq!(moist_air.humidity_ratio(), 0.00523, max_relative = 0.01);
-        assert_relative_eq!(moist_air.specific_enthalpy(), 29.80, max_relative = 0.01);
-        assert_relative_eq!(moist_air.t_dew_point().unwrap(), 40.0, max_relative = 0.01);
+        // exergy destruction of the processes, which is zero only without change
+        let dead_state =
+            MoistAir::from_t_dry_bulb_relative_humidity(25.0, 0.5, 101325.0, UnitSystem::SI)
+                .unwrap();
+        let outputs = [
+            inlet.after_heating_t1(2.0, 40.0).unwrap(),
+            inlet.after_cooling_t1(2.0, 25.0).unwrap(),
+            inlet.after_cooling_t1(2.0, 10.0).unwrap(),
+            inlet.after_cooling_q(2.0, 30.0).unwrap(),
+            inlet.after_humidify_adiabatic(2.0, 0.004).unwrap(),
+            inlet.after_humidify_isothermal(2.0, 0.004).unwrap(),
+            inlet.after_cooling_saturation(2.0).unwrap(),
+        ];
+        for output in outputs {
+            assert!(output.entropy_generation > 0.0);
+            assert_relative_eq!(
+                output.exergy_destruction(&dead_state).unwrap(),
+                298.15 * output.entropy_generation
+            );
+        }
+        let output = inlet.after_heating_dt(2.0, 0.0).unwrap();
+        assert_eq!(output.exergy_destruction(&dead_state).unwrap(), 0.0);
+        // exergy balance of heating at the outlet temperature, X_dest = q (1 - T0 / T1) - m (e1 - e0)
+        let output = inlet.after_heating_t1(2.0, 40.0).unwrap();
+        let de = output.outlet.exergy(&dead_state).unwrap() - inlet.exergy(&dead_state).unwrap();
         assert_relative_eq!(
-            moist_air.relative_humidity().unwrap(),
-            0.13,
-            max_relative = 0.02
+            output.exergy_destruction(&dead_state).unwrap(),
+            output.q * (1.0 - 298.15 / 313.15) - 2.0 * de.total(),
+            max_relative = 1.0E-9
+        );
+        // IP units, in Btu/h
+        let (mut inlet_ip, mut dead_state_ip) = (inlet, dead_state);
+        inlet_ip.set_unit(UnitSystem::IP);
+        dead_state_ip.set_unit(UnitSystem::IP);
+        let output_ip = inlet_ip.after_heating_dt(2.0 * 7936.64, 18.0).unwrap();
+        let output = inlet.after_heating_dt(2.0, 10.0).unwrap();
+        assert_relative_eq!(
+            output_ip.exergy_destruction(&dead_state_ip).unwrap(),
+            output.exergy_destruction(&dead_state).unwrap() * 3412.14,
+            max_relative = 1.0E-4
         );
+        assert!(output.exergy_destruction(&dead_state_ip).is_err());
+
+        // approximate equality
+        let mut other = inlet;
+        other.heating_dt(1.0, 1.0E-07).unwrap();
+        assert_ne!(other, inlet);
+        assert!(other.approx_eq(&inlet, 1.0E-9));
+        other.heating_dt(1.0, 1.0E-03).unwrap();
+        assert!(!other.approx_eq(&inlet, 1.0E-9));
+        let mut ip = inlet;
+        ip.set_unit(UnitSystem::IP);
+        assert!(!ip.approx_eq(&inlet, 1.0));
     }
 
     #[test]
-    fn test_example2_si() {
-        // Test case from ASHRAE Handbook - Fundamentals (2017) SI Ch. 1, Example 2
-        let mut moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(2.0, 1.0, 101325.0, UnitSystem::SI)
-                .unwrap();
-        let volumetric_flow_rate = 10.0; // m3/s
-        let mass_flow_rate =
-            volumetric_flow_rate * moist_air.density() / (1.0 + moist_air.humidity_ratio());
-        assert_relative_eq!(mass_flow_rate, 12.74, max_relative = 0.00001);
-        let q = moist_air.heating_t1(mass_flow_rate, 40.0);
-        assert_relative_eq!(q, 490.0, max_relative = 0.002);
+    fn test_input_validation() {
+        let pressure = 101325.0;
+        let unit = UnitSystem::SI;
+        // non-finite inputs and pressures
+        assert!(matches!(
+            MoistAir::from_t_dry_bulb_humidity_ratio(f64::NAN, 0.01, pressure, unit),
+            Err(PsychroidError::NonFiniteInput { .. })
+        ));
+        assert!(matches!(
+            MoistAir::from_t_dry_bulb_relative_humidity(20.0, f64::NAN, pressure, unit),
+            Err(PsychroidError::NonFiniteInput { .. })
+        ));
+        for pressure in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(matches!(
+                MoistAir::from_t_dry_bulb_relative_humidity(20.0, 0.5, pressure, unit),
+                Err(PsychroidError::NonFiniteInput {
+                    name: "pressure",
+                    ..
+                })
+            ));
+        }
+        for pressure in [0.0, -101325.0] {
+            assert!(matches!(
+                MoistAir::from_t_dry_bulb_relative_humidity(20.0, 0.5, pressure, unit),
+                Err(PsychroidError::InvalidPressure { .. })
+            ));
+        }
+        assert!(matches!(
+            SaturatedWaterVapor::new(f64::NAN, unit),
+            Err(PsychroidError::NonFiniteInput { .. })
+        ));
+        assert!(matches!(
+            MoistAir::from_t_dry_bulb_humidity_ratio(20.0, -0.001, pressure, unit),
+            Err(PsychroidError::InvalidHumidityRatio(_))
+        ));
+
+        // dew point and wet-bulb temperature cannot exceed the dry-bulb temperature
+        assert!(matches!(
+            MoistAir::from_t_dry_bulb_t_dew_point(20.0, 25.0, pressure, unit),
+            Err(PsychroidError::InvalidTDewPoint { .. })
+        ));
+        assert!(matches!(
+            MoistAir::from_t_dry_bulb_t_wet_bulb(20.0, 25.0, pressure, unit),
+            Err(PsychroidError::InvalidTWetBulb { .. })
+        ));
+        // nor can the wet-bulb temperature be so low that the humidity ratio is negative
+        assert!(matches!(
+            MoistAir::from_t_dry_bulb_t_wet_bulb(40.0, 0.0, pressure, unit),
+            Err(PsychroidError::InvalidTWetBulb { .. })
+        ));
+        let pair = PropertyPair(Property::TDewPoint(25.0), Property::TDryBulb(20.0));
+        assert!(matches!(
+            MoistAir::from_pair(pair, pressure, unit),
+            Err(PsychroidError::InvalidTDewPoint { .. })
+        ));
+
+        // specific enthalpy and volume below those of dry air
+        assert!(matches!(
+            MoistAir::from_t_dry_bulb_enthalpy(20.0, 10.0, pressure, unit),
+            Err(PsychroidError::InvalidSpecificEnthalpy { .. })
+        ));
+        let pair = PropertyPair(Property::TDryBulb(20.0), Property::SpecificVolume(0.8));
+        assert!(matches!(
+            MoistAir::from_pair(pair, pressure, unit),
+            Err(PsychroidError::InvalidSpecificVolume { .. })
+        ));
+
+        // dew point above the boiling point at low pressure
+        assert!(matches!(
+            MoistAir::from_t_dry_bulb_t_dew_point(60.0, 50.0, 10000.0, unit),
+            Err(PsychroidError::PressureBelowSaturation { .. })
+        ));
+
+        // the state is unchanged when a humidification fails
+        let inlet = MoistAir::from_t_dry_bulb_relative_humidity(30.0, 0.5, pressure, unit).unwrap();
+        let mut moist_air = inlet;
+        assert!(moist_air.humidify_isothermal(1.0, -1.0).is_err());
+        assert_eq!(moist_air, inlet);
+        assert!(moist_air.humidify_adiabatic(1.0, -1.0).is_err());
+        assert_eq!(moist_air, inlet);
+        assert!(moist_air.humidify_isothermal(1.0, f64::NAN).is_err());
+        assert_eq!(moist_air, inlet);
     }
 
     #[test]
-    fn test_example2_ip() {
-        // Test case from ASHRAE Handbook - Fundamentals (2017) IP Ch. 1, Example 2
-        let mut moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(35.0, 1.0, 14.696, UnitSystem::IP).unwrap();
-        let volumetric_flow_rate = 20000.0; // cfm
-        let mass_flow_rate =
-            volumetric_flow_rate * 60.0 * moist_air.density() / (1.0 + moist_air.humidity_ratio());
-        assert_relative_eq!(mass_flow_rate, 95620.0, max_relative = 0.0006);
-        let q = moist_air.heating_t1(mass_flow_rate, 100.0);
-        assert_relative_eq!(q, 1507000.0, max_relative = 0.003);
+    fn test_robust_solver() {
+        let pressure = 101325.0;
+        let unit = UnitSystem::SI;
+        // wet-bulb temperature and dew point around the switch between ice and water
+        for reference in [SaturationReference::Ice, SaturationReference::Water] {
+            let options = ModelOptions {
+                saturation_reference: reference,
+                ..Default::default()
+            };
+            for (t_dry_bulb, relative_humidity) in
+                [(2.0, 0.3), (0.5, 0.9), (4.0, 0.05), (-0.5, 0.99)]
+            {
+                let pair = PropertyPair(
+                    Property::TDryBulb(t_dry_bulb),
+                    Property::RelativeHumidity(relative_humidity),
+                );
+                let moist_air =
+                    MoistAir::from_pair_with_options(pair, pressure, unit, options).unwrap();
+                for property in [
+                    Property::TWetBulb(moist_air.t_wet_bulb().unwrap()),
+                    Property::TDewPoint(moist_air.t_dew_point().unwrap()),
+                ] {
+                    let pair = PropertyPair(Property::TDryBulb(t_dry_bulb), property);
+                    let result =
+                        MoistAir::from_pair_with_options(pair, pressure, unit, options).unwrap();
+                    assert_relative_eq!(
+                        result.humidity_ratio(),
+                        moist_air.humidity_ratio(),
+                        max_relative = 1.0E-6
+                    );
+                }
+            }
+        }
+
+        // very dry air, and hot humid air close to the critical point
+        let dry = MoistAir::from_t_dry_bulb_humidity_ratio(20.0, 1.0E-06, pressure, unit).unwrap();
+        let t_dew_point = dry.t_dew_point().unwrap();
+        assert!((-100.0..-60.0).contains(&t_dew_point));
+        let moist_air =
+            MoistAir::from_t_dry_bulb_t_dew_point(20.0, t_dew_point, pressure, unit).unwrap();
+        assert_relative_eq!(moist_air.humidity_ratio(), 1.0E-06, max_relative = 1.0E-6);
+        let hot = MoistAir::from_t_dry_bulb_humidity_ratio(390.0, 10.0, pressure, unit).unwrap();
+        let t_wet_bulb = hot.t_wet_bulb().unwrap();
+        assert!(t_wet_bulb < 100.0 && t_wet_bulb > 90.0);
+
+        // no dew point of dry air
+        let mut dry_air =
+            MoistAir::from_t_dry_bulb_humidity_ratio(20.0, 0.0, pressure, unit).unwrap();
+        assert!(matches!(
+            dry_air.cooling_saturation(1.0),
+            Err(PsychroidError::OutOfRange { .. })
+        ));
+
+        // user-configurable tolerance and iteration limit
+        let options = ModelOptions {
+            solver: SolverOptions {
+                tolerance: 0.0,
+                residual_tolerance: 0.0,
+                max_iter: 2,
+            },
+            ..Default::default()
+        };
+        let moist_air = MoistAir::from_t_dry_bulb_humidity_ratio_with_options(
+            30.0, 0.01, pressure, unit, options,
+        )
+        .unwrap();
+        let error = moist_air.t_wet_bulb().unwrap_err();
+        assert_eq!(
+            error.failure_reason(),
+            Some(SolverFailureReason::NoConvergence)
+        );
+        assert_eq!(error.iterations(), Some(2));
     }
 
     #[test]
-    fn test_relative_humidity_100_si() {
-        let t_dry_bulb: Vec<f64> = (-100..=-5).step_by(5).map(|x| x as f64).collect();
+    fn test_solver_report() {
+        let pressure = 101325.0;
         let unit = UnitSystem::SI;
+        // saturated moist air needs no iteration
+        let saturated =
+            MoistAir::from_t_dry_bulb_relative_humidity(20.0, 1.0, pressure, unit).unwrap();
+        let (t_wet_bulb, report) = saturated.t_wet_bulb_with_report().unwrap();
+        assert_relative_eq!(t_wet_bulb, 20.0);
+        assert_eq!(report.iterations, 0);
+        assert_eq!(report.initial_guess, 20.0);
+        assert_eq!(report.branch, SaturationBranch::Water);
 
-        t_dry_bulb.iter().for_each(|&t| {
-            let moist_air =
-                MoistAir::from_t_dry_bulb_relative_humidity(t, 1.0, 101325.0, unit).unwrap();
-            assert_relative_eq!(moist_air.t_dew_point().unwrap(), t, max_relative = 5.0E-5);
-            assert_relative_eq!(moist_air.t_wet_bulb().unwrap(), t, max_relative = 5.0E-5);
-        });
+        // the approximations of Stull (2011) and Magnus are close to the solutions in their range
+        for t_dry_bulb in [-20.0, -10.0, 0.0, 10.0, 25.0, 50.0] {
+            for relative_humidity in [0.05, 0.3, 0.6, 0.9] {
+                let moist_air = MoistAir::from_t_dry_bulb_relative_humidity(
+                    t_dry_bulb,
+                    relative_humidity,
+                    pressure,
+                    unit,
+                )
+                .unwrap();
+                let (t_wet_bulb, report) = moist_air.t_wet_bulb_with_report().unwrap();
+                assert_abs_diff_eq!(report.initial_guess, t_wet_bulb, epsilon = 1.5);
+                let (t_dew_point, report) = moist_air.t_dew_point_with_report().unwrap();
+                assert_abs_diff_eq!(report.initial_guess, t_dew_point, epsilon = 0.05);
+            }
+        }
 
-        let t_dry_bulb: Vec<f64> = (5..=195).step_by(5).map(|x| x as f64).collect();
-        let unit = UnitSystem::SI;
+        // the branch follows the saturation reference below the triple point
+        let pair = PropertyPair(Property::TDryBulb(-10.0), Property::RelativeHumidity(0.5));
+        let over_ice = MoistAir::from_pair(pair, pressure, unit).unwrap();
+        let (t_dew_point, report) = over_ice.t_dew_point_with_report().unwrap();
+        assert_eq!(t_dew_point, over_ice.t_dew_point().unwrap());
+        assert_eq!(report.method, SolverMethod::NewtonRaphson);
+        assert_eq!(report.branch, SaturationBranch::Ice);
+        assert!(report.residual.abs() < 1.0E-6);
+        let options = ModelOptions {
+            saturation_reference: SaturationReference::Water,
+            ..Default::default()
+        };
+        let over_water = MoistAir::from_pair_with_options(pair, pressure, unit, options).unwrap();
+        let (_, report) = over_water.t_wet_bulb_with_report().unwrap();
+        assert_eq!(report.branch, SaturationBranch::Water);
+        assert!(report.iterations > 0);
 
-        t_dry_bulb.iter().for_each(|&t| {
-            let moist_air =
-                MoistAir::from_t_dry_bulb_relative_humidity(t, 1.0, 101325.0, unit).unwrap();
-            assert_relative_eq!(moist_air.t_dew_point().unwrap(), t, max_relative = 5.0E-5);
-            assert_relative_eq!(moist_air.t_wet_bulb().unwrap(), t, max_relative = 5.0E-5);
-        });
+        // the saturation pressure and the condensed water share the boundary at the triple point
+        for (t, branch, phase) in [
+            (0.005, SaturationBranch::Ice, CondensedPhase::Ice),
+            (0.015, SaturationBranch::Water, CondensedPhase::Liquid),
+        ] {
+            let fog = MoistAir::from_t_dry_bulb_humidity_ratio(t, 0.005, pressure, unit).unwrap();
+            let pws = SaturatedWaterVapor::new(t, unit).unwrap();
+            assert_eq!(pws.branch(), branch);
+            assert_eq!(fog.condensed_phase(), Some(phase));
+        }
 
-        let moist_air =
-            MoistAir::from_t_dry_bulb_relative_humidity(0.0, 1.0, 101325.0, unit).unwrap();
-        assert_abs_diff_eq!(moist_air.t_dew_point().unwrap(), 0.0, epsilon = 1.0E-8);
-        assert_abs_diff_eq!(moist_air.t_wet_bulb().unwrap(), 0.0, epsilon = 1.0E-8);
+        // no dew point of dry air
+        let dry_air = MoistAir::from_t_dry_bulb_humidity_ratio(20.0, 0.0, pressure, unit).unwrap();
+        let (t_dew_point, report) = dry_air.t_dew_point_with_report().unwrap();
+        assert!(t_dew_point.is_nan() && report.residual.is_nan());
+        assert_eq!(report.iterations, 0);
     }
 }
@@ -0,0 +1,410 @@
+//! Strongly typed physical quantities, as an optional layer over the API of bare `f64` with [`UnitSystem`].
+//!
+//! Each quantity is a newtype of `f64` in a fixed unit, e.g. [`Celsius`] or [`Pascal`], and the typed
+//! constructors and accessors of [`MoistAir`], [`SaturatedWaterVapor`] and the atmosphere take and return
+//! them instead of the values in the unit system of the state. The temperatures and pressures in other
+//! units, e.g. [`Fahrenheit`], [`Kelvin`] or [`Psi`], are converted on the way in by [`From`],
+//! so that a unit mistake becomes a compile error rather than a wrong state.
+//! The dimensionless [`RelativeHumidity`] is checked to be a ratio in [0, 1] when it is created.
+//!
+//! The typed states are held in SI units, see [`MoistAir::set_unit`] to read them as `f64` in IP units.
+//!
+//! # Example
+//! ```
+//! use psychroid::quantity::{Celsius, Fahrenheit, KgPerKg, Pascal, Psi};
+//! use psychroid::MoistAir;
+//!
+//! let air = MoistAir::from_t_dry_bulb_humidity_ratio_typed(
+//!     Fahrenheit(77.0),
+//!     KgPerKg(0.01),
+//!     Psi(14.696),
+//! )
+//! .unwrap();
+//! let t_dew_point: Celsius = air.t_dew_point_typed().unwrap();
+//! assert!((air.t_dry_bulb_typed().0 - 25.0).abs() < 1.0E-12);
+//! assert!(t_dew_point < air.t_dry_bulb_typed());
+//! assert!((air.pressure_typed().0 - Pascal::from(Psi(14.696)).0).abs() < 1.0E-9);
+//! ```
+//!
+//! A pressure given where a temperature is expected does not compile:
+//! ```compile_fail
+//! use psychroid::quantity::{KgPerKg, Pascal};
+//! use psychroid::MoistAir;
+//!
+//! let air = MoistAir::from_t_dry_bulb_humidity_ratio_typed(
+//!     Pascal(101325.0),
+//!     KgPerKg(0.01),
+//!     Pascal(101325.0),
+//! );
+//! ```
+use crate::atmosphere;
+use crate::common::{t_celsius_to_t_fahrenheit, t_celsius_to_t_kelvin, UnitSystem, PA_PER_PSI};
+use crate::common::{t_fahrenheit_to_t_celsius, t_kelvin_to_t_celsius};
+use crate::error::PsychroidError;
+use crate::moist_air::MoistAir;
+use crate::saturated_water_vapor::SaturatedWaterVapor;
+use crate::units;
+use crate::validation::check_relative_humidity;
+
+/// Temperature in \\(^\\circ \\mathrm{C}\\)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Celsius(pub f64);
+
+/// Temperature in \\(^\\circ \\mathrm{F}\\)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Fahrenheit(pub f64);
+
+/// Absolute temperature in \\(\\mathrm{K}\\)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Kelvin(pub f64);
+
+/// Pressure in \\(\\mathrm{Pa}\\)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Pascal(pub f64);
+
+/// Pressure in \\(\\mathrm{Psi}\\)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Psi(pub f64);
+
+/// Humidity ratio in \\( \\mathrm{kg_w/kg_{da}} \\), which is the same in \\( \\mathrm{lb_w/lb_{da}} \\)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct KgPerKg(pub f64);
+
+/// Specific enthalpy in \\( \\mathrm{kJ/kg_{da}} \\), of which the reference state is dry air
+/// and liquid water at \\(0~^\\circ \\mathrm{C}\\)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct KjPerKg(pub f64);
+
+/// Altitude in \\(\\mathrm{m}\\)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Meter(pub f64);
+
+/// Relative humidity as a ratio in [0, 1], not in percent
+///
+/// Unlike the other quantities, the value is validated on construction,
+/// so that a relative humidity in percent is rejected before it reaches a constructor.
+///
+/// # Example
+/// ```
+/// use psychroid::quantity::RelativeHumidity;
+///
+/// assert_eq!(RelativeHumidity::new(0.5).unwrap().value(), 0.5);
+/// assert!(RelativeHumidity::new(50.0).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct RelativeHumidity(f64);
+
+impl RelativeHumidity {
+    /// Creates the relative humidity from a ratio in [0, 1]
+    ///
+    /// # Errors
+    /// * `NonFiniteInput` if the value is NaN or infinite
+    /// * `InvalidRelativeHumidity` if the value is out of [0, 1]
+    pub fn new(value: f64) -> Result<Self, PsychroidError> {
+        check_relative_humidity(value)?;
+        Ok(RelativeHumidity(value))
+    }
+
+    /// Returns the relative humidity as a ratio
+    pub fn value(self) -> f64 {
+        self.0
+    }
+}
+
+impl TryFrom<f64> for RelativeHumidity {
+    type Error = PsychroidError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        RelativeHumidity::new(value)
+    }
+}
+
+impl From<Fahrenheit> for Celsius {
+    fn from(t: Fahrenheit) -> Self {
+        Celsius(t_fahrenheit_to_t_celsius(t.0))
+    }
+}
+
+impl From<Kelvin> for Celsius {
+    fn from(t: Kelvin) -> Self {
+        Celsius(t_kelvin_to_t_celsius(t.0))
+    }
+}
+
+impl From<Celsius> for Fahrenheit {
+    fn from(t: Celsius) -> Self {
+        Fahrenheit(t_celsius_to_t_fahrenheit(t.0))
+    }
+}
+
+impl From<Celsius> for Kelvin {
+    fn from(t: Celsius) -> Self {
+        Kelvin(t_celsius_to_t_kelvin(t.0))
+    }
+}
+
+impl From<Psi> for Pascal {
+    fn from(pressure: Psi) -> Self {
+        Pascal(pressure.0 * PA_PER_PSI)
+    }
+}
+
+impl From<Pascal> for Psi {
+    fn from(pressure: Pascal) -> Self {
+        Psi(pressure.0 / PA_PER_PSI)
+    }
+}
+
+/// Typed constructors and accessors, of which the states are in SI units
+impl MoistAir {
+    /// Creates moist air from dry-bulb temperature and humidity ratio,
+    /// see [`MoistAir::from_t_dry_bulb_humidity_ratio`]
+    pub fn from_t_dry_bulb_humidity_ratio_typed(
+        t_dry_bulb: impl Into<Celsius>,
+        humidity_ratio: KgPerKg,
+        pressure: impl Into<Pascal>,
+    ) -> Result<Self, PsychroidError> {
+        MoistAir::from_t_dry_bulb_humidity_ratio(
+            t_dry_bulb.into().0,
+            humidity_ratio.0,
+            pressure.into().0,
+            UnitSystem::SI,
+        )
+    }
+
+    /// Creates moist air from dry-bulb temperature and relative humidity,
+    /// see [`MoistAir::from_t_dry_bulb_relative_humidity`]
+    pub fn from_t_dry_bulb_relative_humidity_typed(
+        t_dry_bulb: impl Into<Celsius>,
+        relative_humidity: RelativeHumidity,
+        pressure: impl Into<Pascal>,
+    ) -> Result<Self, PsychroidError> {
+        MoistAir::from_t_dry_bulb_relative_humidity(
+            t_dry_bulb.into().0,
+            relative_humidity.0,
+            pressure.into().0,
+            UnitSystem::SI,
+        )
+    }
+
+    /// Creates moist air from dry-bulb and wet-bulb temperatures,
+    /// see [`MoistAir::from_t_dry_bulb_t_wet_bulb`]
+    pub fn from_t_dry_bulb_t_wet_bulb_typed(
+        t_dry_bulb: impl Into<Celsius>,
+        t_wet_bulb: impl Into<Celsius>,
+        pressure: impl Into<Pascal>,
+    ) -> Result<Self, PsychroidError> {
+        MoistAir::from_t_dry_bulb_t_wet_bulb(
+            t_dry_bulb.into().0,
+            t_wet_bulb.into().0,
+            pressure.into().0,
+            UnitSystem::SI,
+        )
+    }
+
+    /// Creates moist air from dry-bulb and dew point temperatures,
+    /// see [`MoistAir::from_t_dry_bulb_t_dew_point`]
+    pub fn from_t_dry_bulb_t_dew_point_typed(
+        t_dry_bulb: impl Into<Celsius>,
+        t_dew_point: impl Into<Celsius>,
+        pressure: impl Into<Pascal>,
+    ) -> Result<Self, PsychroidError> {
+        MoistAir::from_t_dry_bulb_t_dew_point(
+            t_dry_bulb.into().0,
+            t_dew_point.into().0,
+            pressure.into().0,
+            UnitSystem::SI,
+        )
+    }
+
+    /// Creates moist air from dry-bulb temperature and specific enthalpy,
+    /// see [`MoistAir::from_t_dry_bulb_enthalpy`]
+    pub fn from_t_dry_bulb_enthalpy_typed(
+        t_dry_bulb: impl Into<Celsius>,
+        specific_enthalpy: KjPerKg,
+        pressure: impl Into<Pascal>,
+    ) -> Result<Self, PsychroidError> {
+        MoistAir::from_t_dry_bulb_enthalpy(
+            t_dry_bulb.into().0,
+            specific_enthalpy.0,
+            pressure.into().0,
+            UnitSystem::SI,
+        )
+    }
+
+    /// Returns the dry-bulb temperature, regardless of the unit system of the state
+    pub fn t_dry_bulb_typed(&self) -> Celsius {
+        Celsius(units::t_to_si(self.t_dry_bulb(), self.unit()))
+    }
+
+    /// Returns the humidity ratio, including condensed water
+    pub fn humidity_ratio_typed(&self) -> KgPerKg {
+        KgPerKg(self.humidity_ratio())
+    }
+
+    /// Returns the total pressure, regardless of the unit system of the state
+    pub fn pressure_typed(&self) -> Pascal {
+        Pascal(self.t_kelvin_pressure_si().1)
+    }
+
+    /// Returns the relative humidity, see [`MoistAir::relative_humidity`]
+    pub fn relative_humidity_typed(&self) -> Result<RelativeHumidity, PsychroidError> {
+        Ok(RelativeHumidity(self.relative_humidity()?))
+    }
+
+    /// Returns the wet-bulb temperature, see [`MoistAir::t_wet_bulb`]
+    pub fn t_wet_bulb_typed(&self) -> Result<Celsius, PsychroidError> {
+        Ok(Celsius(units::t_to_si(self.t_wet_bulb()?, self.unit())))
+    }
+
+    /// Returns the dew point temperature, see [`MoistAir::t_dew_point`]
+    pub fn t_dew_point_typed(&self) -> Result<Celsius, PsychroidError> {
+        Ok(Celsius(self.t_dew_point_si()?))
+    }
+
+    /// Returns the specific enthalpy, see [`MoistAir::specific_enthalpy`]
+    pub fn specific_enthalpy_typed(&self) -> KjPerKg {
+        KjPerKg(self.specific_enthalpy_si())
+    }
+
+    /// Returns the partial pressure of water vapor, see [`MoistAir::partial_water_vapor_pressure`]
+    pub fn partial_water_vapor_pressure_typed(&self) -> Pascal {
+        let pressure = self.partial_water_vapor_pressure();
+        Pascal(units::pressure_to_si(pressure, self.unit()))
+    }
+}
+
+/// Typed constructor and accessor of saturated water vapor, of which the state is in SI units
+impl SaturatedWaterVapor {
+    /// Creates saturated water vapor at the temperature, see [`SaturatedWaterVapor::new`]
+    pub fn new_typed(t_dry_bulb: impl Into<Celsius>) -> Result<Self, PsychroidError> {
+        SaturatedWaterVapor::new(t_dry_bulb.into().0, UnitSystem::SI)
+    }
+
+    /// Returns the saturation pressure, regardless of the unit system of the state
+    pub fn saturation_pressure_typed(&self) -> Pascal {
+        Pascal(units::pressure_to_si(
+            self.saturation_pressure(),
+            self.unit(),
+        ))
+    }
+}
+
+/// Calculates the atmosphere temperature at the altitude, see [`atmosphere::atmosphere_temperature`]
+pub fn atmosphere_temperature(altitude: Meter) -> Celsius {
+    Celsius(atmosphere::atmosphere_temperature(altitude.0))
+}
+
+/// Calculates the atmosphere pressure at the altitude, see [`atmosphere::atmosphere_pressure`]
+pub fn atmosphere_pressure(altitude: Meter) -> Pascal {
+    Pascal(atmosphere::atmosphere_pressure(altitude.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_conversions() {
+        assert_relative_eq!(Celsius::from(Fahrenheit(212.0)).0, 100.0);
+        assert_relative_eq!(Celsius::from(Kelvin(273.15)).0, 0.0);
+        assert_relative_eq!(Fahrenheit::from(Celsius(-40.0)).0, -40.0);
+        assert_relative_eq!(Kelvin::from(Celsius(25.0)).0, 298.15);
+        assert_relative_eq!(Psi::from(Pascal::from(Psi(14.696))).0, 14.696);
+    }
+
+    #[test]
+    fn test_consistent_with_untyped() {
+        let typed = MoistAir::from_t_dry_bulb_t_wet_bulb_typed(
+            Fahrenheit(86.0),
+            Celsius(20.0),
+            Psi(14.696),
+        )
+        .unwrap();
+        let untyped =
+            MoistAir::from_t_dry_bulb_t_wet_bulb(30.0, 20.0, 14.696 * PA_PER_PSI, UnitSystem::SI)
+                .unwrap();
+        assert_relative_eq!(typed.humidity_ratio(), untyped.humidity_ratio());
+        assert_relative_eq!(
+            typed.t_wet_bulb_typed().unwrap().0,
+            20.0,
+            max_relative = 1.0E-9
+        );
+
+        // the typed accessors do not depend on the unit system of the state
+        let mut ip = untyped;
+        ip.set_unit(UnitSystem::IP);
+        assert_relative_eq!(ip.t_dry_bulb_typed().0, 30.0, max_relative = 1.0E-12);
+        assert_relative_eq!(ip.pressure_typed().0, untyped.pressure());
+        assert_relative_eq!(
+            ip.t_dew_point_typed().unwrap().0,
+            untyped.t_dew_point().unwrap()
+        );
+        assert_relative_eq!(ip.specific_enthalpy_typed().0, untyped.specific_enthalpy());
+        assert_relative_eq!(
+            ip.partial_water_vapor_pressure_typed().0,
+            untyped.partial_water_vapor_pressure(),
+            max_relative = 1.0E-12
+        );
+
+        let enthalpy = MoistAir::from_t_dry_bulb_enthalpy_typed(
+            Kelvin(303.15),
+            untyped.specific_enthalpy_typed(),
+            Pascal(untyped.pressure()),
+        )
+        .unwrap();
+        assert_relative_eq!(
+            enthalpy.humidity_ratio_typed().0,
+            untyped.humidity_ratio(),
+            max_relative = 1.0E-9
+        );
+
+        let vapor = SaturatedWaterVapor::new(68.0, UnitSystem::IP).unwrap();
+        assert_relative_eq!(
+            vapor.saturation_pressure_typed().0,
+            SaturatedWaterVapor::new_typed(Fahrenheit(68.0))
+                .unwrap()
+                .saturation_pressure(),
+            max_relative = 1.0E-12
+        );
+        assert_relative_eq!(
+            atmosphere_pressure(Meter(0.0)).0,
+            101325.0,
+            max_relative = 1.0E-4
+        );
+        assert_relative_eq!(atmosphere_temperature(Meter(0.0)).0, 15.0);
+    }
+
+    #[test]
+    fn test_relative_humidity() {
+        let relative_humidity = RelativeHumidity::new(0.6).unwrap();
+        let typed = MoistAir::from_t_dry_bulb_relative_humidity_typed(
+            Celsius(25.0),
+            relative_humidity,
+            Pascal(101325.0),
+        )
+        .unwrap();
+        let untyped =
+            MoistAir::from_t_dry_bulb_relative_humidity(25.0, 0.6, 101325.0, UnitSystem::SI)
+                .unwrap();
+        assert_relative_eq!(typed.humidity_ratio(), untyped.humidity_ratio());
+        assert_relative_eq!(
+            typed.relative_humidity_typed().unwrap().value(),
+            0.6,
+            max_relative = 1.0E-12
+        );
+
+        // percent, negative and non-finite values are rejected
+        assert!(matches!(
+            RelativeHumidity::new(60.0),
+            Err(PsychroidError::InvalidRelativeHumidity(_))
+        ));
+        assert!(RelativeHumidity::try_from(-0.1).is_err());
+        assert!(matches!(
+            RelativeHumidity::new(f64::NAN),
+            Err(PsychroidError::NonFiniteInput { .. })
+        ));
+        assert_eq!(RelativeHumidity::try_from(1.0).unwrap().value(), 1.0);
+    }
+}
@@ -0,0 +1,213 @@
+use crate::common::{FREEZING_POINT_WATER_SI, UNIVERSAL_GAS_CONSTANT_SI};
+use crate::float::Float;
+
+// Enhancement factor of water vapor in CO2-free air, over water (0 to 100 °C)
+// L. Greenspan, "Functional equations for the enhancement factors for CO2-free moist air",
+// Journal of Research of the National Bureau of Standards 80A (1976) 41-44.
+const A_WATER: [f64; 4] = [3.53624E-04, 2.93228E-05, 2.61474E-07, 8.57538E-09];
+const B_WATER: [f64; 4] = [-1.07588E+01, 6.32529E-02, -2.53591E-04, 6.33784E-07];
+
+// Enhancement factor over ice (-100 to 0 °C), Greenspan (1976)
+const A_ICE: [f64; 4] = [3.64449E-04, 2.93631E-05, 4.88635E-07, 4.36543E-09];
+const B_ICE: [f64; 4] = [-1.07271E+01, 7.61989E-02, -1.74771E-04, 2.46721E-06];
+
+// Second virial coefficient of dry air in m³/mol
+// R.W. Hyland and A. Wexler, "Formulations for the thermodynamic properties of dry air
+// from 173.15 K to 473.15 K, and of saturated moist air from 173.15 K to 372.15 K,
+// at pressures to 5 MPa", ASHRAE Transactions 89 (1983) 520-535.
+const B_AA: [f64; 4] = [0.349568E-04, -0.668772E-02, -0.210141E+01, 0.924746E+02];
+
+// Second virial coefficient of water vapor in dm³/mol, with reduced temperature T/100 K
+// A.H. Harvey and E.W. Lemmon, "Correlation for the second virial coefficient of water",
+// Journal of Physical and Chemical Reference Data 33 (2004) 369-376.
+const A_WW: [f64; 4] = [0.34404, -0.75826, -24.219, -3978.2];
+const B_WW: [f64; 4] = [-0.5, -0.8, -3.35, -8.3];
+
+// Cross second virial coefficient of air and water vapor in cm³/mol, with reduced temperature T/100 K
+// A.H. Harvey and P.H. Huang, "First-principles calculation of the air-water second virial coefficient",
+// International Journal of Thermophysics 28 (2007) 556-565.
+const C_AW: [f64; 3] = [66.5687, -238.834, -176.755];
+const D_AW: [f64; 3] = [-0.237, -1.048, -3.183];
+
+/// Calculates the enhancement factor of water vapor in moist air,
+/// i.e. the ratio of the saturation mole fraction in air to that of pure water vapor
+///
+/// # Arguments
+/// * `t_celsius` - Temperature \[°C\]
+/// * `pressure` - Total pressure \[Pa\]
+/// * `saturation_pressure` - Saturation pressure of pure water vapor \[Pa\]
+///
+/// # Returns
+/// * Enhancement factor \[-\]
+///
+/// # Example
+/// ```
+/// use psychroid::real_gas::enhancement_factor;
+///
+/// let f: f64 = enhancement_factor(20.0, 101325.0, 2339.3);
+/// assert!((f - 1.004).abs() < 0.001);
+/// ```
+pub fn enhancement_factor<F: Float>(t_celsius: F, pressure: F, saturation_pressure: F) -> F {
+    let c = F::of;
+    let (a, b) = match t_celsius >= c(FREEZING_POINT_WATER_SI) {
+        true => (A_WATER, B_WATER),
+        false => (A_ICE, B_ICE),
+    };
+    let polynomial =
+        |k: [f64; 4]| c(k[0]) + t_celsius * (c(k[1]) + t_celsius * (c(k[2]) + t_celsius * c(k[3])));
+    let alpha = polynomial(a);
+    let beta = polynomial(b).exp();
+    (alpha * (c(1.0) - saturation_pressure / pressure)
+        + beta * (pressure / saturation_pressure - c(1.0)))
+    .exp()
+}
+
+/// Calculates the second virial coefficient of dry air
+///
+/// # Arguments
+/// * `t_kelvin` - Temperature \[K\]
+///
+/// # Returns
+/// * Second virial coefficient \[m³/mol\]
+pub fn second_virial_dry_air<F: Float>(t_kelvin: F) -> F {
+    let b = B_AA.map(F::of);
+    b[0] + b[1] / t_kelvin + b[2] / t_kelvin.powi(2) + b[3] / t_kelvin.powi(3)
+}
+
+/// Calculates the second virial coefficient of water vapor
+///
+/// # Arguments
+/// * `t_kelvin` - Temperature \[K\]
+///
+/// # Returns
+/// * Second virial coefficient \[m³/mol\]
+pub fn second_virial_water_vapor<F: Float>(t_kelvin: F) -> F {
+    let c = F::of;
+    let t_reduced = t_kelvin / c(100.0);
+    let b = A_WW
+        .iter()
+        .zip(B_WW.iter())
+        .fold(c(0.0), |sum, (a, b)| sum + c(*a) * t_reduced.powf(c(*b)));
+    b * c(1.0E-03) // dm³/mol to m³/mol
+}
+
+/// Calculates the cross second virial coefficient of air and water vapor
+///
+/// # Arguments
+/// * `t_kelvin` - Temperature \[K\]
+///
+/// # Returns
+/// * Second virial coefficient \[m³/mol\]
+pub fn second_virial_cross<F: Float>(t_kelvin: F) -> F {
+    let c = F::of;
+    let t_reduced = t_kelvin / c(100.0);
+    let b = C_AW
+        .iter()
+        .zip(D_AW.iter())
+        .fold(c(0.0), |sum, (k, d)| sum + c(*k) * t_reduced.powf(c(*d)));
+    b * c(1.0E-06) // cm³/mol to m³/mol
+}
+
+/// Calculates the second virial coefficient of moist air
+///
+/// # Arguments
+/// * `t_kelvin` - Temperature \[K\]
+/// * `x_w` - Mole fraction of water vapor \[-\]
+///
+/// # Returns
+/// * Second virial coefficient \[m³/mol\]
+pub fn second_virial_mixture<F: Float>(t_kelvin: F, x_w: F) -> F {
+    let x_a = F::of(1.0) - x_w;
+    x_a.powi(2) * second_virial_dry_air(t_kelvin)
+        + F::of(2.0) * x_a * x_w * second_virial_cross(t_kelvin)
+        + x_w.powi(2) * second_virial_water_vapor(t_kelvin)
+}
+
+/// Calculates the compressibility factor of moist air, truncated after the second virial coefficient
+///
+/// # Arguments
+/// * `t_kelvin` - Temperature \[K\]
+/// * `pressure` - Total pressure \[Pa\]
+/// * `x_w` - Mole fraction of water vapor \[-\]
+///
+/// # Returns
+/// * Compressibility factor \\(Z = 1 + B_\\mathrm{m} p / (R T)\\) \[-\]
+pub fn compressibility_factor<F: Float>(t_kelvin: F, pressure: F, x_w: F) -> F {
+    let r = F::of(UNIVERSAL_GAS_CONSTANT_SI * 1.0E-03); // J/(mol·K)
+    F::of(1.0) + second_virial_mixture(t_kelvin, x_w) * pressure / (r * t_kelvin)
+}
+
+/// Calculates the molar residual enthalpy of moist air, i.e. the difference
+/// between the real gas and the ideal gas enthalpy at the same temperature
+///
+/// # Arguments
+/// * `t_kelvin` - Temperature \[K\]
+/// * `pressure` - Total pressure \[Pa\]
+/// * `x_w` - Mole fraction of water vapor \[-\]
+///
+/// # Returns
+/// * Residual enthalpy \\(h^\\mathrm{r} = p (B_\\mathrm{m} - T~dB_\\mathrm{m}/dT)\\) \[J/mol\]
+pub fn residual_enthalpy<F: Float>(t_kelvin: F, pressure: F, x_w: F) -> F {
+    let dt = F::of(1.0E-03);
+    let deriv = (second_virial_mixture(t_kelvin + dt, x_w)
+        - second_virial_mixture(t_kelvin - dt, x_w))
+        / (F::of(2.0) * dt);
+    pressure * (second_virial_mixture(t_kelvin, x_w) - t_kelvin * deriv)
+}
+
+/// Calculates the molar residual entropy of moist air, i.e. the difference
+/// between the real gas and the ideal gas entropy at the same temperature and pressure
+///
+/// # Arguments
+/// * `t_kelvin` - Temperature \[K\]
+/// * `pressure` - Total pressure \[Pa\]
+/// * `x_w` - Mole fraction of water vapor \[-\]
+///
+/// # Returns
+/// * Residual entropy \\(s^\\mathrm{r} = -p~dB_\\mathrm{m}/dT\\) \[J/(mol·K)\]
+pub fn residual_entropy<F: Float>(t_kelvin: F, pressure: F, x_w: F) -> F {
+    let dt = F::of(1.0E-03);
+    let deriv = (second_virial_mixture(t_kelvin + dt, x_w)
+        - second_virial_mixture(t_kelvin - dt, x_w))
+        / (F::of(2.0) * dt);
+    -pressure * deriv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_enhancement_factor() {
+        // Reference values from Greenspan (1976) Table 1 and 2
+        assert_relative_eq!(
+            enhancement_factor(20.0, 101325.0, 2339.3),
+            1.0044,
+            max_relative = 5.0E-4
+        );
+        assert_relative_eq!(
+            enhancement_factor(-20.0, 101325.0, 103.24),
+            1.0044,
+            max_relative = 5.0E-4
+        );
+        // enhancement factor increases with pressure
+        assert!(enhancement_factor(20.0, 1.0E+06, 2339.3) > 1.03);
+    }
+
+    #[test]
+    fn test_second_virial() {
+        assert_relative_eq!(second_virial_dry_air(300.0), -7.3E-06, max_relative = 0.02);
+        assert_relative_eq!(
+            second_virial_water_vapor(300.0),
+            -1.17E-03,
+            max_relative = 0.01
+        );
+        assert_relative_eq!(second_virial_cross(300.0), -29.5E-06, max_relative = 0.02);
+        assert_relative_eq!(
+            compressibility_factor(293.15, 101325.0, 0.0),
+            0.9997,
+            max_relative = 1.0E-4
+        );
+    }
+}
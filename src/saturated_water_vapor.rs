@@ -1,7 +1,10 @@
+use crate::common::t_celsius_to_t_kelvin;
 use crate::common::UnitSystem;
-use crate::common::{t_celsius_to_t_kelvin, t_rankine_from_t_fahrenheit};
-use crate::common::{TRIPLE_POINT_WATER_IP, TRIPLE_POINT_WATER_SI};
+use crate::common::{CRITICAL_TEMPERATURE_WATER, TRIPLE_POINT_WATER_SI};
 use crate::error::PsychroidError;
+use crate::float::Float;
+use crate::heat_capacity::{specific_enthalpy_water_vapor, EnthalpyModel};
+use crate::units;
 
 const C1_SI: f64 = -5.6745359E+03;
 const C2_SI: f64 = 6.3925247E+00;
@@ -18,27 +21,53 @@ const C11_SI: f64 = 4.1764768E-05;
 const C12_SI: f64 = -1.4452093E-08;
 const C13_SI: f64 = 6.5459673E+00;
 
-const C1_IP: f64 = -1.0214165E+04;
-const C2_IP: f64 = -4.8932428E+00;
-const C3_IP: f64 = -5.3765794E-03;
-const C4_IP: f64 = 1.9202377E-07;
-const C5_IP: f64 = 3.5575832E-10;
-const C6_IP: f64 = -9.0344688E-14;
-const C7_IP: f64 = 4.1635019E+00;
-
-const C8_IP: f64 = -1.0440397E+04;
-const C9_IP: f64 = -1.1294650E+01;
-const C10_IP: f64 = -2.7022355E-02;
-const C11_IP: f64 = 1.2890360E-05;
-const C12_IP: f64 = -2.4780681E-09;
-const C13_IP: f64 = 6.5459673E+00;
+// Upper limit of the saturation pressure formula of ASHRAE Handbook in K, i.e. 200 °C
+const T_MAX_ASHRAE: f64 = 473.15;
+// Lower limit of the interval in K, i.e. 190 °C, over which the formula of ASHRAE Handbook is blended into IAPWS-IF97
+const T_BLEND_IF97: f64 = 463.15;
+
+// Coefficients of the saturation-pressure equation of IAPWS-IF97 Region 4, Eq. (30)
+const N_IF97: [f64; 10] = [
+    0.11670521452767E+04,
+    -0.72421316703206E+06,
+    -0.17073846940092E+02,
+    0.12020824702470E+05,
+    -0.32325550322333E+07,
+    0.14915108613530E+02,
+    -0.48232657361591E+04,
+    0.40511340542057E+06,
+    -0.23855557567849E+00,
+    0.65017534844798E+03,
+];
+
+/// Reference phase of the saturation pressure below the triple point of water
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SaturationReference {
+    /// Saturation over ice below the triple point and over liquid water above,
+    /// as in ASHRAE Handbook - Fundamentals (2017) Ch. 1 Eq. (5) and (6)
+    #[default]
+    Ice,
+    /// Saturation over liquid water at all temperatures, i.e. over supercooled water below the triple point.
+    /// This is the WMO convention for the relative humidity of meteorological data.
+    Water,
+}
+
+/// Phase of the saturation-pressure correlation that was active at a given temperature
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SaturationBranch {
+    /// Saturation over ice
+    Ice,
+    /// Saturation over liquid water, including supercooled water
+    Water,
+}
 
 /// <script src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 #[derive(Debug)]
 pub struct SaturatedWaterVapor {
     t_dry_bulb: f64,
     unit: UnitSystem,
-    strict: bool,
+    reference: SaturationReference,
+    enthalpy_model: EnthalpyModel,
 }
 
 impl Default for SaturatedWaterVapor {
@@ -46,39 +75,230 @@ impl Default for SaturatedWaterVapor {
         SaturatedWaterVapor {
             t_dry_bulb: 20.0,
             unit: UnitSystem::SI,
-            strict: true,
+            reference: SaturationReference::default(),
+            enthalpy_model: EnthalpyModel::default(),
         }
     }
 }
 
-/// Check if the dry-bulb temperature is within the valid range
-/// - IP: -148°F to 392°F
-/// - SI: -100°C to 200°C
+/// Returns the valid range of the dry-bulb temperature as (lower, upper) bounds
+/// - IP: -148°F to 752°F
+/// - SI: -100°C to 400°C
 ///
-/// The range is based on the valid range of the water vapor saturation pressure approximation formula.
-pub fn check_range_t_dry_bulb(t_dry_bulb: f64, unit: UnitSystem) -> Result<(), PsychroidError> {
+/// The lower limit is based on the valid range of the water vapor saturation pressure formula of ASHRAE Handbook.
+/// Above 200°C (392°F), the saturation pressure is extended by IAPWS-IF97 up to the critical point,
+/// beyond which water vapor cannot be saturated. Both formulas are blended between 190°C (374°F) and 200°C.
+pub fn t_dry_bulb_range(unit: UnitSystem) -> (f64, f64) {
     match unit {
-        UnitSystem::IP => {
-            if !((-148.0..=392.0).contains(&t_dry_bulb)) {
-                return Err(PsychroidError::InvalidTDryBulb { t_dry_bulb, unit });
-            }
-        }
-        UnitSystem::SI => {
-            if !((-100.0..=200.0).contains(&t_dry_bulb)) {
-                return Err(PsychroidError::InvalidTDryBulb { t_dry_bulb, unit });
-            }
-        }
+        UnitSystem::IP => (-148.0, 752.0),
+        UnitSystem::SI => (-100.0, 400.0),
+    }
+}
+
+/// Check if the dry-bulb temperature is within the valid range, see [`t_dry_bulb_range`].
+/// NaN and infinite values are rejected as `NonFiniteInput`.
+pub fn check_range_t_dry_bulb(t_dry_bulb: f64, unit: UnitSystem) -> Result<(), PsychroidError> {
+    if !t_dry_bulb.is_finite() {
+        return Err(PsychroidError::NonFiniteInput {
+            name: "temperature",
+            value: t_dry_bulb,
+        });
+    }
+    let (min, max) = t_dry_bulb_range(unit);
+    if !(min..=max).contains(&t_dry_bulb) {
+        return Err(PsychroidError::InvalidTDryBulb { t_dry_bulb, unit });
     }
     Ok(())
 }
 
+/// Calculates the logarithm of the saturation pressure of water in Pa and its derivative in 1/K,
+/// by the saturation-pressure equation of IAPWS-IF97 Region 4.
+/// Above the critical temperature, the saturation pressure is infinite with a zero derivative.
+///
+/// W. Wagner et al., "The IAPWS Industrial Formulation 1997 for the Thermodynamic Properties of Water and Steam",
+/// Journal of Engineering for Gas Turbines and Power 122 (2000) 150-182.
+fn ln_saturation_pressure_if97<F: Float>(t_kelvin: F) -> (F, F) {
+    if t_kelvin >= F::of(CRITICAL_TEMPERATURE_WATER) {
+        return (F::of(f64::INFINITY), F::of(0.0));
+    }
+    let n = N_IF97.map(F::of);
+    let (two, four) = (F::of(2.0), F::of(4.0));
+    let theta = t_kelvin + n[8] / (t_kelvin - n[9]);
+    let deriv_theta = F::of(1.0) - n[8] / (t_kelvin - n[9]).powi(2);
+    let a = theta.powi(2) + n[0] * theta + n[1];
+    let b = n[2] * theta.powi(2) + n[3] * theta + n[4];
+    let c = n[5] * theta.powi(2) + n[6] * theta + n[7];
+    let (da, db, dc) = (
+        two * theta + n[0],
+        two * n[2] * theta + n[3],
+        two * n[5] * theta + n[6],
+    );
+    let root = (b.powi(2) - four * a * c).sqrt();
+    let d = -b + root;
+    let dd = -db + (b * db - two * (da * c + a * dc)) / root;
+    // p = (2C / D)^4 in MPa
+    let ln_pressure = four * (two * c / d).ln() + F::of(1.0E+06).ln();
+    let deriv_ln_pressure = four * (dc / c - dd / d) * deriv_theta;
+    (ln_pressure, deriv_ln_pressure)
+}
+
+/// Calculates the logarithm of the saturation pressure in Pa and its derivative in 1/K.
+///
+/// Between 190°C and 200°C, the formula of ASHRAE Handbook is blended into IAPWS-IF97 with a smoothstep weight,
+/// so that the saturation pressure and its derivative are continuous and increasing across the switch.
+/// The two formulas differ by about 0.02% at 200°C.
+#[inline]
+fn ln_saturation_pressure_and_deriv_si<F: Float>(t_dry_bulb: F, over_ice: bool) -> (F, F) {
+    let c = F::of;
+    let t_k = t_celsius_to_t_kelvin(t_dry_bulb);
+    if t_k >= c(T_MAX_ASHRAE) {
+        return ln_saturation_pressure_if97(t_k);
+    }
+    if t_k <= c(T_BLEND_IF97) {
+        return ln_saturation_pressure_ashrae(t_k, over_ice);
+    }
+    let width = c(T_MAX_ASHRAE - T_BLEND_IF97);
+    let x = (t_k - c(T_BLEND_IF97)) / width;
+    let weight = x * x * (c(3.0) - c(2.0) * x);
+    let deriv_weight = c(6.0) * x * (c(1.0) - x) / width;
+    let (ln_ashrae, deriv_ashrae) = ln_saturation_pressure_ashrae(t_k, over_ice);
+    let (ln_if97, deriv_if97) = ln_saturation_pressure_if97(t_k);
+    (
+        ln_ashrae + weight * (ln_if97 - ln_ashrae),
+        deriv_ashrae + weight * (deriv_if97 - deriv_ashrae) + deriv_weight * (ln_if97 - ln_ashrae),
+    )
+}
+
+/// Calculates the logarithm of the saturation pressure in Pa and its derivative in 1/K
+/// by the formula of ASHRAE Handbook, sharing the powers of the temperature in K
+#[inline]
+fn ln_saturation_pressure_ashrae<F: Float>(t_k: F, over_ice: bool) -> (F, F) {
+    let c = F::of;
+    let (t_k2, t_k3, ln_t_k) = (t_k * t_k, t_k * t_k * t_k, t_k.ln());
+    match over_ice {
+        true => (
+            c(C1_SI) / t_k
+                + c(C2_SI)
+                + c(C3_SI) * t_k
+                + c(C4_SI) * t_k2
+                + c(C5_SI) * t_k3
+                + c(C6_SI) * t_k2 * t_k2
+                + c(C7_SI) * ln_t_k,
+            -c(C1_SI) / t_k2
+                + c(C3_SI)
+                + c(2.0 * C4_SI) * t_k
+                + c(3.0 * C5_SI) * t_k2
+                + c(4.0 * C6_SI) * t_k3
+                + c(C7_SI) / t_k,
+        ),
+        false => (
+            c(C8_SI) / t_k
+                + c(C9_SI)
+                + c(C10_SI) * t_k
+                + c(C11_SI) * t_k2
+                + c(C12_SI) * t_k3
+                + c(C13_SI) * ln_t_k,
+            -c(C8_SI) / t_k2
+                + c(C10_SI)
+                + c(2.0 * C11_SI) * t_k
+                + c(3.0 * C12_SI) * t_k2
+                + c(C13_SI) / t_k,
+        ),
+    }
+}
+
+/// Returns true if the saturation pressure is evaluated over ice, i.e. below the triple point
+/// with [`SaturationReference::Ice`], for the temperature in °C.
+/// This is the single boundary between ice and liquid water of all the properties.
+#[inline]
+pub(crate) fn is_over_ice<F: Float>(t_dry_bulb: F, reference: SaturationReference) -> bool {
+    reference == SaturationReference::Ice && t_dry_bulb < F::of(TRIPLE_POINT_WATER_SI)
+}
+
+/// Calculates the saturation pressure of water vapor in Pa and its derivative in Pa/K
+/// for the temperature in °C, i.e. the core in SI units of [`saturation_pressure_and_deriv`]
+#[inline]
+pub(crate) fn saturation_pressure_and_deriv_si<F: Float>(
+    t_dry_bulb: F,
+    reference: SaturationReference,
+) -> (F, F) {
+    let over_ice = is_over_ice(t_dry_bulb, reference);
+    let (ln_pws, deriv_ln_pws) = ln_saturation_pressure_and_deriv_si(t_dry_bulb, over_ice);
+    let pws = ln_pws.exp();
+    match deriv_ln_pws == F::of(0.0) {
+        true => (pws, F::of(0.0)), // above the critical temperature
+        false => (pws, pws * deriv_ln_pws),
+    }
+}
+
+/// Calculates the saturation pressure of water vapor in Pa for the temperature in °C,
+/// i.e. the core in SI units of [`saturation_pressure`]
+#[inline]
+pub(crate) fn saturation_pressure_si<F: Float>(t_dry_bulb: F, reference: SaturationReference) -> F {
+    let over_ice = is_over_ice(t_dry_bulb, reference);
+    // the unused derivative is eliminated by inlining
+    ln_saturation_pressure_and_deriv_si(t_dry_bulb, over_ice)
+        .0
+        .exp()
+}
+
+/// Calculates the saturation pressure of water vapor and its derivative with respect to the temperature,
+/// generic over [`Float`], see [`SaturatedWaterVapor::saturation_pressure_and_deriv`].
+///
+/// Unlike [`SaturatedWaterVapor::new`], the temperature is not checked against [`t_dry_bulb_range`].
+///
+/// # Arguments
+/// * `t_dry_bulb` - Temperature in \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
+/// * `unit` - Unit system
+/// * `reference` - Saturation reference below the triple point
+///
+/// # Returns
+/// Saturation pressure and its derivative in \\(\\mathrm{Pa}\\) and \\(\\mathrm{Pa/K}\\) (SI)
+/// or \\(\\mathrm{Psi}\\) and \\(\\mathrm{Psi/^\\circ F}\\) (IP)
+#[inline]
+pub fn saturation_pressure_and_deriv<F: Float>(
+    t_dry_bulb: F,
+    unit: UnitSystem,
+    reference: SaturationReference,
+) -> (F, F) {
+    let (pws, deriv_pws) =
+        saturation_pressure_and_deriv_si(units::t_to_si(t_dry_bulb, unit), reference);
+    (
+        units::pressure_from_si(pws, unit),
+        units::dt_to_si(units::pressure_from_si(deriv_pws, unit), unit),
+    )
+}
+
+/// Calculates the saturation pressure of water vapor, generic over [`Float`],
+/// see [`SaturatedWaterVapor::saturation_pressure`].
+///
+/// Unlike [`SaturatedWaterVapor::new`], the temperature is not checked against [`t_dry_bulb_range`].
+///
+/// # Arguments
+/// * `t_dry_bulb` - Temperature in \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
+/// * `unit` - Unit system
+/// * `reference` - Saturation reference below the triple point
+///
+/// # Returns
+/// Saturation pressure in \\(\\mathrm{Pa}\\) (SI) or \\(\\mathrm{Psi}\\) (IP)
+#[inline]
+pub fn saturation_pressure<F: Float>(
+    t_dry_bulb: F,
+    unit: UnitSystem,
+    reference: SaturationReference,
+) -> F {
+    let pws = saturation_pressure_si(units::t_to_si(t_dry_bulb, unit), reference);
+    units::pressure_from_si(pws, unit)
+}
+
 impl SaturatedWaterVapor {
     pub fn new(t_dry_bulb: f64, unit: UnitSystem) -> Result<Self, PsychroidError> {
         check_range_t_dry_bulb(t_dry_bulb, unit)?;
         Ok(SaturatedWaterVapor {
             t_dry_bulb,
             unit,
-            strict: true,
+            reference: SaturationReference::default(),
+            enthalpy_model: EnthalpyModel::default(),
         })
     }
 
@@ -86,123 +306,112 @@ impl SaturatedWaterVapor {
         SaturatedWaterVapor {
             t_dry_bulb,
             unit,
-            strict: false,
+            reference: SaturationReference::default(),
+            enthalpy_model: EnthalpyModel::default(),
         }
     }
 
-    pub fn saturation_pressure(&self) -> f64 {
-        let ln_pws = match self.unit {
-            UnitSystem::IP => self.ln_saturation_pressure_ip(),
-            UnitSystem::SI => self.ln_saturation_pressure_si(),
-        };
-        f64::exp(ln_pws)
+    /// Sets the reference phase of the saturation pressure below the triple point
+    ///
+    /// # Example
+    /// ```
+    /// use psychroid::{SaturatedWaterVapor, SaturationReference, UnitSystem};
+    ///
+    /// let over_ice = SaturatedWaterVapor::new(-10.0, UnitSystem::SI).unwrap();
+    /// let over_water = SaturatedWaterVapor::new(-10.0, UnitSystem::SI)
+    ///     .unwrap()
+    ///     .with_reference(SaturationReference::Water);
+    /// // supercooled water has a higher saturation pressure than ice
+    /// assert!(over_water.saturation_pressure() > over_ice.saturation_pressure());
+    /// ```
+    pub fn with_reference(mut self, reference: SaturationReference) -> Self {
+        self.reference = reference;
+        self
     }
 
-    pub fn deriv_saturation_pressure(&self) -> f64 {
-        match self.unit {
-            UnitSystem::IP => {
-                f64::exp(self.ln_saturation_pressure_ip()) * self.deriv_ln_saturation_pressure_ip()
-            }
-            UnitSystem::SI => {
-                f64::exp(self.ln_saturation_pressure_si()) * self.deriv_ln_saturation_pressure_si()
-            }
-        }
+    /// Sets the model of the specific enthalpy of water vapor
+    pub fn with_enthalpy_model(mut self, enthalpy_model: EnthalpyModel) -> Self {
+        self.enthalpy_model = enthalpy_model;
+        self
     }
 
-    fn ln_saturation_pressure_ip(&self) -> f64 {
-        let t_r: f64 = t_rankine_from_t_fahrenheit(self.t_dry_bulb);
-        match self.t_dry_bulb < TRIPLE_POINT_WATER_IP {
-            true => {
-                C1_IP / t_r
-                    + C2_IP
-                    + C3_IP * t_r
-                    + C4_IP * t_r.powi(2)
-                    + C5_IP * t_r.powi(3)
-                    + C6_IP * t_r.powi(4)
-                    + C7_IP * t_r.ln()
-            }
-            false => {
-                C8_IP / t_r
-                    + C9_IP
-                    + C10_IP * t_r
-                    + C11_IP * t_r.powi(2)
-                    + C12_IP * t_r.powi(3)
-                    + C13_IP * t_r.ln()
-            }
-        }
+    /// Returns the unit system of saturated water vapor
+    pub fn unit(&self) -> UnitSystem {
+        self.unit
     }
 
-    fn ln_saturation_pressure_si(&self) -> f64 {
-        let t_k: f64 = t_celsius_to_t_kelvin(self.t_dry_bulb);
-        match self.t_dry_bulb < TRIPLE_POINT_WATER_SI {
-            true => {
-                C1_SI / t_k
-                    + C2_SI
-                    + C3_SI * t_k
-                    + C4_SI * t_k.powi(2)
-                    + C5_SI * t_k.powi(3)
-                    + C6_SI * t_k.powi(4)
-                    + C7_SI * t_k.ln()
-            }
-            false => {
-                C8_SI / t_k
-                    + C9_SI
-                    + C10_SI * t_k
-                    + C11_SI * t_k.powi(2)
-                    + C12_SI * t_k.powi(3)
-                    + C13_SI * t_k.ln()
-            }
-        }
+    /// Returns the reference phase of the saturation pressure below the triple point
+    pub fn reference(&self) -> SaturationReference {
+        self.reference
     }
 
-    fn deriv_ln_saturation_pressure_ip(&self) -> f64 {
-        let t_r: f64 = t_rankine_from_t_fahrenheit(self.t_dry_bulb);
-        match self.t_dry_bulb < TRIPLE_POINT_WATER_IP {
-            true => {
-                -C1_IP / t_r.powi(2)
-                    + C3_IP
-                    + 2.0 * C4_IP * t_r
-                    + 3.0 * C5_IP * t_r.powi(2)
-                    + 4.0 * C6_IP * t_r.powi(3)
-                    + C7_IP / t_r
-            }
-            false => {
-                -C8_IP / t_r.powi(2)
-                    + C10_IP
-                    + 2.0 * C11_IP * t_r
-                    + 3.0 * C12_IP * t_r.powi(2)
-                    + C13_IP / t_r
-            }
+    /// Returns the phase over which the saturation pressure is evaluated,
+    /// i.e. ice below the triple point with [`SaturationReference::Ice`] and liquid water otherwise
+    pub fn branch(&self) -> SaturationBranch {
+        match self.is_over_ice() {
+            true => SaturationBranch::Ice,
+            false => SaturationBranch::Water,
         }
     }
 
-    fn deriv_ln_saturation_pressure_si(&self) -> f64 {
-        let t_k: f64 = t_celsius_to_t_kelvin(self.t_dry_bulb);
-        match self.t_dry_bulb < TRIPLE_POINT_WATER_SI {
-            true => {
-                -C1_SI / t_k.powi(2)
-                    + C3_SI
-                    + 2.0 * C4_SI * t_k
-                    + 3.0 * C5_SI * t_k.powi(2)
-                    + 4.0 * C6_SI * t_k.powi(3)
-                    + C7_SI / t_k
-            }
-            false => {
-                -C8_SI / t_k.powi(2)
-                    + C10_SI
-                    + 2.0 * C11_SI * t_k
-                    + 3.0 * C12_SI * t_k.powi(2)
-                    + C13_SI / t_k
-            }
+    /// Returns true if the saturation pressure is evaluated over ice
+    fn is_over_ice(&self) -> bool {
+        is_over_ice(units::t_to_si(self.t_dry_bulb, self.unit), self.reference)
+    }
+
+    /// Returns the saturation pressure of water vapor, in \\(\\mathrm{Pa}\\) (SI) or \\(\\mathrm{Psi}\\) (IP)
+    ///
+    /// ASHRAE Handbook - Fundamentals (2017) Ch. 1 Eq. (5) and (6) up to 190°C (374°F),
+    /// and IAPWS-IF97 Eq. (30) above 200°C (392°F), blended smoothly in between. Above the critical temperature of water,
+    /// the saturation pressure is infinite, i.e. water vapor is never saturated.
+    pub fn saturation_pressure(&self) -> f64 {
+        saturation_pressure(self.t_dry_bulb, self.unit, self.reference)
+    }
+
+    pub fn deriv_saturation_pressure(&self) -> f64 {
+        match self.unit {
+            UnitSystem::IP => self.deriv_saturation_pressure_ip(),
+            UnitSystem::SI => self.deriv_saturation_pressure_si(),
         }
     }
 
+    /// Returns the saturation pressure of water vapor and its derivative with respect to the temperature,
+    /// in \(\mathrm{Pa}\) and \(\mathrm{Pa/K}\) (SI) or \(\mathrm{Psi}\) and \(\mathrm{Psi/^\circ F}\) (IP)
+    ///
+    /// Both are evaluated at once with a single exponential, which is cheaper than calling
+    /// [`SaturatedWaterVapor::saturation_pressure`] and [`SaturatedWaterVapor::deriv_saturation_pressure`]
+    /// in Newton-Raphson iterations. Above the critical temperature, the saturation pressure is infinite
+    /// with a zero derivative.
+    ///
+    /// # Example
+    /// ```
+    /// use psychroid::{SaturatedWaterVapor, UnitSystem};
+    ///
+    /// let water = SaturatedWaterVapor::new(20.0, UnitSystem::SI).unwrap();
+    /// let (pws, deriv_pws) = water.saturation_pressure_and_deriv();
+    /// assert_eq!(pws, water.saturation_pressure());
+    /// assert!((deriv_pws - water.deriv_saturation_pressure()).abs() < 1.0E-09);
+    /// ```
+    pub fn saturation_pressure_and_deriv(&self) -> (f64, f64) {
+        saturation_pressure_and_deriv(self.t_dry_bulb, self.unit, self.reference)
+    }
+
     pub fn deriv_saturation_pressure_ip(&self) -> f64 {
-        f64::exp(self.ln_saturation_pressure_ip()) * self.deriv_ln_saturation_pressure_ip()
+        SaturatedWaterVapor {
+            unit: UnitSystem::IP,
+            ..*self
+        }
+        .saturation_pressure_and_deriv()
+        .1
     }
 
     pub fn deriv_saturation_pressure_si(&self) -> f64 {
-        f64::exp(self.ln_saturation_pressure_si()) * self.deriv_ln_saturation_pressure_si()
+        SaturatedWaterVapor {
+            unit: UnitSystem::SI,
+            ..*self
+        }
+        .saturation_pressure_and_deriv()
+        .1
     }
 
     /// Calculates the specific enthalpy of saturated water vapor
@@ -223,17 +432,18 @@ impl SaturatedWaterVapor {
     /// - \\(t\\) is the saturation temperature in \\(^\\circ \\mathrm{C}\\) or \\(^\\circ \\mathrm{F}\\)
     ///
     /// Reference: ASHRAE Fundamentals Handbook (2017) Chapter 1
+    ///
+    /// For [`EnthalpyModel::VariableHeatCapacity`], the temperature-dependent heat capacity is integrated instead,
+    /// see [`crate::heat_capacity::specific_enthalpy_water_vapor`].
     pub fn specific_enthalpy(&self) -> f64 {
-        match self.unit {
-            UnitSystem::SI => 2501.0 + 1.860 * self.t_dry_bulb,
-            UnitSystem::IP => 1061.0 + 0.444 * self.t_dry_bulb,
-        }
+        specific_enthalpy_water_vapor(self.t_dry_bulb, self.unit, self.enthalpy_model)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::PA_PER_PSI;
     use approx::assert_abs_diff_eq;
     use approx::assert_relative_eq;
 
@@ -290,4 +500,171 @@ mod tests {
         let wsat = SaturatedWaterVapor::new(300.0, UnitSystem::IP).unwrap();
         assert_relative_eq!(wsat.saturation_pressure(), 67.0206, max_relative = 0.0003);
     }
+
+    #[test]
+    fn test_saturation_reference() {
+        // saturation pressure over supercooled water, Hyland and Wexler (1983)
+        let wsat = SaturatedWaterVapor::new(-10.0, UnitSystem::SI)
+            .unwrap()
+            .with_reference(SaturationReference::Water);
+        assert_relative_eq!(wsat.saturation_pressure(), 286.5, max_relative = 0.001);
+        let wsat = SaturatedWaterVapor::new(-20.0, UnitSystem::SI)
+            .unwrap()
+            .with_reference(SaturationReference::Water);
+        assert_relative_eq!(wsat.saturation_pressure(), 125.6, max_relative = 0.001);
+
+        // the reference has no effect above the triple point
+        let over_ice = SaturatedWaterVapor::new(25.0, UnitSystem::SI).unwrap();
+        let over_water = SaturatedWaterVapor::new(25.0, UnitSystem::SI)
+            .unwrap()
+            .with_reference(SaturationReference::Water);
+        assert_eq!(
+            over_ice.saturation_pressure(),
+            over_water.saturation_pressure()
+        );
+        assert_eq!(over_ice.reference(), SaturationReference::Ice);
+
+        // both correlations agree at the triple point
+        let over_water = SaturatedWaterVapor::new(32.018, UnitSystem::IP)
+            .unwrap()
+            .with_reference(SaturationReference::Water);
+        let over_ice = SaturatedWaterVapor::new(32.017, UnitSystem::IP).unwrap();
+        assert_relative_eq!(
+            over_water.saturation_pressure(),
+            over_ice.saturation_pressure(),
+            max_relative = 0.0003
+        );
+    }
+
+    #[test]
+    fn test_saturation_pressure_extended() {
+        // verification values of IAPWS-IF97 Table 35
+        let wsat = SaturatedWaterVapor::new(500.0 - 273.15, UnitSystem::SI).unwrap();
+        assert_relative_eq!(
+            wsat.saturation_pressure(),
+            2.63889776E+06,
+            max_relative = 1.0E-8
+        );
+        let wsat = SaturatedWaterVapor::new(600.0 - 273.15, UnitSystem::SI).unwrap();
+        assert_relative_eq!(
+            wsat.saturation_pressure(),
+            12.3443146E+06,
+            max_relative = 1.0E-8
+        );
+        // unchanged formula of ASHRAE Handbook below the blending interval
+        let wsat = SaturatedWaterVapor::new(190.0, UnitSystem::SI).unwrap();
+        assert_relative_eq!(wsat.saturation_pressure(), 1255200.0, max_relative = 0.0003);
+        // derivative is consistent with the saturation pressure
+        let dt = 1.0E-04;
+        let deriv = (SaturatedWaterVapor::new(300.0 + dt, UnitSystem::SI)
+            .unwrap()
+            .saturation_pressure()
+            - SaturatedWaterVapor::new(300.0 - dt, UnitSystem::SI)
+                .unwrap()
+                .saturation_pressure())
+            / (2.0 * dt);
+        let wsat = SaturatedWaterVapor::new(300.0, UnitSystem::SI).unwrap();
+        assert_relative_eq!(
+            wsat.deriv_saturation_pressure(),
+            deriv,
+            max_relative = 1.0E-6
+        );
+        // IP units
+        let wsat = SaturatedWaterVapor::new(572.0, UnitSystem::IP).unwrap();
+        assert_relative_eq!(
+            wsat.saturation_pressure(),
+            SaturatedWaterVapor::new(300.0, UnitSystem::SI)
+                .unwrap()
+                .saturation_pressure()
+                / PA_PER_PSI,
+            max_relative = 1.0E-9
+        );
+        // no saturation above the critical point
+        let wsat = SaturatedWaterVapor::new(380.0, UnitSystem::SI).unwrap();
+        assert_eq!(wsat.saturation_pressure(), f64::INFINITY);
+        assert_eq!(wsat.deriv_saturation_pressure(), 0.0);
+        assert!(SaturatedWaterVapor::new(400.1, UnitSystem::SI).is_err());
+    }
+
+    #[test]
+    fn test_saturation_pressure_blending() {
+        // continuous in value and derivative at both ends of the blending interval
+        let dt = 1.0E-09;
+        for t in [190.0, 200.0] {
+            let (below, deriv_below) =
+                saturation_pressure_and_deriv(t - dt, UnitSystem::SI, SaturationReference::Ice);
+            let (above, deriv_above) =
+                saturation_pressure_and_deriv(t + dt, UnitSystem::SI, SaturationReference::Ice);
+            assert_relative_eq!(below, above, max_relative = 1.0E-9);
+            assert_relative_eq!(deriv_below, deriv_above, max_relative = 1.0E-6);
+        }
+        // strictly increasing across the blending interval
+        let mut previous = 0.0;
+        for k in 0..=40_000 {
+            let t = 180.0 + k as f64 * 1.0E-03;
+            let (pws, deriv_pws) =
+                saturation_pressure_and_deriv(t, UnitSystem::SI, SaturationReference::Ice);
+            assert!(pws > previous, "not increasing at {t}°C");
+            assert!(deriv_pws > 0.0);
+            previous = pws;
+        }
+        // derivative is consistent with the saturation pressure within the interval
+        let dt = 1.0E-04;
+        let saturation_pressure_si =
+            |t| saturation_pressure(t, UnitSystem::SI, SaturationReference::Ice);
+        let deriv =
+            (saturation_pressure_si(195.0 + dt) - saturation_pressure_si(195.0 - dt)) / (2.0 * dt);
+        let (_, deriv_pws) =
+            saturation_pressure_and_deriv(195.0, UnitSystem::SI, SaturationReference::Ice);
+        assert_relative_eq!(deriv_pws, deriv, max_relative = 1.0E-6);
+    }
+
+    #[test]
+    fn test_saturation_pressure_and_deriv() {
+        // over ice, over water, over supercooled water and by IAPWS-IF97, in both unit systems
+        let cases = [
+            (-40.0, UnitSystem::SI, SaturationReference::Ice),
+            (-40.0, UnitSystem::SI, SaturationReference::Water),
+            (25.0, UnitSystem::SI, SaturationReference::Ice),
+            (250.0, UnitSystem::SI, SaturationReference::Ice),
+            (10.0, UnitSystem::IP, SaturationReference::Ice),
+            (10.0, UnitSystem::IP, SaturationReference::Water),
+            (77.0, UnitSystem::IP, SaturationReference::Ice),
+            (500.0, UnitSystem::IP, SaturationReference::Ice),
+        ];
+        let dt = 1.0E-04;
+        for (t, unit, reference) in cases {
+            let saturation_pressure = |t| {
+                SaturatedWaterVapor::new(t, unit)
+                    .unwrap()
+                    .with_reference(reference)
+                    .saturation_pressure()
+            };
+            let wsat = SaturatedWaterVapor::new(t, unit)
+                .unwrap()
+                .with_reference(reference);
+            let (pws, deriv_pws) = wsat.saturation_pressure_and_deriv();
+            assert_eq!(pws, wsat.saturation_pressure());
+            assert_eq!(deriv_pws, wsat.deriv_saturation_pressure());
+            let deriv = (saturation_pressure(t + dt) - saturation_pressure(t - dt)) / (2.0 * dt);
+            assert_relative_eq!(deriv_pws, deriv, max_relative = 1.0E-6);
+        }
+    }
+
+    #[test]
+    fn test_single_precision() {
+        for (unit, min, max) in [
+            (UnitSystem::SI, -100.0, 370.0),
+            (UnitSystem::IP, -148.0, 698.0),
+        ] {
+            for k in 0..=100 {
+                let t = min + (max - min) * k as f64 / 100.0;
+                for reference in [SaturationReference::Ice, SaturationReference::Water] {
+                    let pws = saturation_pressure(t, unit, reference);
+                    let pws_f32 = saturation_pressure(t as f32, unit, reference) as f64;
+                    assert!((pws_f32 / pws - 1.0).abs() < 2.0E-05);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,599 @@
+//! Sensitivity of the properties of moist air to the inputs which define the state.
+//!
+//! The partial derivatives are exact up to rounding, without finite differences:
+//! the closed-form relations of moist air are evaluated in forward-mode [`Dual`] numbers
+//! seeded with the dry-bulb temperature, the humidity ratio and the pressure.
+//! The wet-bulb and dew point temperatures, which are solved iteratively, are differentiated
+//! through their defining equations by the implicit function theorem, i.e. for the residual
+//! \\(F(x, t, W, p) = 0\\) of the solution \\(x\\),
+//! $$
+//! \\frac{\\partial x}{\\partial t} = -\\frac{\\partial F / \\partial t}{\\partial F / \\partial x}.
+//! $$
+//!
+//! The derivatives with respect to the dry-bulb temperature, humidity ratio and pressure are then
+//! transformed into those with respect to any pair of inputs, e.g. dry-bulb and wet-bulb temperatures,
+//! by the inverse of the Jacobian of the pair. See [`MoistAir::gradient`].
+//!
+//! At the ice-water switch of the saturation (the freezing point with [`SaturationReference::Ice`])
+//! and at the saturation curve of fog, the properties are not differentiable, and the one-sided
+//! derivative of the branch selected for the state is returned.
+//!
+//! [`SaturationReference::Ice`]: crate::SaturationReference::Ice
+//!
+//! # Example
+//! ```
+//! use psychroid::batch::Output;
+//! use psychroid::{MoistAir, UnitSystem};
+//!
+//! let air = MoistAir::from_t_dry_bulb_t_wet_bulb(30.0, 20.0, 101325.0, UnitSystem::SI).unwrap();
+//! // relative humidity with respect to dry-bulb and wet-bulb temperatures and pressure
+//! let gradient = air
+//!     .gradient(Output::RelativeHumidity, Output::TDryBulb, Output::TWetBulb)
+//!     .unwrap();
+//! assert!(gradient.first < 0.0 && gradient.second > 0.0);
+//! // with respect to dry-bulb temperature and humidity ratio instead
+//! let gradient = air
+//!     .gradient(Output::RelativeHumidity, Output::TDryBulb, Output::HumidityRatio)
+//!     .unwrap();
+//! assert!((gradient.first + 0.0228).abs() < 1.0E-04); // about -2.3 %/K
+//! ```
+use crate::batch::Output;
+use crate::common::MASS_RATIO_WATER_DRY_AIR;
+use crate::error::PsychroidError;
+use crate::float::Float;
+use crate::moist_air::{humidity_ratio_from_t_dew_point, humidity_ratio_from_t_wet_bulb};
+use crate::moist_air::{relative_humidity_from_humidity_ratio, saturation_humidity_ratio};
+use crate::moist_air::{specific_enthalpy_from_humidity_ratio, specific_heat_from_humidity_ratio};
+use crate::moist_air::{specific_volume_from_humidity_ratio, MoistAir};
+use crate::units;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Dual number of forward-mode automatic differentiation,
+/// i.e. a value with its partial derivatives with respect to `N` variables
+///
+/// Comparisons are made on the values, so that the branches of the correlations,
+/// e.g. over ice or over water, are selected as in `f64`.
+///
+/// # Example
+/// ```
+/// use psychroid::saturated_water_vapor::saturation_pressure;
+/// use psychroid::sensitivity::Dual;
+/// use psychroid::{SaturationReference, UnitSystem};
+///
+/// let t = Dual::<1>::variable(20.0, 0);
+/// let pws = saturation_pressure(t, UnitSystem::SI, SaturationReference::Ice);
+/// assert!((pws.value() - 2338.8).abs() < 0.1);
+/// assert!((pws.derivs()[0] - 144.9).abs() < 0.1); // Pa/K
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Dual<const N: usize> {
+    value: f64,
+    derivs: [f64; N],
+}
+
+impl<const N: usize> Dual<N> {
+    /// Creates a constant, of which the derivatives are 0
+    pub fn constant(value: f64) -> Self {
+        Dual {
+            value,
+            derivs: [0.0; N],
+        }
+    }
+
+    /// Creates the variable of the given index, of which the derivative is 1 with respect to itself
+    ///
+    /// # Panics
+    /// If the index is not less than `N`
+    pub fn variable(value: f64, index: usize) -> Self {
+        let mut derivs = [0.0; N];
+        derivs[index] = 1.0;
+        Dual { value, derivs }
+    }
+
+    /// Returns the value
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Returns the partial derivatives with respect to the variables
+    pub fn derivs(&self) -> [f64; N] {
+        self.derivs
+    }
+
+    /// Applies a function of the given value and derivative by the chain rule
+    fn chain(self, value: f64, deriv: f64) -> Self {
+        Dual {
+            value,
+            derivs: self.derivs.map(|d| d * deriv),
+        }
+    }
+}
+
+impl<const N: usize> PartialEq for Dual<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<const N: usize> PartialOrd for Dual<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<const N: usize> Add for Dual<N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Dual {
+            value: self.value + other.value,
+            derivs: core::array::from_fn(|i| self.derivs[i] + other.derivs[i]),
+        }
+    }
+}
+
+impl<const N: usize> Sub for Dual<N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Dual {
+            value: self.value - other.value,
+            derivs: core::array::from_fn(|i| self.derivs[i] - other.derivs[i]),
+        }
+    }
+}
+
+impl<const N: usize> Mul for Dual<N> {
+    type Output = Self;
+
+    // the product rule
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, other: Self) -> Self {
+        Dual {
+            value: self.value * other.value,
+            derivs: core::array::from_fn(|i| {
+                self.derivs[i] * other.value + self.value * other.derivs[i]
+            }),
+        }
+    }
+}
+
+impl<const N: usize> Div for Dual<N> {
+    type Output = Self;
+
+    // the quotient rule
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        let value = self.value / other.value;
+        Dual {
+            value,
+            derivs: core::array::from_fn(|i| {
+                (self.derivs[i] - value * other.derivs[i]) / other.value
+            }),
+        }
+    }
+}
+
+impl<const N: usize> Neg for Dual<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Dual {
+            value: -self.value,
+            derivs: self.derivs.map(|d| -d),
+        }
+    }
+}
+
+impl<const N: usize> Float for Dual<N> {
+    fn of(value: f64) -> Self {
+        Dual::constant(value)
+    }
+
+    fn to_f64(self) -> f64 {
+        self.value
+    }
+
+    fn exp(self) -> Self {
+        let value = self.value.exp();
+        self.chain(value, value)
+    }
+
+    fn ln(self) -> Self {
+        self.chain(self.value.ln(), 1.0 / self.value)
+    }
+
+    fn sqrt(self) -> Self {
+        let value = self.value.sqrt();
+        self.chain(value, 0.5 / value)
+    }
+
+    fn powi(self, n: i32) -> Self {
+        self.chain(self.value.powi(n), n as f64 * self.value.powi(n - 1))
+    }
+
+    fn powf(self, n: Self) -> Self {
+        let value = self.value.powf(n.value);
+        let deriv = n.value * self.value.powf(n.value - 1.0);
+        Dual {
+            value,
+            derivs: core::array::from_fn(|i| match n.derivs[i] == 0.0 {
+                // the logarithm is not evaluated for a constant exponent, e.g. of a negative base
+                true => self.derivs[i] * deriv,
+                false => self.derivs[i] * deriv + n.derivs[i] * value * self.value.ln(),
+            }),
+        }
+    }
+
+    fn abs(self) -> Self {
+        match self.value < 0.0 {
+            true => -self,
+            false => self,
+        }
+    }
+
+    fn min(self, other: Self) -> Self {
+        match other.value < self.value || self.value.is_nan() {
+            true => other,
+            false => self,
+        }
+    }
+
+    fn max(self, other: Self) -> Self {
+        match other.value > self.value || self.value.is_nan() {
+            true => other,
+            false => self,
+        }
+    }
+}
+
+/// Partial derivatives of a property of moist air with respect to the two inputs and the pressure,
+/// in the unit system of the state, see [`MoistAir::gradient`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gradient {
+    /// With respect to the first input, at constant second input and pressure
+    pub first: f64,
+    /// With respect to the second input, at constant first input and pressure
+    pub second: f64,
+    /// With respect to the pressure, at constant first and second inputs
+    pub pressure: f64,
+}
+
+/// Returns the property with its partial derivatives with respect to
+/// dry-bulb temperature, humidity ratio and pressure, in this order
+fn partials(moist_air: &MoistAir, output: Output) -> Result<Dual<3>, PsychroidError> {
+    // the errors are those of the evaluation of the property, e.g. out of range
+    let value = output.evaluate(moist_air)?;
+    // the property is not defined at the state, e.g. the dew point of dry air
+    if value.is_nan() {
+        return Ok(Dual {
+            value,
+            derivs: [f64::NAN; 3],
+        });
+    }
+    let (unit, options) = (moist_air.unit(), &moist_air.options());
+    let t_dry_bulb = Dual::variable(moist_air.t_dry_bulb(), 0);
+    let humidity_ratio = Dual::variable(moist_air.humidity_ratio(), 1);
+    let pressure = Dual::variable(moist_air.pressure(), 2);
+    // the correlations are evaluated in SI units, and the derivatives follow the conversions
+    let t_dry_bulb_si = units::t_to_si(t_dry_bulb, unit);
+    let pressure_si = units::pressure_to_si(pressure, unit);
+    let saturation_humidity_ratio = saturation_humidity_ratio(t_dry_bulb_si, pressure_si, options);
+    let vapor_humidity_ratio = humidity_ratio.min(saturation_humidity_ratio);
+    let specific_volume = || {
+        specific_volume_from_humidity_ratio(
+            t_dry_bulb_si,
+            vapor_humidity_ratio,
+            pressure_si,
+            options,
+        )
+    };
+    let partials_si = match output {
+        Output::TDryBulb => return Ok(t_dry_bulb),
+        Output::HumidityRatio => return Ok(humidity_ratio),
+        Output::RelativeHumidity => relative_humidity_from_humidity_ratio(
+            t_dry_bulb_si,
+            vapor_humidity_ratio,
+            pressure_si,
+            options,
+        )
+        .map_err(units::error_from_si(unit, options))?,
+        Output::TWetBulb => {
+            // the residual does not depend on the humidity ratio, of which the slot holds the wet-bulb temperature
+            let t_wet_bulb = units::t_to_si(Dual::variable(value, 1), unit);
+            let residual =
+                humidity_ratio_from_t_wet_bulb(t_dry_bulb_si, t_wet_bulb, pressure_si, options)
+                    .map_err(units::error_from_si(unit, options))?;
+            return Ok(implicit(value, vapor_humidity_ratio, residual, 1));
+        }
+        Output::TDewPoint => {
+            // the residual does not depend on the dry-bulb temperature, of which the slot holds the dew point
+            let t_dew_point = units::t_to_si(Dual::variable(value, 0), unit);
+            let residual = humidity_ratio_from_t_dew_point(t_dew_point, pressure_si, options)
+                .map_err(units::error_from_si(unit, options))?;
+            return Ok(implicit(value, vapor_humidity_ratio, residual, 0));
+        }
+        Output::SpecificEnthalpy => specific_enthalpy_from_humidity_ratio(
+            t_dry_bulb_si,
+            humidity_ratio,
+            pressure_si,
+            options,
+        ),
+        Output::SpecificVolume => specific_volume(),
+        Output::Density => (Dual::constant(1.0) + humidity_ratio) / specific_volume(),
+        Output::PartialWaterVaporPressure => {
+            pressure_si * vapor_humidity_ratio
+                / (Dual::constant(MASS_RATIO_WATER_DRY_AIR) + vapor_humidity_ratio)
+        }
+        Output::DegreeOfSaturation => humidity_ratio / saturation_humidity_ratio,
+        Output::SpecificHeat => {
+            specific_heat_from_humidity_ratio(t_dry_bulb_si, vapor_humidity_ratio, options)
+        }
+    };
+    Ok(units::output_from_si(output, partials_si, unit, options))
+}
+
+/// Differentiates the solution `root` of `residual(x) = target` by the implicit function theorem,
+/// where the variable of the given slot of `residual` is the solution `x` in place of the input
+fn implicit(root: f64, target: Dual<3>, residual: Dual<3>, slot: usize) -> Dual<3> {
+    let deriv_root = residual.derivs[slot];
+    Dual {
+        value: root,
+        derivs: core::array::from_fn(|i| match i == slot {
+            true => target.derivs[i] / deriv_root,
+            false => (target.derivs[i] - residual.derivs[i]) / deriv_root,
+        }),
+    }
+}
+
+/// Returns the gradient of the property with respect to the pair of inputs and the pressure,
+/// from the Jacobian of the inputs with respect to dry-bulb temperature, humidity ratio and pressure
+pub(crate) fn gradient(
+    moist_air: &MoistAir,
+    output: Output,
+    first: Output,
+    second: Output,
+) -> Result<Gradient, PsychroidError> {
+    let [x_t, x_w, x_p] = partials(moist_air, first)?.derivs;
+    let [y_t, y_w, y_p] = partials(moist_air, second)?.derivs;
+    let [z_t, z_w, z_p] = partials(moist_air, output)?.derivs;
+    let determinant = x_t * y_w - x_w * y_t;
+    // relative to the terms, so that the cancellation of dependent inputs is detected
+    if determinant.abs() <= f64::EPSILON * (x_t * y_w).abs().max((x_w * y_t).abs()) {
+        return Err(PsychroidError::DependentInputs(first, second));
+    }
+    let deriv_first = (z_t * y_w - z_w * y_t) / determinant;
+    let deriv_second = (z_w * x_t - z_t * x_w) / determinant;
+    Ok(Gradient {
+        first: deriv_first,
+        second: deriv_second,
+        pressure: z_p - deriv_first * x_p - deriv_second * y_p,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::UnitSystem;
+    use crate::heat_capacity::EnthalpyModel;
+    use crate::moist_air::{GasModel, ModelOptions, Property, PropertyPair};
+    use approx::assert_relative_eq;
+
+    /// Central difference of the property with respect to each input of the pair and the pressure
+    fn central_difference(
+        output: Output,
+        pair: PropertyPair,
+        pressure: f64,
+        unit: UnitSystem,
+        options: ModelOptions,
+        steps: [f64; 3],
+    ) -> [f64; 3] {
+        let evaluate = |pair: PropertyPair, pressure: f64| {
+            let air = MoistAir::from_pair_with_options(pair, pressure, unit, options).unwrap();
+            output.evaluate(&air).unwrap()
+        };
+        let shift = |property: Property, step: f64| property.with_value(property.value() + step);
+        let PropertyPair(first, second) = pair;
+        let [h_first, h_second, h_pressure] = steps;
+        [
+            (evaluate(PropertyPair(shift(first, h_first), second), pressure)
+                - evaluate(PropertyPair(shift(first, -h_first), second), pressure))
+                / (2.0 * h_first),
+            (evaluate(PropertyPair(first, shift(second, h_second)), pressure)
+                - evaluate(PropertyPair(first, shift(second, -h_second)), pressure))
+                / (2.0 * h_second),
+            (evaluate(pair, pressure + h_pressure) - evaluate(pair, pressure - h_pressure))
+                / (2.0 * h_pressure),
+        ]
+    }
+
+    fn assert_gradient(
+        output: Output,
+        pair: PropertyPair,
+        pressure: f64,
+        unit: UnitSystem,
+        options: ModelOptions,
+        steps: [f64; 3],
+    ) {
+        let air = MoistAir::from_pair_with_options(pair, pressure, unit, options).unwrap();
+        let gradient = air.gradient(output, pair.0.into(), pair.1.into()).unwrap();
+        let expected = central_difference(output, pair, pressure, unit, options, steps);
+        let actual = [gradient.first, gradient.second, gradient.pressure];
+        for (actual, expected) in actual.iter().zip(expected.iter()) {
+            assert_relative_eq!(
+                *actual,
+                *expected,
+                max_relative = 1.0E-05,
+                epsilon = 1.0E-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_gradient_si() {
+        let unit = UnitSystem::SI;
+        let steps = [1.0E-04, 1.0E-06, 1.0];
+        let options = ModelOptions::default();
+        // dRH/dT at constant humidity ratio
+        let pair = PropertyPair(Property::TDryBulb(30.0), Property::HumidityRatio(0.01));
+        assert_gradient(
+            Output::RelativeHumidity,
+            pair,
+            101325.0,
+            unit,
+            options,
+            steps,
+        );
+        // d(t_dew)/dW
+        assert_gradient(Output::TDewPoint, pair, 101325.0, unit, options, steps);
+        // dW/dT_wb
+        let pair = PropertyPair(Property::TDryBulb(30.0), Property::TWetBulb(20.0));
+        assert_gradient(
+            Output::HumidityRatio,
+            pair,
+            101325.0,
+            unit,
+            options,
+            [1.0E-04; 3],
+        );
+        // dh/dp at constant dry-bulb temperature and relative humidity
+        let pair = PropertyPair(Property::TDryBulb(30.0), Property::RelativeHumidity(0.5));
+        assert_gradient(
+            Output::SpecificEnthalpy,
+            pair,
+            101325.0,
+            unit,
+            options,
+            steps,
+        );
+        // below freezing, and the other properties
+        let pair = PropertyPair(Property::TDryBulb(-10.0), Property::RelativeHumidity(0.5));
+        for output in [Output::TWetBulb, Output::SpecificVolume, Output::Density] {
+            assert_gradient(
+                output,
+                pair,
+                101325.0,
+                unit,
+                options,
+                [1.0E-04, 1.0E-05, 1.0],
+            );
+        }
+        // the iterative solutions as inputs
+        let pair = PropertyPair(Property::SpecificEnthalpy(50.0), Property::TDewPoint(10.0));
+        for output in [Output::TDryBulb, Output::RelativeHumidity, Output::TWetBulb] {
+            assert_gradient(
+                output,
+                pair,
+                101325.0,
+                unit,
+                options,
+                [1.0E-03, 1.0E-04, 1.0],
+            );
+        }
+    }
+
+    #[test]
+    fn test_gradient_ip() {
+        let unit = UnitSystem::IP;
+        let pair = PropertyPair(Property::TDryBulb(86.0), Property::TWetBulb(68.0));
+        for output in [
+            Output::HumidityRatio,
+            Output::RelativeHumidity,
+            Output::TDewPoint,
+            Output::SpecificEnthalpy,
+            Output::PartialWaterVaporPressure,
+            Output::DegreeOfSaturation,
+            Output::SpecificHeat,
+        ] {
+            assert_gradient(
+                output,
+                pair,
+                14.696,
+                unit,
+                ModelOptions::default(),
+                [1.0E-04; 3],
+            );
+        }
+    }
+
+    #[test]
+    fn test_gradient_options() {
+        let options = ModelOptions {
+            gas_model: GasModel::Real,
+            enthalpy_model: EnthalpyModel::VariableHeatCapacity,
+            ..Default::default()
+        };
+        let pair = PropertyPair(Property::TDryBulb(40.0), Property::TWetBulb(35.0));
+        for output in [
+            Output::HumidityRatio,
+            Output::SpecificEnthalpy,
+            Output::SpecificVolume,
+            Output::SpecificHeat,
+        ] {
+            assert_gradient(
+                output,
+                pair,
+                1.0E+06,
+                UnitSystem::SI,
+                options,
+                [1.0E-03, 1.0E-03, 10.0],
+            );
+        }
+    }
+
+    #[test]
+    fn test_dependent_inputs() {
+        let unit = UnitSystem::SI;
+        let air = MoistAir::from_t_dry_bulb_humidity_ratio(25.0, 0.01, 101325.0, unit).unwrap();
+        let result = air.gradient(
+            Output::SpecificEnthalpy,
+            Output::HumidityRatio,
+            Output::TDewPoint,
+        );
+        assert_eq!(
+            result,
+            Err(PsychroidError::DependentInputs(
+                Output::HumidityRatio,
+                Output::TDewPoint
+            ))
+        );
+        // the relative humidity of fog is 1 regardless of the humidity ratio
+        let fog = MoistAir::from_t_dry_bulb_humidity_ratio(10.0, 0.01, 101325.0, unit).unwrap();
+        let result = fog.gradient(
+            Output::SpecificEnthalpy,
+            Output::TDryBulb,
+            Output::RelativeHumidity,
+        );
+        assert!(matches!(result, Err(PsychroidError::DependentInputs(..))));
+        // the enthalpy of fog includes that of the condensed water
+        let gradient = fog
+            .gradient(
+                Output::SpecificEnthalpy,
+                Output::TDryBulb,
+                Output::HumidityRatio,
+            )
+            .unwrap();
+        assert_relative_eq!(gradient.second, 4.186 * 10.0, max_relative = 1.0E-12);
+        // the dew point of dry air is not defined
+        let dry = MoistAir::from_t_dry_bulb_humidity_ratio(25.0, 0.0, 101325.0, unit).unwrap();
+        assert!(dry
+            .gradient(Output::TDewPoint, Output::TDryBulb, Output::HumidityRatio)
+            .unwrap()
+            .second
+            .is_nan());
+    }
+
+    #[test]
+    fn test_dual() {
+        let x = Dual::<2>::variable(2.0, 0);
+        let y = Dual::<2>::variable(3.0, 1);
+        let z = x * y / (x + y) - x.powf(y) + x.powi(2).ln() * y.exp().sqrt();
+        let deriv_x = 3.0 * 3.0 / 25.0 - 3.0 * 4.0 + 1.0 * 3.0_f64.exp().sqrt();
+        let deriv_y =
+            2.0 * 2.0 / 25.0 - 8.0 * 2.0_f64.ln() + 4.0_f64.ln() * 0.5 * 3.0_f64.exp().sqrt();
+        assert_relative_eq!(z.derivs()[0], deriv_x, max_relative = 1.0E-14);
+        assert_relative_eq!(z.derivs()[1], deriv_y, max_relative = 1.0E-14);
+        // comparisons and the selection by min are made on the values
+        assert!(x < y && x.min(y) == x && (-x).abs().derivs() == x.derivs());
+    }
+}
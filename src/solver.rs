@@ -0,0 +1,414 @@
+//! Root finding for the inverse calculations, which reports the method, the iteration count
+//! and the last residual in [`PsychroidError::SolverFailure`] if the search fails.
+use crate::error::{PsychroidError, SolverFailureReason, SolverMethod};
+use crate::saturated_water_vapor::SaturationBranch;
+
+/// Options of the iterative solutions, e.g. of the wet-bulb and dew point temperatures
+///
+/// # Example
+/// ```
+/// use psychroid::{ModelOptions, MoistAir, SolverOptions, UnitSystem};
+///
+/// let options = ModelOptions {
+///     solver: SolverOptions {
+///         tolerance: 1.0E-12,
+///         residual_tolerance: 0.0,
+///         max_iter: 200,
+///     },
+///     ..Default::default()
+/// };
+/// let air = MoistAir::from_t_dry_bulb_humidity_ratio_with_options(30.0, 0.01, 101325.0, UnitSystem::SI, options)
+///     .unwrap();
+/// assert!(air.t_wet_bulb().unwrap() < 30.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolverOptions {
+    /// Absolute tolerance of the solution, in the SI unit of the unknown, e.g. \\(^\\circ \\mathrm{C}\\)
+    /// for temperatures, since the iterations run in SI units for both unit systems
+    pub tolerance: f64,
+    /// Absolute tolerance of the residual, in the SI unit of the equation, e.g. kJ/kg for an enthalpy balance.
+    /// Zero stops the iteration on the tolerance of the solution alone, as the residuals of the equations differ in units.
+    pub residual_tolerance: f64,
+    /// Maximum number of iterations
+    pub max_iter: usize,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        SolverOptions {
+            tolerance: 1.0E-09,
+            residual_tolerance: 0.0,
+            max_iter: 100,
+        }
+    }
+}
+
+/// Diagnostics of an iterative solution, e.g. of the wet-bulb or dew point temperature
+///
+/// The report is returned on request by [`MoistAir::t_wet_bulb_with_report`](crate::MoistAir::t_wet_bulb_with_report)
+/// and [`MoistAir::t_dew_point_with_report`](crate::MoistAir::t_dew_point_with_report),
+/// e.g. to flag marginal points in batch runs.
+///
+/// # Example
+/// ```
+/// use psychroid::{MoistAir, SaturationBranch, UnitSystem};
+///
+/// let air = MoistAir::from_t_dry_bulb_relative_humidity(2.0, 0.3, 101325.0, UnitSystem::SI).unwrap();
+/// let (t_wet_bulb, report) = air.t_wet_bulb_with_report().unwrap();
+/// assert_eq!(t_wet_bulb, air.t_wet_bulb().unwrap());
+/// assert!(report.initial_guess < 2.0);
+/// assert!(report.iterations > 0 && report.residual.abs() < 1.0E-6);
+/// assert_eq!(report.branch, SaturationBranch::Ice);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolverReport {
+    /// Method of the iterative solution
+    pub method: SolverMethod,
+    /// Number of iterations, zero if the initial guess is the solution, e.g. for saturated moist air
+    pub iterations: usize,
+    /// Residual of the equation at the solution, in SI units
+    pub residual: f64,
+    /// Initial guess of the iteration, in the unit system of the state
+    pub initial_guess: f64,
+    /// Phase of the saturation correlation active at the solution
+    pub branch: SaturationBranch,
+}
+
+/// Root of an equation with the iteration count, the residual and the initial guess
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Solution {
+    pub root: f64,
+    pub iterations: usize,
+    pub residual: f64,
+    pub initial_guess: f64,
+}
+
+impl Solution {
+    /// Solution given by the initial guess without iteration
+    pub(crate) fn initial(initial_guess: f64, residual: f64) -> Self {
+        Solution {
+            root: initial_guess,
+            iterations: 0,
+            residual,
+            initial_guess,
+        }
+    }
+
+    /// Returns the report of the safeguarded Newton-Raphson method, with the active saturation branch
+    pub(crate) fn report(&self, branch: SaturationBranch) -> SolverReport {
+        SolverReport {
+            method: SolverMethod::NewtonRaphson,
+            iterations: self.iterations,
+            residual: self.residual,
+            initial_guess: self.initial_guess,
+            branch,
+        }
+    }
+}
+
+/// Finds the root in the bracket by Newton-Raphson method from `start`, safeguarded by bisection,
+/// where `fd` returns the value and the derivative of the function at once.
+///
+/// Newton steps are taken as long as they stay in the bracket and reduce the step by half.
+/// Otherwise, the ends of the bracket are evaluated, and the bracket is narrowed at every iteration
+/// by bisection, so that the iteration converges whenever the function changes its sign in the bracket,
+/// even where the derivative is discontinuous (e.g. at the switch between ice and water).
+/// The ends are not evaluated at all if Newton-Raphson method converges from a good initial guess.
+/// The residual may be infinite, but not NaN, in the bracket.
+///
+/// # Errors
+/// * `SolverFailure` with `NoBracketing` if the function does not change its sign between the ends of the bracket
+/// * `SolverFailure` with `InvalidResidual` if the residual is NaN
+/// * `SolverFailure` with `NoConvergence` if the iteration limit is reached
+pub(crate) fn find_root_bracketed(
+    start: f64,
+    bracket: (f64, f64),
+    fd: impl Fn(f64) -> (f64, f64),
+    options: &SolverOptions,
+) -> Result<Solution, PsychroidError> {
+    let failure = |reason, iterations, residual| PsychroidError::SolverFailure {
+        method: SolverMethod::NewtonRaphson,
+        reason,
+        iterations,
+        residual,
+    };
+    let solution = |root, iterations, residual| Solution {
+        root,
+        iterations,
+        residual,
+        initial_guess: start,
+    };
+    let (a, b) = bracket;
+    let (min, max) = (a.min(b), a.max(b));
+    let mut x = match (min..=max).contains(&start) {
+        true => start,
+        false => 0.5 * (a + b),
+    };
+    let (mut fx, mut dfx) = fd(x);
+    // (low, high) with f(low) < 0 < f(high), once the ends are evaluated
+    let mut signed: Option<(f64, f64)> = None;
+    let mut step = max - min;
+    let mut last_step = step;
+    for iterations in 1..=options.max_iter {
+        if fx.is_nan() {
+            return Err(failure(
+                SolverFailureReason::InvalidResidual,
+                iterations,
+                fx,
+            ));
+        }
+        if fx.abs() <= options.residual_tolerance {
+            return Ok(solution(x, iterations - 1, fx));
+        }
+        let newton = x - fx / dfx;
+        let (lower, upper) = match signed {
+            Some((low, high)) => (low.min(high), low.max(high)),
+            None => (min, max),
+        };
+        let converging = fx.is_finite()
+            && (lower..=upper).contains(&newton)
+            && (2.0 * fx).abs() <= (last_step * dfx).abs();
+        last_step = step;
+        if converging {
+            step = newton - x;
+            x = newton;
+        } else {
+            let (low, high) = match signed {
+                Some(signed) => signed,
+                None => {
+                    let (fa, fb) = (fd(a).0, fd(b).0);
+                    if fa.abs() <= options.residual_tolerance {
+                        return Ok(solution(a, iterations, fa));
+                    }
+                    if fb.abs() <= options.residual_tolerance {
+                        return Ok(solution(b, iterations, fb));
+                    }
+                    if fa.is_nan() || fb.is_nan() || fa * fb > 0.0 {
+                        let residual = match fa.abs() < fb.abs() {
+                            true => fa,
+                            false => fb,
+                        };
+                        return Err(failure(
+                            SolverFailureReason::NoBracketing,
+                            iterations,
+                            residual,
+                        ));
+                    }
+                    // the current point narrows the bracket
+                    let (low, high) = match fa < 0.0 {
+                        true => (a, b),
+                        false => (b, a),
+                    };
+                    match fx < 0.0 {
+                        true => (x, high),
+                        false => (low, x),
+                    }
+                }
+            };
+            step = 0.5 * (high - low);
+            x = low + step;
+            signed = Some((low, high));
+        }
+        (fx, dfx) = fd(x);
+        if step.abs() < options.tolerance {
+            return Ok(solution(x, iterations, fx));
+        }
+        if let Some((low, high)) = signed.as_mut() {
+            if fx < 0.0 {
+                *low = x;
+            } else if fx > 0.0 {
+                *high = x;
+            }
+        }
+    }
+    Err(failure(
+        SolverFailureReason::NoConvergence,
+        options.max_iter,
+        fx,
+    ))
+}
+
+/// Returns the pair of points and values, ordered so that the value of the first one is the larger in magnitude
+fn arrange(a: f64, fa: f64, b: f64, fb: f64) -> (f64, f64, f64, f64) {
+    match fa.abs() > fb.abs() {
+        true => (a, fa, b, fb),
+        false => (b, fb, a, fa),
+    }
+}
+
+/// Finds the root of `f` by Brent's method between `a` and `b`
+///
+/// R. P. Brent, "Algorithms for Minimization without Derivatives", Prentice-Hall (1973) Ch. 4.
+/// The iteration stops when the residual is within the residual tolerance,
+/// or the bracket is smaller than the tolerance of the solution, and returns the best estimate.
+pub(crate) fn brent(
+    a: f64,
+    b: f64,
+    f: impl Fn(f64) -> f64,
+    options: &SolverOptions,
+) -> Result<f64, PsychroidError> {
+    let failure = |reason, iterations, residual| PsychroidError::SolverFailure {
+        method: SolverMethod::Brent,
+        reason,
+        iterations,
+        residual,
+    };
+    let (tolerance, residual_tolerance) = (options.tolerance, options.residual_tolerance);
+    // b is the best estimate, and a is the contrapoint
+    let (mut a, mut fa, mut b, mut fb) = arrange(a, f(a), b, f(b));
+    if fa * fb > 0.0 {
+        return Err(failure(SolverFailureReason::NoBracketing, 0, fb));
+    }
+    let (mut c, mut fc, mut d) = (a, fa, a);
+    let mut bisected = true;
+    let mut iterations = 0;
+    loop {
+        // b is closer to the root than a in the residual
+        if fb.abs() <= residual_tolerance || (a - b).abs() < tolerance {
+            return Ok(b);
+        }
+        // inverse quadratic interpolation, or the secant method
+        let mut s = match fa != fc && fb != fc {
+            true => {
+                a * fb * fc / ((fa - fb) * (fa - fc))
+                    + b * fa * fc / ((fb - fa) * (fb - fc))
+                    + c * fa * fb / ((fc - fa) * (fc - fb))
+            }
+            false => b - fb * (b - a) / (fb - fa),
+        };
+        let outside = (s - b) * (s - (3.0 * a + b) / 4.0) > 0.0;
+        let slow = match bisected {
+            true => (s - b).abs() >= (b - c).abs() / 2.0 || (b - c).abs() < tolerance,
+            false => (s - b).abs() >= (c - d).abs() / 2.0 || (c - d).abs() < tolerance,
+        };
+        bisected = outside || slow;
+        if bisected {
+            s = 0.5 * (a + b);
+        }
+        let fs = f(s);
+        (d, c, fc) = (c, b, fb);
+        (a, fa, b, fb) = match fa * fs < 0.0 {
+            true => arrange(a, fa, s, fs),
+            false => arrange(s, fs, b, fb),
+        };
+        iterations += 1;
+        if iterations >= options.max_iter {
+            return Err(failure(SolverFailureReason::NoConvergence, iterations, fs));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_find_root_bracketed() {
+        let options = SolverOptions::default();
+        // Newton-Raphson method alone cycles between 0 and 1
+        let f = |x: f64| x.powi(3) - 2.0 * x + 2.0;
+        let d = |x: f64| 3.0 * x.powi(2) - 2.0;
+        let root = find_root_bracketed(0.0, (-3.0, 3.0), |x| (f(x), d(x)), &options)
+            .unwrap()
+            .root;
+        assert_relative_eq!(f(root), 0.0, epsilon = 1.0E-8);
+
+        // and diverges from far away for a function flattening out
+        let root = find_root_bracketed(
+            10.0,
+            (-20.0, 30.0),
+            |x| (x.atan(), 1.0 / (1.0 + x * x)),
+            &options,
+        )
+        .unwrap()
+        .root;
+        assert!(root.abs() < 1.0E-9);
+
+        // discontinuous derivative
+        let f = |x: f64| match x < 1.0 {
+            true => 0.1 * (x - 1.0) + 0.5,
+            false => 10.0 * (x - 1.0) + 0.5,
+        };
+        let d = |x: f64| if x < 1.0 { 0.1 } else { 10.0 };
+        let solution = find_root_bracketed(2.0, (-10.0, 10.0), |x| (f(x), d(x)), &options).unwrap();
+        assert_relative_eq!(solution.root, -4.0, max_relative = 1.0E-9);
+        assert_eq!(solution.initial_guess, 2.0);
+        assert!(solution.iterations > 0);
+        assert_eq!(solution.residual, f(solution.root));
+
+        // no sign change
+        let error = find_root_bracketed(0.5, (0.0, 1.0), |x| (x + 1.0, 1.0), &options).unwrap_err();
+        assert_eq!(
+            error.failure_reason(),
+            Some(SolverFailureReason::NoBracketing)
+        );
+        assert_eq!(error.iterations(), Some(1));
+        assert_eq!(error.residual(), Some(1.0));
+
+        // iteration limit
+        let options = SolverOptions {
+            tolerance: 0.0,
+            residual_tolerance: 0.0,
+            max_iter: 5,
+        };
+        let error =
+            find_root_bracketed(1.0, (0.0, 2.0), |x| (x * x - 2.0, 2.0 * x), &options).unwrap_err();
+        assert_eq!(
+            error.failure_reason(),
+            Some(SolverFailureReason::NoConvergence)
+        );
+        assert_eq!(error.iterations(), Some(5));
+
+        // the ends are not evaluated if Newton-Raphson method converges
+        let options = SolverOptions::default();
+        let fd = |x: f64| match x < 0.0 {
+            true => (f64::NAN, f64::NAN),
+            false => (x * x - 2.0, 2.0 * x),
+        };
+        let solution = find_root_bracketed(1.5, (-1.0, 2.0), fd, &options).unwrap();
+        assert_relative_eq!(solution.root, 2.0f64.sqrt(), max_relative = 1.0E-12);
+        assert!(solution.iterations <= 5);
+    }
+
+    #[test]
+    fn test_solver_failure() {
+        let options = SolverOptions {
+            tolerance: 1.0E-12,
+            residual_tolerance: 0.0,
+            max_iter: 20,
+        };
+        let root = brent(0.0, 2.0, |x| x * x - 2.0, &options).unwrap();
+        assert_relative_eq!(root, 2.0f64.sqrt(), max_relative = 1.0E-12);
+
+        // the best estimate lies within the tolerance of the solution
+        let f = |x: f64| x.powi(3) - 2.0;
+        for tolerance in [1.0E-02, 1.0E-04, 1.0E-06] {
+            let options = SolverOptions {
+                tolerance,
+                ..options
+            };
+            let root = brent(0.0, 2.0, f, &options).unwrap();
+            assert!((root - 2.0f64.cbrt()).abs() < tolerance);
+        }
+
+        // or the residual within the residual tolerance
+        let options = SolverOptions {
+            tolerance: 0.0,
+            residual_tolerance: 1.0E-03,
+            max_iter: 20,
+        };
+        let root = brent(0.0, 2.0, f, &options).unwrap();
+        assert!(f(root).abs() <= 1.0E-03);
+
+        let error = brent(0.0, 1.0, |x| x + 1.0, &options).unwrap_err();
+        assert!(matches!(
+            error,
+            PsychroidError::SolverFailure {
+                method: SolverMethod::Brent,
+                reason: SolverFailureReason::NoBracketing,
+                ..
+            }
+        ));
+    }
+}
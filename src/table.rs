@@ -0,0 +1,816 @@
+//! Precomputed interpolation tables for embedded controllers, which cannot afford iterative
+//! calculations at each scan.
+//!
+//! A [`SaturationTable`] tabulates the saturation pressure of water vapor over a grid of temperature,
+//! and a [`PropertyTable`] tabulates a property of moist air, e.g. the wet-bulb or dew point temperature,
+//! over a grid of dry-bulb temperature and relative humidity at a fixed total pressure.
+//! The tables are built from [`SaturatedWaterVapor`] and [`MoistAir`](crate::MoistAir),
+//! and evaluated by linear (bilinear) interpolation without any iteration.
+//!
+//! Each table carries an error estimate of the interpolation, obtained at the build by comparing
+//! the interpolated values with the exact values at the midpoints of the grid intervals.
+//! For a function convex or concave within an interval, the error at the midpoint is at least
+//! half of the maximum error in the interval, and the estimate is twice the largest error at the midpoints
+//! plus the rounding error of the stored values.
+//! The estimate is not a guaranteed bound: the convexity within each interval is assumed rather than checked,
+//! a kink or a jump inside an interval, e.g. of the wet-bulb temperature at the switch between ice and water,
+//! may be underestimated when it lies close to a node, and in two dimensions the errors are sampled
+//! along the grid lines and through the cell centers only.
+//! Where a guaranteed error is required, compare the table with the exact values on a finer grid.
+//!
+//! The tables are stored as a compact binary, i.e. a short header followed by the values
+//! as little-endian `f32`, which can be flashed onto devices as it is. A table is read back
+//! from the binary without copying, e.g. from a `static` array generated by `to_rust_source`.
+//!
+//! # Example
+//! ```
+//! use psychroid::batch::Output;
+//! use psychroid::table::{Grid, PropertyTable};
+//! use psychroid::{ModelOptions, MoistAir, UnitSystem};
+//!
+//! let unit = UnitSystem::SI;
+//! let table = PropertyTable::new(
+//!     Output::TWetBulb,
+//!     Grid::new(10.0, 40.0, 61).unwrap(),
+//!     Grid::new(0.1, 1.0, 19).unwrap(),
+//!     101325.0,
+//!     unit,
+//!     &ModelOptions::default(),
+//! )
+//! .unwrap();
+//! assert!(table.error_estimate() < 0.05);
+//!
+//! // e.g. flashed onto a device and read back
+//! let bytes = table.as_bytes().to_vec();
+//! let table = PropertyTable::from_bytes(&bytes).unwrap();
+//! let t_wet_bulb = table.evaluate(25.3, 0.42).unwrap();
+//!
+//! let exact = MoistAir::from_t_dry_bulb_relative_humidity(25.3, 0.42, 101325.0, unit)
+//!     .unwrap()
+//!     .t_wet_bulb()
+//!     .unwrap();
+//! assert!((t_wet_bulb - exact).abs() <= table.error_estimate());
+//! ```
+use crate::batch::{self, Output};
+use crate::common::UnitSystem;
+use crate::error::PsychroidError;
+use crate::moist_air::ModelOptions;
+use crate::saturated_water_vapor::{SaturatedWaterVapor, SaturationReference};
+use crate::validation::{check_finite, check_pressure};
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+#[cfg(not(feature = "std"))]
+use num_traits::Float as _;
+
+/// Leading bytes of the binary of a table
+const MAGIC: &[u8; 4] = b"PSYT";
+/// Version of the binary layout
+const VERSION: u8 = 1;
+/// Kind of the table in the binary
+const KIND_SATURATION: u8 = 0;
+const KIND_PROPERTY: u8 = 1;
+/// Sizes of the fixed part of the header and of each grid in the header
+const PREAMBLE_SIZE: usize = 24;
+const GRID_SIZE: usize = 20;
+/// Properties in the order of their codes in the binary
+const OUTPUTS: [Output; 11] = [
+    Output::TDryBulb,
+    Output::HumidityRatio,
+    Output::RelativeHumidity,
+    Output::TWetBulb,
+    Output::TDewPoint,
+    Output::SpecificEnthalpy,
+    Output::SpecificVolume,
+    Output::Density,
+    Output::PartialWaterVaporPressure,
+    Output::DegreeOfSaturation,
+    Output::SpecificHeat,
+];
+
+/// Uniform grid of an input variable, from `min` to `max` with `count` points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Grid {
+    min: f64,
+    max: f64,
+    count: usize,
+}
+
+impl Grid {
+    /// Creates a uniform grid
+    ///
+    /// # Arguments
+    /// * `min` - First point of the grid
+    /// * `max` - Last point of the grid, greater than `min`
+    /// * `count` - Number of points, at least 2
+    ///
+    /// # Errors
+    /// If the bounds are not finite, `max` is not greater than `min`,
+    /// or the number of points is out of range
+    pub fn new(min: f64, max: f64, count: usize) -> Result<Self, PsychroidError> {
+        check_finite("grid minimum", min)?;
+        check_finite("grid maximum", max)?;
+        if max <= min {
+            return Err(PsychroidError::OutOfRange {
+                quantity: "grid maximum",
+                value: max,
+                min,
+                max: f64::INFINITY,
+                unit: None,
+            });
+        }
+        if !(2..=u32::MAX as usize).contains(&count) {
+            return Err(PsychroidError::OutOfRange {
+                quantity: "grid count",
+                value: count as f64,
+                min: 2.0,
+                max: u32::MAX as f64,
+                unit: None,
+            });
+        }
+        Ok(Grid { min, max, count })
+    }
+
+    /// Returns the first point of the grid
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// Returns the last point of the grid
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// Returns the number of points
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the interval between the points
+    pub fn step(&self) -> f64 {
+        (self.max - self.min) / (self.count - 1) as f64
+    }
+
+    /// Returns the points of the grid with the midpoints of the intervals in between,
+    /// i.e. the grid refined by a factor of 2
+    fn refined_points(&self) -> Vec<f64> {
+        let n = 2 * self.count - 1;
+        let half_step = 0.5 * self.step();
+        (0..n)
+            .map(|k| match k == n - 1 {
+                true => self.max,
+                false => self.min + k as f64 * half_step,
+            })
+            .collect()
+    }
+
+    /// Returns the index of the interval containing `x` and the fraction of `x` within it,
+    /// or an error if `x` is outside the grid
+    fn locate(
+        &self,
+        quantity: &'static str,
+        x: f64,
+        unit: Option<UnitSystem>,
+    ) -> Result<(usize, f64), PsychroidError> {
+        check_finite(quantity, x)?;
+        if !(self.min..=self.max).contains(&x) {
+            return Err(PsychroidError::OutOfRange {
+                quantity,
+                value: x,
+                min: self.min,
+                max: self.max,
+                unit,
+            });
+        }
+        let s = (x - self.min) / self.step();
+        let i = (s as usize).min(self.count - 2);
+        Ok((i, s - i as f64))
+    }
+}
+
+/// Binary of a table over `D` grids and its decoded header
+#[derive(Debug, Clone, PartialEq)]
+struct Table<'a, const D: usize> {
+    unit: UnitSystem,
+    code: u8,
+    pressure: f64,
+    error_estimate: f64,
+    grids: [Grid; D],
+    bytes: Cow<'a, [u8]>,
+}
+
+impl<'a, const D: usize> Table<'a, D> {
+    /// Size of the header in bytes
+    const HEADER_SIZE: usize = PREAMBLE_SIZE + D * GRID_SIZE;
+
+    /// Encodes the header and the values
+    ///
+    /// The layout is, in little-endian: the magic `PSYT`, the version, the kind, the unit system (0: SI, 1: IP),
+    /// the code of the reference or the property (`u8` each), the pressure and the error estimate (`f64`),
+    /// the minimum and maximum (`f64`) and the count (`u32`) of each grid, and the values (`f32`)
+    /// with the last grid varying fastest.
+    fn encode(
+        kind: u8,
+        unit: UnitSystem,
+        code: u8,
+        pressure: f64,
+        error_estimate: f64,
+        grids: [Grid; D],
+        values: &[f32],
+    ) -> Self {
+        let mut bytes = Vec::with_capacity(Self::HEADER_SIZE + 4 * values.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&[VERSION, kind, unit_code(unit), code]);
+        bytes.extend_from_slice(&pressure.to_le_bytes());
+        bytes.extend_from_slice(&error_estimate.to_le_bytes());
+        for grid in &grids {
+            bytes.extend_from_slice(&grid.min.to_le_bytes());
+            bytes.extend_from_slice(&grid.max.to_le_bytes());
+            bytes.extend_from_slice(&(grid.count as u32).to_le_bytes());
+        }
+        for value in values {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        Table {
+            unit,
+            code,
+            pressure,
+            error_estimate,
+            grids,
+            bytes: Cow::Owned(bytes),
+        }
+    }
+
+    /// Decodes the header of the binary, which is borrowed for the values
+    fn decode(bytes: &'a [u8], kind: u8) -> Result<Self, PsychroidError> {
+        if bytes.len() < Self::HEADER_SIZE || &bytes[..4] != MAGIC {
+            return Err(PsychroidError::InvalidTableData("missing header"));
+        }
+        if bytes[4] != VERSION {
+            return Err(PsychroidError::InvalidTableData("unsupported version"));
+        }
+        if bytes[5] != kind {
+            return Err(PsychroidError::InvalidTableData("wrong kind of table"));
+        }
+        let unit = match bytes[6] {
+            0 => UnitSystem::SI,
+            1 => UnitSystem::IP,
+            _ => return Err(PsychroidError::InvalidTableData("unknown unit system")),
+        };
+        let f64_at =
+            |offset: usize| f64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+        let mut grids = [Grid {
+            min: 0.0,
+            max: 0.0,
+            count: 0,
+        }; D];
+        let mut count = 1;
+        for (k, grid) in grids.iter_mut().enumerate() {
+            let offset = PREAMBLE_SIZE + k * GRID_SIZE;
+            let n = u32::from_le_bytes(bytes[offset + 16..offset + 20].try_into().unwrap());
+            *grid = Grid::new(f64_at(offset), f64_at(offset + 8), n as usize)
+                .map_err(|_| PsychroidError::InvalidTableData("invalid grid"))?;
+            count *= grid.count;
+        }
+        if bytes.len() != Self::HEADER_SIZE + 4 * count {
+            return Err(PsychroidError::InvalidTableData(
+                "length does not match the grids",
+            ));
+        }
+        Ok(Table {
+            unit,
+            code: bytes[7],
+            pressure: f64_at(8),
+            error_estimate: f64_at(16),
+            grids,
+            bytes: Cow::Borrowed(bytes),
+        })
+    }
+
+    /// Returns the stored value at the flat index
+    #[inline]
+    fn value(&self, index: usize) -> f64 {
+        let offset = Self::HEADER_SIZE + 4 * index;
+        f32::from_le_bytes(self.bytes[offset..offset + 4].try_into().unwrap()) as f64
+    }
+
+    /// Formats the binary as the source of a Rust `static` array
+    fn to_rust_source(&self, name: &str, description: &str) -> String {
+        let mut source = format!(
+            "/// {}\npub static {}: [u8; {}] = [",
+            description,
+            name,
+            self.bytes.len()
+        );
+        for (k, byte) in self.bytes.iter().enumerate() {
+            if k % 16 == 0 {
+                source.push_str("\n   ");
+            }
+            // OK to unwrap because writing to a String does not fail
+            write!(source, " 0x{:02x},", byte).unwrap();
+        }
+        source.push_str("\n];\n");
+        source
+    }
+}
+
+/// Returns the code of the unit system in the binary
+fn unit_code(unit: UnitSystem) -> u8 {
+    match unit {
+        UnitSystem::SI => 0,
+        UnitSystem::IP => 1,
+    }
+}
+
+/// Returns the exact values on the nodes of a refined grid, stored and interpolated over the coarse grid,
+/// and the error estimate from the midpoints, see the module documentation.
+/// `exact` is indexed by the refined grid, with the second dimension (of length `m`, 1 in one dimension) varying fastest.
+fn tabulate(exact: &[f64], n: usize, m: usize) -> Result<(Vec<f32>, f64), PsychroidError> {
+    if exact.iter().any(|v| !v.is_finite()) {
+        return Err(PsychroidError::InvalidTableData(
+            "non-finite value on the grid",
+        ));
+    }
+    let rm = 2 * m - 1;
+    let at = |i: usize, j: usize| exact[i * rm + j];
+    let mut values = Vec::with_capacity(n * m);
+    let mut rounding_error: f64 = 0.0;
+    for i in 0..n {
+        for j in 0..m {
+            let v = at(2 * i, 2 * j);
+            values.push(v as f32);
+            rounding_error = rounding_error.max((v as f32 as f64 - v).abs());
+        }
+    }
+    // errors of the linear interpolation along the first grid, on the grid lines of the second one
+    let mut error_first: f64 = 0.0;
+    for i in 0..n - 1 {
+        for j in 0..m {
+            let mid = 0.5 * (at(2 * i, 2 * j) + at(2 * i + 2, 2 * j));
+            error_first = error_first.max((mid - at(2 * i + 1, 2 * j)).abs());
+        }
+    }
+    // errors along the second grid, on the grid lines of the first one and through the cell centers
+    let mut error_second: f64 = 0.0;
+    for i in 0..2 * n - 1 {
+        for j in 0..m - 1 {
+            let mid = 0.5 * (at(i, 2 * j) + at(i, 2 * j + 2));
+            error_second = error_second.max((mid - at(i, 2 * j + 1)).abs());
+        }
+    }
+    Ok((
+        values,
+        2.0 * error_first + 2.0 * error_second + rounding_error,
+    ))
+}
+
+/// Linear interpolation between `a` and `b`
+#[inline]
+fn lerp(a: f64, b: f64, fraction: f64) -> f64 {
+    a + (b - a) * fraction
+}
+
+/// Table of the saturation pressure of water vapor over a grid of temperature
+///
+/// The natural logarithm of the saturation pressure is tabulated and interpolated linearly,
+/// so that the error is nearly uniform in relative terms over the exponential growth of the pressure.
+///
+/// # Example
+/// ```
+/// use psychroid::table::{Grid, SaturationTable};
+/// use psychroid::{SaturatedWaterVapor, SaturationReference, UnitSystem};
+///
+/// let unit = UnitSystem::SI;
+/// let grid = Grid::new(-40.0, 60.0, 201).unwrap();
+/// let table = SaturationTable::new(grid, unit, SaturationReference::Ice).unwrap();
+/// let pws = table.saturation_pressure(23.7).unwrap();
+///
+/// let exact = SaturatedWaterVapor::new(23.7, unit).unwrap().saturation_pressure();
+/// assert!((pws / exact - 1.0).abs() <= table.error_estimate());
+/// assert!(table.error_estimate() < 2.0E-4);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaturationTable<'a> {
+    table: Table<'a, 1>,
+}
+
+impl SaturationTable<'static> {
+    /// Builds the table from [`SaturatedWaterVapor`]
+    ///
+    /// # Arguments
+    /// * `t_dry_bulb` - Grid of temperature in \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
+    /// * `unit` - Unit system
+    /// * `reference` - Saturation reference below the triple point
+    ///
+    /// # Errors
+    /// If the grid is out of the valid range of temperature, or reaches the critical point
+    pub fn new(
+        t_dry_bulb: Grid,
+        unit: UnitSystem,
+        reference: SaturationReference,
+    ) -> Result<Self, PsychroidError> {
+        let exact = t_dry_bulb
+            .refined_points()
+            .into_iter()
+            .map(|t| {
+                let pws = SaturatedWaterVapor::new(t, unit)?
+                    .with_reference(reference)
+                    .saturation_pressure();
+                Ok(pws.ln())
+            })
+            .collect::<Result<Vec<f64>, PsychroidError>>()?;
+        let (values, error_estimate) = tabulate(&exact, t_dry_bulb.count, 1)?;
+        let code = match reference {
+            SaturationReference::Ice => 0,
+            SaturationReference::Water => 1,
+        };
+        Ok(SaturationTable {
+            // the error of the logarithm is converted to the relative error of the pressure
+            table: Table::encode(
+                KIND_SATURATION,
+                unit,
+                code,
+                0.0,
+                error_estimate.exp_m1(),
+                [t_dry_bulb],
+                &values,
+            ),
+        })
+    }
+}
+
+impl<'a> SaturationTable<'a> {
+    /// Reads the table from its binary without copying
+    ///
+    /// # Errors
+    /// If the binary is not a valid saturation table
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, PsychroidError> {
+        let table = Table::decode(bytes, KIND_SATURATION)?;
+        if table.code > 1 {
+            return Err(PsychroidError::InvalidTableData(
+                "unknown saturation reference",
+            ));
+        }
+        Ok(SaturationTable { table })
+    }
+
+    /// Returns the saturation pressure interpolated from the table
+    ///
+    /// # Arguments
+    /// * `t_dry_bulb` - Temperature in \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
+    ///
+    /// # Returns
+    /// Saturation pressure in \\(\\mathrm{Pa}\\) (SI) or \\(\\mathrm{Psi}\\) (IP)
+    ///
+    /// # Errors
+    /// If the temperature is outside the grid
+    pub fn saturation_pressure(&self, t_dry_bulb: f64) -> Result<f64, PsychroidError> {
+        let (i, fraction) =
+            self.table.grids[0].locate("dry-bulb temperature", t_dry_bulb, Some(self.unit()))?;
+        Ok(lerp(self.table.value(i), self.table.value(i + 1), fraction).exp())
+    }
+
+    /// Returns the estimate of the maximum relative error of the interpolated saturation pressure,
+    /// which is not a guaranteed bound, see the [module documentation](self)
+    pub fn error_estimate(&self) -> f64 {
+        self.table.error_estimate
+    }
+
+    /// Returns the grid of temperature
+    pub fn t_dry_bulb_grid(&self) -> Grid {
+        self.table.grids[0]
+    }
+
+    /// Returns the unit system
+    pub fn unit(&self) -> UnitSystem {
+        self.table.unit
+    }
+
+    /// Returns the saturation reference below the triple point
+    pub fn reference(&self) -> SaturationReference {
+        match self.table.code {
+            0 => SaturationReference::Ice,
+            _ => SaturationReference::Water,
+        }
+    }
+
+    /// Returns the binary of the table
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.table.bytes
+    }
+
+    /// Returns the source of a Rust `static` array of the binary, to be read by [`SaturationTable::from_bytes`]
+    ///
+    /// # Arguments
+    /// * `name` - Name of the array, e.g. `SATURATION_TABLE`
+    pub fn to_rust_source(&self, name: &str) -> String {
+        let grid = self.t_dry_bulb_grid();
+        let description = format!(
+            "Saturation pressure table over {} to {} ({} points), {:?}, {:?}",
+            grid.min,
+            grid.max,
+            grid.count,
+            self.unit(),
+            self.reference()
+        );
+        self.table.to_rust_source(name, &description)
+    }
+}
+
+/// Table of a property of moist air over a grid of dry-bulb temperature and relative humidity at a fixed pressure
+///
+/// The property is interpolated bilinearly. The error estimate is given in the unit of the property.
+/// Note that the wet-bulb temperature jumps by a few tenths of a degree where it crosses the triple point,
+/// between the equations of a bulb covered with ice and with water (ASHRAE Handbook - Fundamentals (2017) SI Ch. 1 Eq. (33) and (35)),
+/// which dominates the error estimate of a grid across it.
+/// See the [module documentation](self) for an example.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyTable<'a> {
+    table: Table<'a, 2>,
+}
+
+impl PropertyTable<'static> {
+    /// Builds the table from [`MoistAir`](crate::MoistAir) by [`batch::from_t_dry_bulb_relative_humidity`]
+    ///
+    /// # Arguments
+    /// * `output` - Property to be tabulated, e.g. [`Output::TWetBulb`] or [`Output::TDewPoint`]
+    /// * `t_dry_bulb` - Grid of dry-bulb temperature in \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
+    /// * `relative_humidity` - Grid of relative humidity, in the range of 0 to 1
+    /// * `pressure` - Total pressure in \\(\\mathrm{Pa}\\) (SI) or \\(\\mathrm{Psi}\\) (IP)
+    /// * `unit` - Unit system
+    /// * `options` - Model options
+    ///
+    /// # Errors
+    /// If the pressure is invalid, or the property cannot be evaluated at a point of the grids,
+    /// e.g. the dew point temperature of dry air
+    pub fn new(
+        output: Output,
+        t_dry_bulb: Grid,
+        relative_humidity: Grid,
+        pressure: f64,
+        unit: UnitSystem,
+        options: &ModelOptions,
+    ) -> Result<Self, PsychroidError> {
+        check_pressure(pressure, unit)?;
+        let t_points = t_dry_bulb.refined_points();
+        let rh_points = relative_humidity.refined_points();
+        let t_samples: Vec<f64> = t_points
+            .iter()
+            .flat_map(|&t| core::iter::repeat_n(t, rh_points.len()))
+            .collect();
+        let rh_samples: Vec<f64> = t_points
+            .iter()
+            .flat_map(|_| rh_points.iter().copied())
+            .collect();
+        let mut exact = vec![0.0; t_samples.len()];
+        let errors = batch::from_t_dry_bulb_relative_humidity(
+            &t_samples,
+            &rh_samples,
+            &[pressure],
+            unit,
+            options,
+            &mut [(output, &mut exact)],
+        );
+        if let Some((_, error)) = errors.into_iter().next() {
+            return Err(error);
+        }
+        let (values, error_estimate) = tabulate(&exact, t_dry_bulb.count, relative_humidity.count)?;
+        // OK to unwrap because OUTPUTS contains all the properties
+        let code = OUTPUTS.iter().position(|o| *o == output).unwrap() as u8;
+        Ok(PropertyTable {
+            table: Table::encode(
+                KIND_PROPERTY,
+                unit,
+                code,
+                pressure,
+                error_estimate,
+                [t_dry_bulb, relative_humidity],
+                &values,
+            ),
+        })
+    }
+}
+
+impl<'a> PropertyTable<'a> {
+    /// Reads the table from its binary without copying
+    ///
+    /// # Errors
+    /// If the binary is not a valid property table
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, PsychroidError> {
+        let table = Table::decode(bytes, KIND_PROPERTY)?;
+        if table.code as usize >= OUTPUTS.len() {
+            return Err(PsychroidError::InvalidTableData("unknown property"));
+        }
+        Ok(PropertyTable { table })
+    }
+
+    /// Returns the property interpolated from the table
+    ///
+    /// # Arguments
+    /// * `t_dry_bulb` - Dry-bulb temperature in \\(^\\circ \\mathrm{C}\\) (SI) or \\(^\\circ \\mathrm{F}\\) (IP)
+    /// * `relative_humidity` - Relative humidity, in the range of 0 to 1
+    ///
+    /// # Errors
+    /// If the dry-bulb temperature or the relative humidity is outside the grid
+    pub fn evaluate(&self, t_dry_bulb: f64, relative_humidity: f64) -> Result<f64, PsychroidError> {
+        let [t_grid, rh_grid] = &self.table.grids;
+        let (i, u) = t_grid.locate("dry-bulb temperature", t_dry_bulb, Some(self.unit()))?;
+        let (j, v) = rh_grid.locate("relative humidity", relative_humidity, None)?;
+        let m = rh_grid.count;
+        let k = i * m + j;
+        let lower = lerp(self.table.value(k), self.table.value(k + 1), v);
+        let upper = lerp(self.table.value(k + m), self.table.value(k + m + 1), v);
+        Ok(lerp(lower, upper, u))
+    }
+
+    /// Returns the estimate of the maximum absolute error of the interpolated property,
+    /// which is not a guaranteed bound, see the [module documentation](self)
+    pub fn error_estimate(&self) -> f64 {
+        self.table.error_estimate
+    }
+
+    /// Returns the tabulated property
+    pub fn output(&self) -> Output {
+        OUTPUTS[self.table.code as usize]
+    }
+
+    /// Returns the grid of dry-bulb temperature
+    pub fn t_dry_bulb_grid(&self) -> Grid {
+        self.table.grids[0]
+    }
+
+    /// Returns the grid of relative humidity
+    pub fn relative_humidity_grid(&self) -> Grid {
+        self.table.grids[1]
+    }
+
+    /// Returns the total pressure
+    pub fn pressure(&self) -> f64 {
+        self.table.pressure
+    }
+
+    /// Returns the unit system
+    pub fn unit(&self) -> UnitSystem {
+        self.table.unit
+    }
+
+    /// Returns the binary of the table
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.table.bytes
+    }
+
+    /// Returns the source of a Rust `static` array of the binary, to be read by [`PropertyTable::from_bytes`]
+    ///
+    /// # Arguments
+    /// * `name` - Name of the array, e.g. `WET_BULB_TABLE`
+    pub fn to_rust_source(&self, name: &str) -> String {
+        let (t_grid, rh_grid) = (self.t_dry_bulb_grid(), self.relative_humidity_grid());
+        let description = format!(
+            "{:?} table over dry-bulb temperature {} to {} ({} points) and relative humidity {} to {} ({} points) at pressure {}, {:?}",
+            self.output(),
+            t_grid.min,
+            t_grid.max,
+            t_grid.count,
+            rh_grid.min,
+            rh_grid.max,
+            rh_grid.count,
+            self.pressure(),
+            self.unit()
+        );
+        self.table.to_rust_source(name, &description)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moist_air::MoistAir;
+
+    #[test]
+    fn test_saturation_table() {
+        for (unit, min, max) in [
+            (UnitSystem::SI, -60.0, 150.0),
+            (UnitSystem::IP, -76.0, 302.0),
+        ] {
+            let grid = Grid::new(min, max, 421).unwrap();
+            let table = SaturationTable::new(grid, unit, SaturationReference::Ice).unwrap();
+            assert!(table.error_estimate() < 2.0E-4);
+            for k in 0..=1000 {
+                let t = min + (max - min) * k as f64 / 1000.0;
+                let exact = SaturatedWaterVapor::new(t, unit)
+                    .unwrap()
+                    .saturation_pressure();
+                let pws = table.saturation_pressure(t).unwrap();
+                assert!((pws / exact - 1.0).abs() <= table.error_estimate());
+            }
+            assert!(matches!(
+                table.saturation_pressure(max + 1.0),
+                Err(PsychroidError::OutOfRange { .. })
+            ));
+        }
+
+        // no saturation pressure above the critical point
+        let grid = Grid::new(300.0, 400.0, 11).unwrap();
+        assert!(matches!(
+            SaturationTable::new(grid, UnitSystem::SI, SaturationReference::Ice),
+            Err(PsychroidError::InvalidTableData(_))
+        ));
+    }
+
+    #[test]
+    fn test_property_table() {
+        let unit = UnitSystem::SI;
+        let options = ModelOptions::default();
+        let t_grid = Grid::new(-20.0, 50.0, 71).unwrap();
+        let rh_grid = Grid::new(0.05, 1.0, 20).unwrap();
+        for output in [Output::TWetBulb, Output::TDewPoint] {
+            let table =
+                PropertyTable::new(output, t_grid, rh_grid, 101325.0, unit, &options).unwrap();
+            // the dew point temperature is steep at low relative humidity
+            assert!(table.error_estimate() < 2.0);
+            for k in 0..=200 {
+                // points scattered over the cells
+                let t = -20.0 + 70.0 * ((k as f64 * 0.618034) % 1.0);
+                let rh = 0.05 + 0.95 * ((k as f64 * 0.414214) % 1.0);
+                let moist_air =
+                    MoistAir::from_t_dry_bulb_relative_humidity(t, rh, 101325.0, unit).unwrap();
+                let exact = output.evaluate(&moist_air).unwrap();
+                let value = table.evaluate(t, rh).unwrap();
+                assert!((value - exact).abs() <= table.error_estimate());
+            }
+        }
+
+        // the dew point temperature of dry air is undefined
+        let rh_grid = Grid::new(0.0, 1.0, 11).unwrap();
+        assert!(
+            PropertyTable::new(Output::TDewPoint, t_grid, rh_grid, 101325.0, unit, &options)
+                .is_err()
+        );
+        assert!(matches!(
+            Grid::new(1.0, 0.0, 11),
+            Err(PsychroidError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            Grid::new(0.0, 1.0, 1),
+            Err(PsychroidError::OutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_serialization() {
+        let unit = UnitSystem::IP;
+        let table = PropertyTable::new(
+            Output::TWetBulb,
+            Grid::new(32.0, 104.0, 37).unwrap(),
+            Grid::new(0.1, 1.0, 10).unwrap(),
+            14.696,
+            unit,
+            &ModelOptions::default(),
+        )
+        .unwrap();
+        let bytes = table.as_bytes();
+        assert_eq!(bytes.len(), PREAMBLE_SIZE + 2 * GRID_SIZE + 4 * 37 * 10);
+        let restored = PropertyTable::from_bytes(bytes).unwrap();
+        assert_eq!(restored, table);
+        assert_eq!(restored.output(), Output::TWetBulb);
+        assert_eq!(restored.pressure(), 14.696);
+        assert_eq!(
+            restored.evaluate(77.7, 0.55).unwrap(),
+            table.evaluate(77.7, 0.55).unwrap()
+        );
+
+        // a saturation table is not a property table
+        let saturation = SaturationTable::new(
+            Grid::new(32.0, 104.0, 37).unwrap(),
+            unit,
+            SaturationReference::Water,
+        )
+        .unwrap();
+        assert_eq!(
+            PropertyTable::from_bytes(saturation.as_bytes()),
+            Err(PsychroidError::InvalidTableData("wrong kind of table"))
+        );
+        let restored = SaturationTable::from_bytes(saturation.as_bytes()).unwrap();
+        assert_eq!(restored.reference(), SaturationReference::Water);
+        assert!(matches!(
+            PropertyTable::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(PsychroidError::InvalidTableData(_))
+        ));
+        assert!(matches!(
+            PropertyTable::from_bytes(&bytes[1..]),
+            Err(PsychroidError::InvalidTableData(_))
+        ));
+
+        let source = saturation.to_rust_source("SATURATION_TABLE");
+        let length = saturation.as_bytes().len();
+        assert!(source.contains(&format!(
+            "pub static SATURATION_TABLE: [u8; {}] = [",
+            length
+        )));
+        assert!(source.contains("0x50, 0x53, 0x59, 0x54,"));
+        assert_eq!(source.matches("0x").count(), length);
+    }
+}
@@ -0,0 +1,212 @@
+use crate::common::{MOLAR_MASS_DRY_AIR, MOLAR_MASS_WATER, ZERO_CELSIUS_AS_KELVIN};
+#[cfg(not(feature = "std"))]
+use num_traits::Float as _;
+
+// Correlations for viscosity and thermal conductivity are from:
+// P.T. Tsilingiris, "Thermophysical and transport properties of humid air at temperature range
+// between 0 and 100 °C", Energy Conversion and Management 49 (2008) 1098-1110.
+
+// Dynamic viscosity of dry air, Tsilingiris (2008) Eq. (21), in 10⁻⁶ Pa·s
+const MA0: f64 = -9.8601E-01;
+const MA1: f64 = 9.080125E-02;
+const MA2: f64 = -1.17635575E-04;
+const MA3: f64 = 1.2349703E-07;
+const MA4: f64 = -5.7971299E-11;
+
+// Dynamic viscosity of water vapor, Tsilingiris (2008) Eq. (22), in 10⁻⁷ Pa·s
+const MV0: f64 = 8.058131868E+01;
+const MV1: f64 = 4.000549451E-01;
+
+// Thermal conductivity of dry air, Tsilingiris (2008) Eq. (28), in W/(m·K)
+const KA0: f64 = -2.276501E-03;
+const KA1: f64 = 1.2598485E-04;
+const KA2: f64 = -1.4815235E-07;
+const KA3: f64 = 1.73550646E-10;
+const KA4: f64 = -1.066657E-13;
+const KA5: f64 = 2.47663035E-17;
+
+// Thermal conductivity of water vapor, Tsilingiris (2008) Eq. (29), in 10⁻³ W/(m·K)
+const KV0: f64 = 1.761758242E+01;
+const KV1: f64 = 5.558941059E-02;
+
+// Standard atmosphere in Pa
+const STANDARD_ATMOSPHERE: f64 = 101325.0;
+
+/// Calculates the dynamic viscosity of dry air
+///
+/// # Arguments
+/// * `t_kelvin` - Temperature \[K\]
+///
+/// # Returns
+/// * Dynamic viscosity \[Pa·s\]
+pub fn dynamic_viscosity_dry_air(t_kelvin: f64) -> f64 {
+    let t = t_kelvin;
+    (MA0 + MA1 * t + MA2 * t.powi(2) + MA3 * t.powi(3) + MA4 * t.powi(4)) * 1.0E-06
+}
+
+/// Calculates the dynamic viscosity of water vapor
+///
+/// # Arguments
+/// * `t_kelvin` - Temperature \[K\]
+///
+/// # Returns
+/// * Dynamic viscosity \[Pa·s\]
+pub fn dynamic_viscosity_water_vapor(t_kelvin: f64) -> f64 {
+    let t_celsius = t_kelvin - ZERO_CELSIUS_AS_KELVIN;
+    (MV0 + MV1 * t_celsius) * 1.0E-07
+}
+
+/// Calculates the thermal conductivity of dry air
+///
+/// # Arguments
+/// * `t_kelvin` - Temperature \[K\]
+///
+/// # Returns
+/// * Thermal conductivity \[W/(m·K)\]
+pub fn thermal_conductivity_dry_air(t_kelvin: f64) -> f64 {
+    let t = t_kelvin;
+    KA0 + KA1 * t + KA2 * t.powi(2) + KA3 * t.powi(3) + KA4 * t.powi(4) + KA5 * t.powi(5)
+}
+
+/// Calculates the thermal conductivity of water vapor
+///
+/// # Arguments
+/// * `t_kelvin` - Temperature \[K\]
+///
+/// # Returns
+/// * Thermal conductivity \[W/(m·K)\]
+pub fn thermal_conductivity_water_vapor(t_kelvin: f64) -> f64 {
+    let t_celsius = t_kelvin - ZERO_CELSIUS_AS_KELVIN;
+    (KV0 + KV1 * t_celsius) * 1.0E-03
+}
+
+/// Calculates the interaction parameters of Wilke's mixing rule, Tsilingiris (2008) Eq. (20)
+///
+/// # Returns
+/// * Tuple \\((\\Phi_{av}, \\Phi_{va})\\)
+fn wilke_interaction(mu_a: f64, mu_v: f64) -> (f64, f64) {
+    let ratio_mass = MOLAR_MASS_WATER / MOLAR_MASS_DRY_AIR;
+    let phi_av = core::f64::consts::SQRT_2 / 4.0
+        * (1.0 + 1.0 / ratio_mass).powf(-0.5)
+        * (1.0 + (mu_a / mu_v).sqrt() * ratio_mass.powf(0.25)).powi(2);
+    let phi_va = core::f64::consts::SQRT_2 / 4.0
+        * (1.0 + ratio_mass).powf(-0.5)
+        * (1.0 + (mu_v / mu_a).sqrt() * (1.0 / ratio_mass).powf(0.25)).powi(2);
+    (phi_av, phi_va)
+}
+
+/// Mixes a property of dry air and water vapor with Wilke's rule, Tsilingiris (2008) Eq. (19) and (27)
+fn wilke_mixture(t_kelvin: f64, x_w: f64, value_a: f64, value_v: f64) -> f64 {
+    let (phi_av, phi_va) = wilke_interaction(
+        dynamic_viscosity_dry_air(t_kelvin),
+        dynamic_viscosity_water_vapor(t_kelvin),
+    );
+    let x_a = 1.0 - x_w;
+    x_a * value_a / (x_a + x_w * phi_av) + x_w * value_v / (x_w + x_a * phi_va)
+}
+
+/// Calculates the dynamic viscosity of humid air
+///
+/// # Arguments
+/// * `t_kelvin` - Temperature \[K\]
+/// * `x_w` - Mole fraction of water vapor \[-\]
+///
+/// # Returns
+/// * Dynamic viscosity \[Pa·s\]
+///
+/// # Example
+/// ```
+/// use psychroid::transport::dynamic_viscosity;
+///
+/// let mu = dynamic_viscosity(300.0, 0.0);
+/// assert!((mu - 18.5E-06).abs() < 0.1E-06);
+/// ```
+pub fn dynamic_viscosity(t_kelvin: f64, x_w: f64) -> f64 {
+    wilke_mixture(
+        t_kelvin,
+        x_w,
+        dynamic_viscosity_dry_air(t_kelvin),
+        dynamic_viscosity_water_vapor(t_kelvin),
+    )
+}
+
+/// Calculates the thermal conductivity of humid air
+///
+/// # Arguments
+/// * `t_kelvin` - Temperature \[K\]
+/// * `x_w` - Mole fraction of water vapor \[-\]
+///
+/// # Returns
+/// * Thermal conductivity \[W/(m·K)\]
+pub fn thermal_conductivity(t_kelvin: f64, x_w: f64) -> f64 {
+    wilke_mixture(
+        t_kelvin,
+        x_w,
+        thermal_conductivity_dry_air(t_kelvin),
+        thermal_conductivity_water_vapor(t_kelvin),
+    )
+}
+
+/// Calculates the binary mass diffusivity of water vapor in air,
+/// according to Marrero and Mason (1972), valid between 280 K and 450 K
+///
+/// # Arguments
+/// * `t_kelvin` - Temperature \[K\]
+/// * `pressure` - Total pressure \[Pa\]
+///
+/// # Returns
+/// * Mass diffusivity \[m²/s\]
+pub fn mass_diffusivity(t_kelvin: f64, pressure: f64) -> f64 {
+    1.87E-10 * t_kelvin.powf(2.072) / (pressure / STANDARD_ATMOSPHERE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_dry_air() {
+        // Reference values from Tsilingiris (2008) Table 2
+        assert_relative_eq!(
+            dynamic_viscosity(300.0, 0.0),
+            18.53E-06,
+            max_relative = 0.005
+        );
+        assert_relative_eq!(
+            thermal_conductivity(300.0, 0.0),
+            0.0261,
+            max_relative = 0.01
+        );
+    }
+
+    #[test]
+    fn test_humid_air() {
+        // viscosity decreases and conductivity slightly changes with the water vapor content
+        let t_kelvin = 333.15;
+        let mu_dry = dynamic_viscosity(t_kelvin, 0.0);
+        let mu_humid = dynamic_viscosity(t_kelvin, 0.2);
+        assert!(mu_humid < mu_dry);
+        assert_relative_eq!(
+            dynamic_viscosity(t_kelvin, 1.0),
+            dynamic_viscosity_water_vapor(t_kelvin)
+        );
+        assert_relative_eq!(
+            thermal_conductivity(t_kelvin, 1.0),
+            thermal_conductivity_water_vapor(t_kelvin)
+        );
+    }
+
+    #[test]
+    fn test_mass_diffusivity() {
+        assert_relative_eq!(
+            mass_diffusivity(298.15, 101325.0),
+            2.50E-05,
+            max_relative = 0.01
+        );
+        assert_relative_eq!(
+            mass_diffusivity(298.15, 50662.5),
+            2.0 * mass_diffusivity(298.15, 101325.0)
+        );
+    }
+}
@@ -0,0 +1,466 @@
+//! Propagation of the measurement uncertainty of the inputs to the properties of moist air.
+//!
+//! A state measured by a pair of sensors, e.g. dry-bulb temperature and relative humidity,
+//! and the total pressure is given as a [`Measurement`] with the standard uncertainties of the inputs,
+//! which are assumed to be independent. The combined standard uncertainty of each property is evaluated
+//! by either of the methods of the Guide to the Expression of Uncertainty in Measurement (GUM):
+//!
+//! - [`linearization`]: the law of propagation of uncertainty, JCGM 100:2008 Eq. (10),
+//!   $$
+//!   u_\\mathrm{c}^2(y) = \\sum_i \\left( \\frac{\\partial y}{\\partial x_i} \\right)^2 u^2(x_i),
+//!   $$
+//!   with the sensitivity coefficients of [`MoistAir::gradient`].
+//! - [`monte_carlo`]: the propagation of distributions, JCGM 101:2008, with normally distributed inputs
+//!   drawn from a pseudo-random generator of the given seed, so that the results are reproducible.
+//!
+//! The linearization is exact for small uncertainties, while the Monte Carlo method also accounts for
+//! the curvature of the relations, e.g. of the saturation pressure, at the cost of many evaluations.
+//!
+//! # Example
+//! ```
+//! use psychroid::batch::Output;
+//! use psychroid::uncertainty::{self, Measurement, StandardUncertainties};
+//! use psychroid::{ModelOptions, Property, PropertyPair, UnitSystem};
+//!
+//! // ±0.2 K and ±2 %RH sensors
+//! let measurement = Measurement {
+//!     pair: PropertyPair(Property::TDryBulb(25.0), Property::RelativeHumidity(0.5)),
+//!     pressure: 101325.0,
+//!     uncertainties: StandardUncertainties {
+//!         first: 0.2,
+//!         second: 0.02,
+//!         pressure: 0.0,
+//!     },
+//! };
+//! let outputs = [Output::TWetBulb, Output::TDewPoint, Output::SpecificEnthalpy];
+//! let options = ModelOptions::default();
+//! let estimates = uncertainty::linearization(&measurement, UnitSystem::SI, options, &outputs).unwrap();
+//! assert!((estimates[0].uncertainty - 0.36).abs() < 0.01); // K
+//! assert!((estimates[1].uncertainty - 0.64).abs() < 0.01); // K
+//! assert!((estimates[2].uncertainty - 1.14).abs() < 0.01); // kJ/kg
+//!
+//! let report = uncertainty::monte_carlo(&measurement, UnitSystem::SI, options, &outputs, 10_000, 1).unwrap();
+//! assert_eq!(report.rejected, 0);
+//! for (linearized, simulated) in estimates.iter().zip(report.estimates.iter()) {
+//!     assert!((simulated.uncertainty / linearized.uncertainty - 1.0).abs() < 0.05);
+//! }
+//! ```
+use crate::batch::Output;
+use crate::common::UnitSystem;
+use crate::error::PsychroidError;
+use crate::moist_air::{ModelOptions, MoistAir, PropertyPair};
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use num_traits::Float as _;
+
+/// Standard uncertainties of the inputs of a [`Measurement`], in the units of the inputs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StandardUncertainties {
+    /// Of the first property of the pair
+    pub first: f64,
+    /// Of the second property of the pair
+    pub second: f64,
+    /// Of the total pressure
+    pub pressure: f64,
+}
+
+/// Measured state of moist air, i.e. the arguments of [`MoistAir::from_pair_with_options`]
+/// other than the unit system and the model options, with the standard uncertainties of the inputs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Measurement {
+    /// Pair of measured properties
+    pub pair: PropertyPair,
+    /// Measured total pressure in \\(\\mathrm{Pa}\\) (SI) or \\(\\mathrm{Psi}\\) (IP)
+    pub pressure: f64,
+    /// Standard uncertainties of the pair and of the pressure
+    pub uncertainties: StandardUncertainties,
+}
+
+/// Estimate of a property with its combined standard uncertainty, in the unit of the property
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    /// Value at the measured state (linearization) or mean of the samples (Monte Carlo)
+    pub value: f64,
+    /// Combined standard uncertainty
+    pub uncertainty: f64,
+}
+
+/// Estimates of the Monte Carlo method, with the number of the samples
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonteCarloReport {
+    /// Estimates in the order of the requested properties
+    pub estimates: Vec<Estimate>,
+    /// Number of the samples of which the properties are evaluated
+    pub accepted: usize,
+    /// Number of the samples outside the valid range, e.g. relative humidity above 1,
+    /// which are discarded, i.e. the distributions of the inputs are truncated
+    pub rejected: usize,
+}
+
+/// Checks the standard uncertainties, which must be finite and non-negative
+fn check_uncertainties(uncertainties: &StandardUncertainties) -> Result<(), PsychroidError> {
+    let StandardUncertainties {
+        first,
+        second,
+        pressure,
+    } = *uncertainties;
+    for value in [first, second, pressure] {
+        if !(value.is_finite() && value >= 0.0) {
+            return Err(PsychroidError::OutOfRange {
+                quantity: "standard uncertainty",
+                value,
+                min: 0.0,
+                max: f64::INFINITY,
+                unit: None,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Propagates the uncertainties by the linearization of the relations at the measured state
+///
+/// # Arguments
+/// * `measurement` - Measured state with the standard uncertainties of the inputs
+/// * `unit` - Unit system
+/// * `options` - Model options
+/// * `outputs` - Properties to be estimated
+///
+/// # Returns
+/// The estimates in the order of `outputs`
+///
+/// # Errors
+/// * Errors of [`MoistAir::from_pair_with_options`] for the measured state
+/// * Errors of [`MoistAir::gradient`], e.g. the dew point of dry air
+/// * `OutOfRange` if a standard uncertainty is negative or not finite
+pub fn linearization(
+    measurement: &Measurement,
+    unit: UnitSystem,
+    options: ModelOptions,
+    outputs: &[Output],
+) -> Result<Vec<Estimate>, PsychroidError> {
+    check_uncertainties(&measurement.uncertainties)?;
+    let Measurement {
+        pair,
+        pressure,
+        uncertainties,
+    } = *measurement;
+    let moist_air = MoistAir::from_pair_with_options(pair, pressure, unit, options)?;
+    let (first, second) = (pair.0.into(), pair.1.into());
+    outputs
+        .iter()
+        .map(|output| {
+            let gradient = moist_air.gradient(*output, first, second)?;
+            let variance = (gradient.first * uncertainties.first).powi(2)
+                + (gradient.second * uncertainties.second).powi(2)
+                + (gradient.pressure * uncertainties.pressure).powi(2);
+            Ok(Estimate {
+                value: output.evaluate(&moist_air)?,
+                uncertainty: variance.sqrt(),
+            })
+        })
+        .collect()
+}
+
+/// Propagates the uncertainties by the Monte Carlo method with normally distributed inputs
+///
+/// The samples of which the state or any of the properties cannot be evaluated, e.g. with
+/// the relative humidity above 1 near saturation, are discarded and counted in the report.
+///
+/// # Arguments
+/// * `measurement` - Measured state with the standard uncertainties of the inputs
+/// * `unit` - Unit system
+/// * `options` - Model options
+/// * `outputs` - Properties to be estimated
+/// * `trials` - Number of the samples, e.g. \\(10^4\\) for the uncertainties to about 1 %
+/// * `seed` - Seed of the pseudo-random generator
+///
+/// # Returns
+/// The means and the standard deviations of the properties of the accepted samples
+///
+/// # Errors
+/// * `OutOfRange` if a standard uncertainty is negative or not finite, or `trials` is less than 2
+/// * The error of the first rejected sample, if less than 2 samples are accepted
+pub fn monte_carlo(
+    measurement: &Measurement,
+    unit: UnitSystem,
+    options: ModelOptions,
+    outputs: &[Output],
+    trials: usize,
+    seed: u64,
+) -> Result<MonteCarloReport, PsychroidError> {
+    check_uncertainties(&measurement.uncertainties)?;
+    if trials < 2 {
+        return Err(PsychroidError::OutOfRange {
+            quantity: "number of trials",
+            value: trials as f64,
+            min: 2.0,
+            max: f64::INFINITY,
+            unit: None,
+        });
+    }
+    let Measurement {
+        pair: PropertyPair(first, second),
+        pressure,
+        uncertainties,
+    } = *measurement;
+    let mut random = Random::new(seed);
+    // running means and sums of squared deviations, by Welford's algorithm
+    let mut means = vec![0.0; outputs.len()];
+    let mut squares = vec![0.0; outputs.len()];
+    let mut values = vec![0.0; outputs.len()];
+    let mut accepted = 0;
+    let mut error = None;
+    for _ in 0..trials {
+        let pair = PropertyPair(
+            first.with_value(first.value() + uncertainties.first * random.normal()),
+            second.with_value(second.value() + uncertainties.second * random.normal()),
+        );
+        let pressure = pressure + uncertainties.pressure * random.normal();
+        let sample =
+            MoistAir::from_pair_with_options(pair, pressure, unit, options).and_then(|moist_air| {
+                for (value, output) in values.iter_mut().zip(outputs.iter()) {
+                    *value = output.evaluate(&moist_air)?;
+                }
+                Ok(())
+            });
+        if let Err(e) = sample {
+            error.get_or_insert(e);
+            continue;
+        }
+        // a property not defined at the sample, e.g. the dew point of dry air, is NaN
+        if values.iter().any(|value| value.is_nan()) {
+            continue;
+        }
+        accepted += 1;
+        for ((mean, square), value) in means.iter_mut().zip(squares.iter_mut()).zip(values.iter()) {
+            let deviation = value - *mean;
+            *mean += deviation / accepted as f64;
+            *square += deviation * (value - *mean);
+        }
+    }
+    if accepted < 2 {
+        return Err(error.unwrap_or(PsychroidError::OutOfRange {
+            quantity: "number of accepted samples",
+            value: accepted as f64,
+            min: 2.0,
+            max: f64::INFINITY,
+            unit: None,
+        }));
+    }
+    let estimates = means
+        .iter()
+        .zip(squares.iter())
+        .map(|(mean, square)| Estimate {
+            value: *mean,
+            uncertainty: (square / (accepted - 1) as f64).sqrt(),
+        })
+        .collect();
+    Ok(MonteCarloReport {
+        estimates,
+        accepted,
+        rejected: trials - accepted,
+    })
+}
+
+/// Pseudo-random generator xoshiro256**, seeded by SplitMix64
+///
+/// D. Blackman and S. Vigna, "Scrambled linear pseudorandom number generators",
+/// ACM Transactions on Mathematical Software 47 (2021) 36.
+struct Random {
+    state: [u64; 4],
+    spare: Option<f64>,
+}
+
+impl Random {
+    fn new(seed: u64) -> Self {
+        let mut seed = seed;
+        let mut split_mix = || {
+            seed = seed.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            z ^ (z >> 31)
+        };
+        Random {
+            state: [split_mix(), split_mix(), split_mix(), split_mix()],
+            spare: None,
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Uniform in [-1, 1)
+    fn uniform(&mut self) -> f64 {
+        // 53 bits of the mantissa
+        (self.next_u64() >> 11) as f64 * 2.0f64.powi(-52) - 1.0
+    }
+
+    /// Standard normal by the Marsaglia polar method, which yields the values in pairs
+    fn normal(&mut self) -> f64 {
+        if let Some(value) = self.spare.take() {
+            return value;
+        }
+        loop {
+            let (u, v) = (self.uniform(), self.uniform());
+            let s = u * u + v * v;
+            if s > 0.0 && s < 1.0 {
+                let factor = (-2.0 * s.ln() / s).sqrt();
+                self.spare = Some(v * factor);
+                return u * factor;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moist_air::Property;
+    use approx::assert_relative_eq;
+
+    fn measured(pair: PropertyPair, pressure: f64, uncertainties: [f64; 3]) -> Measurement {
+        let [first, second, u_pressure] = uncertainties;
+        Measurement {
+            pair,
+            pressure,
+            uncertainties: StandardUncertainties {
+                first,
+                second,
+                pressure: u_pressure,
+            },
+        }
+    }
+
+    #[test]
+    fn test_random() {
+        let mut random = Random::new(42);
+        let samples: Vec<f64> = (0..100_000).map(|_| random.normal()).collect();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let variance =
+            samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+        assert!(mean.abs() < 0.01);
+        assert_relative_eq!(variance, 1.0, max_relative = 0.01);
+        // reproducible with the same seed
+        assert_eq!(Random::new(42).next_u64(), Random::new(42).next_u64());
+        assert_ne!(Random::new(42).next_u64(), Random::new(43).next_u64());
+    }
+
+    #[test]
+    fn test_linearization() {
+        let unit = UnitSystem::SI;
+        let options = ModelOptions::default();
+        let pair = PropertyPair(Property::TDryBulb(30.0), Property::TWetBulb(20.0));
+        let measurement = measured(pair, 101325.0, [0.1, 0.2, 100.0]);
+        let outputs = [Output::RelativeHumidity, Output::HumidityRatio];
+        let estimates = linearization(&measurement, unit, options, &outputs).unwrap();
+        let air = MoistAir::from_pair(pair, 101325.0, unit).unwrap();
+        for (estimate, output) in estimates.iter().zip(outputs.iter()) {
+            let gradient = air
+                .gradient(*output, Output::TDryBulb, Output::TWetBulb)
+                .unwrap();
+            let uncertainty = ((0.1 * gradient.first).powi(2)
+                + (0.2 * gradient.second).powi(2)
+                + (100.0 * gradient.pressure).powi(2))
+            .sqrt();
+            assert_eq!(estimate.value, output.evaluate(&air).unwrap());
+            assert_relative_eq!(estimate.uncertainty, uncertainty, max_relative = 1.0E-12);
+        }
+        // the inputs themselves have their own uncertainties
+        let outputs = [Output::TDryBulb, Output::TWetBulb];
+        let estimates = linearization(&measurement, unit, options, &outputs).unwrap();
+        assert_relative_eq!(estimates[0].uncertainty, 0.1, max_relative = 1.0E-12);
+        assert_relative_eq!(estimates[1].uncertainty, 0.2, max_relative = 1.0E-12);
+    }
+
+    #[test]
+    fn test_monte_carlo() {
+        let options = ModelOptions::default();
+        let outputs = [
+            Output::TWetBulb,
+            Output::TDewPoint,
+            Output::SpecificEnthalpy,
+            Output::SpecificVolume,
+        ];
+        for (unit, pair, pressure, uncertainties) in [
+            (
+                UnitSystem::SI,
+                PropertyPair(Property::TDryBulb(25.0), Property::RelativeHumidity(0.5)),
+                101325.0,
+                [0.2, 0.02, 50.0],
+            ),
+            (
+                UnitSystem::IP,
+                PropertyPair(Property::TDryBulb(77.0), Property::RelativeHumidity(0.5)),
+                14.696,
+                [0.36, 0.02, 0.01],
+            ),
+        ] {
+            let measurement = measured(pair, pressure, uncertainties);
+            let linearized = linearization(&measurement, unit, options, &outputs).unwrap();
+            let report = monte_carlo(&measurement, unit, options, &outputs, 20_000, 7).unwrap();
+            assert_eq!((report.accepted, report.rejected), (20_000, 0));
+            for (linearized, simulated) in linearized.iter().zip(report.estimates.iter()) {
+                // the mean is shifted by the curvature, e.g. of the dew point in the relative humidity
+                let shift = simulated.value - linearized.value;
+                assert!(shift.abs() < 0.05 * linearized.uncertainty);
+                assert_relative_eq!(
+                    simulated.uncertainty,
+                    linearized.uncertainty,
+                    max_relative = 0.03
+                );
+            }
+            // reproducible with the same seed
+            let again = monte_carlo(&measurement, unit, options, &outputs, 20_000, 7).unwrap();
+            assert_eq!(report, again);
+        }
+    }
+
+    #[test]
+    fn test_monte_carlo_rejection() {
+        let unit = UnitSystem::SI;
+        let options = ModelOptions::default();
+        // about half of the samples of the relative humidity are above 1
+        let pair = PropertyPair(Property::TDryBulb(20.0), Property::RelativeHumidity(1.0));
+        let measurement = measured(pair, 101325.0, [0.2, 0.02, 0.0]);
+        let report =
+            monte_carlo(&measurement, unit, options, &[Output::TDewPoint], 1000, 0).unwrap();
+        assert_eq!(report.accepted + report.rejected, 1000);
+        assert!(report.rejected > 400 && report.rejected < 600);
+        assert!(report.estimates[0].value < 20.0);
+
+        // all the samples are out of range
+        let pair = PropertyPair(Property::TDryBulb(20.0), Property::RelativeHumidity(2.0));
+        let measurement = measured(pair, 101325.0, [0.2, 0.02, 0.0]);
+        let error = monte_carlo(&measurement, unit, options, &[Output::TDewPoint], 100, 0);
+        assert!(matches!(
+            error,
+            Err(PsychroidError::InvalidRelativeHumidity(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let unit = UnitSystem::SI;
+        let options = ModelOptions::default();
+        let pair = PropertyPair(Property::TDryBulb(20.0), Property::RelativeHumidity(0.5));
+        let invalid = measured(pair, 101325.0, [-0.2, 0.02, 0.0]);
+        let error = linearization(&invalid, unit, options, &[Output::TDewPoint]).unwrap_err();
+        assert_eq!(error.quantity(), Some("standard uncertainty"));
+        let valid = measured(pair, 101325.0, [0.2, 0.02, 0.0]);
+        let error = monte_carlo(&valid, unit, options, &[Output::TDewPoint], 1, 0).unwrap_err();
+        assert_eq!(error.quantity(), Some("number of trials"));
+    }
+}
@@ -0,0 +1,500 @@
+//! Conversion between the unit systems at the boundary of the API.
+//! <script src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+//!
+//! The correlations and solvers run in SI units only, i.e. temperatures in \\(^\\circ \\mathrm{C}\\),
+//! pressures in \\(\\mathrm{Pa}\\), specific enthalpies in \\(\\mathrm{kJ/kg_{da}}\\) and specific volumes
+//! in \\(\\mathrm{m^3/kg_{da}}\\). The inputs given in IP units are converted into SI units on construction,
+//! and the results are converted back by the accessors, so that both unit systems give the same states
+//! up to rounding.
+//!
+//! The specific enthalpy and entropy in IP units keep the reference state of ASHRAE Handbook - Fundamentals (2017)
+//! IP Ch. 1, i.e. dry air at \\(0~^\\circ \\mathrm{F}\\) and standard atmosphere, instead of \\(0~^\\circ \\mathrm{C}\\) in SI units.
+//! Their differences, e.g. the heat of a process, are converted by the factor only.
+//!
+//! The conversions are generic over [`Float`], so that the derivatives of [`crate::sensitivity`]
+//! are carried through them.
+use crate::batch::Output;
+use crate::common::{t_celsius_to_t_fahrenheit, t_fahrenheit_to_t_celsius, UnitSystem};
+use crate::common::{J_PER_KG_K_PER_BTU_PER_LB_F, J_PER_KG_K_PER_FT_LBF_PER_LB_R};
+use crate::common::{KJ_PER_KG_PER_BTU_PER_LB, M3_PER_KG_PER_FT3_PER_LB, PA_PER_PSI};
+use crate::common::{STANDARD_ATMOSPHERE_IP, STANDARD_ATMOSPHERE_SI};
+use crate::error::PsychroidError;
+use crate::float::Float;
+use crate::moist_air::{specific_enthalpy_from_humidity_ratio, specific_entropy_gas};
+use crate::moist_air::{ModelOptions, Property};
+
+/// Converts the temperature from \\(^\\circ \\mathrm{F}\\) (IP) to \\(^\\circ \\mathrm{C}\\)
+pub(crate) fn t_to_si<F: Float>(t: F, unit: UnitSystem) -> F {
+    match unit {
+        UnitSystem::SI => t,
+        UnitSystem::IP => t_fahrenheit_to_t_celsius(t),
+    }
+}
+
+/// Converts the temperature from \\(^\\circ \\mathrm{C}\\) to \\(^\\circ \\mathrm{F}\\) (IP)
+pub(crate) fn t_from_si<F: Float>(t: F, unit: UnitSystem) -> F {
+    match unit {
+        UnitSystem::SI => t,
+        UnitSystem::IP => t_celsius_to_t_fahrenheit(t),
+    }
+}
+
+/// Converts the temperature difference from \\(^\\circ \\mathrm{F}\\) (IP) to \\(\\mathrm{K}\\)
+pub(crate) fn dt_to_si<F: Float>(dt: F, unit: UnitSystem) -> F {
+    match unit {
+        UnitSystem::SI => dt,
+        UnitSystem::IP => dt / F::of(1.8),
+    }
+}
+
+/// Converts the pressure from \\(\\mathrm{Psi}\\) (IP) to \\(\\mathrm{Pa}\\)
+pub(crate) fn pressure_to_si<F: Float>(pressure: F, unit: UnitSystem) -> F {
+    match unit {
+        UnitSystem::SI => pressure,
+        UnitSystem::IP => pressure * F::of(PA_PER_PSI),
+    }
+}
+
+/// Converts the pressure from \\(\\mathrm{Pa}\\) to \\(\\mathrm{Psi}\\) (IP)
+pub(crate) fn pressure_from_si<F: Float>(pressure: F, unit: UnitSystem) -> F {
+    match unit {
+        UnitSystem::SI => pressure,
+        UnitSystem::IP => pressure / F::of(PA_PER_PSI),
+    }
+}
+
+/// Returns the specific enthalpy of dry air at \\(0~^\\circ \\mathrm{F}\\) and standard atmosphere
+/// in \\(\\mathrm{kJ/kg_{da}}\\), i.e. the reference state of the specific enthalpy in IP units
+fn specific_enthalpy_datum_ip(options: &ModelOptions) -> f64 {
+    specific_enthalpy_from_humidity_ratio(
+        t_fahrenheit_to_t_celsius(0.0),
+        0.0,
+        STANDARD_ATMOSPHERE_SI,
+        &options.for_unit(UnitSystem::IP),
+    )
+}
+
+/// Converts the specific enthalpy from \\(\\mathrm{Btu/lb_{da}}\\) (IP) to \\(\\mathrm{kJ/kg_{da}}\\),
+/// shifting the reference state from \\(0~^\\circ \\mathrm{F}\\) to \\(0~^\\circ \\mathrm{C}\\)
+pub(crate) fn specific_enthalpy_to_si<F: Float>(
+    specific_enthalpy: F,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> F {
+    match unit {
+        UnitSystem::SI => specific_enthalpy,
+        UnitSystem::IP => {
+            specific_energy_to_si(specific_enthalpy, unit)
+                + F::of(specific_enthalpy_datum_ip(options))
+        }
+    }
+}
+
+/// Converts the specific enthalpy from \\(\\mathrm{kJ/kg_{da}}\\) to \\(\\mathrm{Btu/lb_{da}}\\) (IP),
+/// shifting the reference state from \\(0~^\\circ \\mathrm{C}\\) to \\(0~^\\circ \\mathrm{F}\\)
+pub(crate) fn specific_enthalpy_from_si<F: Float>(
+    specific_enthalpy: F,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> F {
+    match unit {
+        UnitSystem::SI => specific_enthalpy,
+        UnitSystem::IP => specific_energy_from_si(
+            specific_enthalpy - F::of(specific_enthalpy_datum_ip(options)),
+            unit,
+        ),
+    }
+}
+
+/// Converts the energy per unit mass, e.g. a difference of the specific enthalpy,
+/// from \\(\\mathrm{Btu/lb}\\) (IP) to \\(\\mathrm{kJ/kg}\\)
+pub(crate) fn specific_energy_to_si<F: Float>(energy: F, unit: UnitSystem) -> F {
+    match unit {
+        UnitSystem::SI => energy,
+        UnitSystem::IP => energy * F::of(KJ_PER_KG_PER_BTU_PER_LB),
+    }
+}
+
+/// Converts the energy per unit mass, e.g. a difference of the specific enthalpy,
+/// from \\(\\mathrm{kJ/kg}\\) to \\(\\mathrm{Btu/lb}\\) (IP)
+pub(crate) fn specific_energy_from_si<F: Float>(energy: F, unit: UnitSystem) -> F {
+    match unit {
+        UnitSystem::SI => energy,
+        UnitSystem::IP => energy / F::of(KJ_PER_KG_PER_BTU_PER_LB),
+    }
+}
+
+/// Converts the specific volume from \\(\\mathrm{ft^3/lb_{da}}\\) (IP) to \\(\\mathrm{m^3/kg_{da}}\\)
+pub(crate) fn specific_volume_to_si<F: Float>(specific_volume: F, unit: UnitSystem) -> F {
+    match unit {
+        UnitSystem::SI => specific_volume,
+        UnitSystem::IP => specific_volume * F::of(M3_PER_KG_PER_FT3_PER_LB),
+    }
+}
+
+/// Converts the specific volume from \\(\\mathrm{m^3/kg_{da}}\\) to \\(\\mathrm{ft^3/lb_{da}}\\) (IP)
+pub(crate) fn specific_volume_from_si<F: Float>(specific_volume: F, unit: UnitSystem) -> F {
+    match unit {
+        UnitSystem::SI => specific_volume,
+        UnitSystem::IP => specific_volume / F::of(M3_PER_KG_PER_FT3_PER_LB),
+    }
+}
+
+/// Converts the density from \\(\\mathrm{kg/m^3}\\) to \\(\\mathrm{lb/ft^3}\\) (IP)
+pub(crate) fn density_from_si<F: Float>(density: F, unit: UnitSystem) -> F {
+    match unit {
+        UnitSystem::SI => density,
+        UnitSystem::IP => density * F::of(M3_PER_KG_PER_FT3_PER_LB),
+    }
+}
+
+/// Converts the specific heat from \\(\\mathrm{kJ/(kg \\cdot K)}\\)
+/// to \\(\\mathrm{Btu/(lb \\cdot {}^\\circ F)}\\) (IP)
+pub(crate) fn specific_heat_from_si<F: Float>(specific_heat: F, unit: UnitSystem) -> F {
+    match unit {
+        UnitSystem::SI => specific_heat,
+        UnitSystem::IP => specific_heat / F::of(J_PER_KG_K_PER_BTU_PER_LB_F * 0.001),
+    }
+}
+
+/// Converts the specific entropy from \\(\\mathrm{kJ/(kg_{da} \\cdot K)}\\)
+/// to \\(\\mathrm{Btu/(lb_{da} \\cdot {}^\\circ R)}\\) (IP), shifting the reference state of dry air
+/// from \\(0~^\\circ \\mathrm{C}\\) and \\(101.325~\\mathrm{kPa}\\) to \\(0~^\\circ \\mathrm{F}\\) and \\(14.696~\\mathrm{Psi}\\)
+pub(crate) fn specific_entropy_from_si(
+    specific_entropy: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> f64 {
+    match unit {
+        UnitSystem::SI => specific_entropy,
+        UnitSystem::IP => {
+            let datum = specific_entropy_gas(
+                t_fahrenheit_to_t_celsius(0.0),
+                0.0,
+                pressure_to_si(STANDARD_ATMOSPHERE_IP, unit),
+                &options.for_unit(unit),
+            );
+            specific_heat_from_si(specific_entropy - datum, unit)
+        }
+    }
+}
+
+/// Converts the specific gas constant from \\(\\mathrm{J/(kg \\cdot K)}\\)
+/// to \\(\\mathrm{ft \\cdot lbf/(lb \\cdot {}^\\circ R)}\\) (IP)
+pub(crate) fn gas_constant_from_si(gas_constant: f64, unit: UnitSystem) -> f64 {
+    match unit {
+        UnitSystem::SI => gas_constant,
+        UnitSystem::IP => gas_constant / J_PER_KG_K_PER_FT_LBF_PER_LB_R,
+    }
+}
+
+/// Converts the value of the property into SI units
+pub(crate) fn property_to_si(
+    property: Property,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Property {
+    let value = property.value();
+    property.with_value(match property {
+        Property::TDryBulb(_) | Property::TWetBulb(_) | Property::TDewPoint(_) => {
+            t_to_si(value, unit)
+        }
+        Property::HumidityRatio(_) | Property::RelativeHumidity(_) => value,
+        Property::SpecificEnthalpy(_) => specific_enthalpy_to_si(value, unit, options),
+        Property::SpecificVolume(_) => specific_volume_to_si(value, unit),
+    })
+}
+
+/// Converts the value of the output from SI units, e.g. a value of [`crate::sensitivity::Dual`]
+/// with its derivatives
+pub(crate) fn output_from_si<F: Float>(
+    output: Output,
+    value: F,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> F {
+    match output {
+        Output::TDryBulb | Output::TWetBulb | Output::TDewPoint => t_from_si(value, unit),
+        Output::HumidityRatio | Output::RelativeHumidity | Output::DegreeOfSaturation => value,
+        Output::SpecificEnthalpy => specific_enthalpy_from_si(value, unit, options),
+        Output::SpecificVolume => specific_volume_from_si(value, unit),
+        Output::Density => density_from_si(value, unit),
+        Output::PartialWaterVaporPressure => pressure_from_si(value, unit),
+        Output::SpecificHeat => specific_heat_from_si(value, unit),
+    }
+}
+
+/// Returns the conversion of the errors of the core in SI units into the unit system,
+/// for the values and the valid ranges of the variants tagged with the unit system.
+/// Used as `map_err(error_from_si(unit, &options))` at the boundary of the API.
+pub(crate) fn error_from_si(
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> impl Fn(PsychroidError) -> PsychroidError {
+    let options = *options;
+    move |error| {
+        let t = |t: f64| t_from_si(t, unit);
+        match error {
+            PsychroidError::InvalidTDryBulb {
+                t_dry_bulb,
+                unit: UnitSystem::SI,
+            } => PsychroidError::InvalidTDryBulb {
+                t_dry_bulb: t(t_dry_bulb),
+                unit,
+            },
+            PsychroidError::InvalidPressure {
+                pressure,
+                unit: UnitSystem::SI,
+            } => PsychroidError::InvalidPressure {
+                pressure: pressure_from_si(pressure, unit),
+                unit,
+            },
+            PsychroidError::InvalidTWetBulb {
+                t_wet_bulb,
+                min,
+                max,
+                unit: UnitSystem::SI,
+            } => PsychroidError::InvalidTWetBulb {
+                t_wet_bulb: t(t_wet_bulb),
+                min: t(min),
+                max: t(max),
+                unit,
+            },
+            PsychroidError::InvalidTDewPoint {
+                t_dew_point,
+                t_dry_bulb,
+                unit: UnitSystem::SI,
+            } => PsychroidError::InvalidTDewPoint {
+                t_dew_point: t(t_dew_point),
+                t_dry_bulb: t(t_dry_bulb),
+                unit,
+            },
+            PsychroidError::InvalidSpecificEnthalpy {
+                specific_enthalpy,
+                min,
+                unit: UnitSystem::SI,
+            } => PsychroidError::InvalidSpecificEnthalpy {
+                specific_enthalpy: specific_enthalpy_from_si(specific_enthalpy, unit, &options),
+                min: specific_enthalpy_from_si(min, unit, &options),
+                unit,
+            },
+            PsychroidError::InvalidSpecificVolume {
+                specific_volume,
+                min,
+                unit: UnitSystem::SI,
+            } => PsychroidError::InvalidSpecificVolume {
+                specific_volume: specific_volume_from_si(specific_volume, unit),
+                min: specific_volume_from_si(min, unit),
+                unit,
+            },
+            PsychroidError::PressureBelowSaturation {
+                pressure,
+                saturation_pressure,
+                unit: UnitSystem::SI,
+            } => PsychroidError::PressureBelowSaturation {
+                pressure: pressure_from_si(pressure, unit),
+                saturation_pressure: pressure_from_si(saturation_pressure, unit),
+                unit,
+            },
+            error => error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heat_capacity::EnthalpyModel;
+    use crate::moist_air::{GasModel, MoistAir, PropertyPair};
+    use crate::saturated_water_vapor::SaturationReference;
+    use approx::assert_relative_eq;
+
+    const OUTPUTS: [Output; 11] = [
+        Output::TDryBulb,
+        Output::HumidityRatio,
+        Output::RelativeHumidity,
+        Output::TWetBulb,
+        Output::TDewPoint,
+        Output::SpecificEnthalpy,
+        Output::SpecificVolume,
+        Output::Density,
+        Output::PartialWaterVaporPressure,
+        Output::DegreeOfSaturation,
+        Output::SpecificHeat,
+    ];
+
+    /// Returns the same state given in SI and in IP units
+    fn states(
+        t_dry_bulb: f64,
+        humidity_ratio: f64,
+        pressure: f64,
+        options: ModelOptions,
+    ) -> (MoistAir, MoistAir) {
+        let state = |t, p, unit| {
+            let pair = PropertyPair(
+                Property::TDryBulb(t),
+                Property::HumidityRatio(humidity_ratio),
+            );
+            MoistAir::from_pair_with_options(pair, p, unit, options).unwrap()
+        };
+        (
+            state(t_dry_bulb, pressure, UnitSystem::SI),
+            state(
+                t_celsius_to_t_fahrenheit(t_dry_bulb),
+                pressure / PA_PER_PSI,
+                UnitSystem::IP,
+            ),
+        )
+    }
+
+    fn assert_same_state(si: &MoistAir, ip: &MoistAir) {
+        assert_relative_eq!(
+            t_from_si(si.t_dry_bulb(), UnitSystem::IP),
+            ip.t_dry_bulb(),
+            epsilon = 1.0E-9,
+            max_relative = 1.0E-9
+        );
+        assert_relative_eq!(
+            si.humidity_ratio(),
+            ip.humidity_ratio(),
+            epsilon = 1.0E-12,
+            max_relative = 1.0E-9
+        );
+    }
+
+    #[test]
+    fn test_si_ip_agreement() {
+        // the linear model of the IP edition in both unit systems, which is the default in IP units
+        let linear = ModelOptions {
+            enthalpy_model: EnthalpyModel::LinearIp,
+            ..Default::default()
+        };
+        let variable = ModelOptions {
+            enthalpy_model: EnthalpyModel::VariableHeatCapacity,
+            ..Default::default()
+        };
+        let real = ModelOptions {
+            gas_model: GasModel::Real,
+            ..linear
+        };
+        let water = ModelOptions {
+            saturation_reference: SaturationReference::Water,
+            ..linear
+        };
+        let (_, ip) = states(30.0, 0.012, 101325.0, ModelOptions::default());
+        assert_eq!(ip.options(), linear);
+        let cases = [
+            (30.0, 0.012, 101325.0, linear),
+            (-10.0, 0.001, 101325.0, linear),
+            (-10.0, 0.001, 101325.0, water),
+            // fog
+            (10.0, 0.01, 101325.0, linear),
+            (60.0, 0.05, 80000.0, variable),
+            (40.0, 0.005, 1.0E+06, real),
+        ];
+        for (t_dry_bulb, humidity_ratio, pressure, options) in cases {
+            let (si, ip) = states(t_dry_bulb, humidity_ratio, pressure, options);
+            assert_same_state(&si, &ip);
+
+            // every property
+            for output in OUTPUTS {
+                let value_si = output.evaluate(&si).unwrap();
+                assert_relative_eq!(
+                    output_from_si(output, value_si, UnitSystem::IP, &options),
+                    output.evaluate(&ip).unwrap(),
+                    epsilon = 1.0E-9,
+                    max_relative = 1.0E-9
+                );
+            }
+            assert_relative_eq!(
+                specific_entropy_from_si(si.specific_entropy(), UnitSystem::IP, &options),
+                ip.specific_entropy(),
+                epsilon = 1.0E-12,
+                max_relative = 1.0E-9
+            );
+
+            // every process, with the same mass flow rate of dry air in both unit systems
+            let mda = 1.5;
+            let energy = |q_si: f64, q_ip: f64| {
+                assert_relative_eq!(
+                    specific_energy_from_si(q_si, UnitSystem::IP),
+                    q_ip,
+                    epsilon = 1.0E-9,
+                    max_relative = 1.0E-9
+                );
+            };
+            let (mut a, mut b) = (si, ip);
+            energy(
+                a.heating_t1(mda, t_dry_bulb + 20.0).unwrap(),
+                b.heating_t1(mda, t_celsius_to_t_fahrenheit(t_dry_bulb + 20.0))
+                    .unwrap(),
+            );
+            assert_same_state(&a, &b);
+            energy(
+                a.heating_dt(mda, 5.0).unwrap(),
+                b.heating_dt(mda, 9.0).unwrap(),
+            );
+            assert_same_state(&a, &b);
+            a.heating_q(mda, 10.0).unwrap();
+            b.heating_q(mda, specific_energy_from_si(10.0, UnitSystem::IP))
+                .unwrap();
+            assert_same_state(&a, &b);
+
+            let (mut a, mut b) = (si, ip);
+            energy(
+                a.cooling_t1(mda, t_dry_bulb - 15.0).unwrap(),
+                b.cooling_t1(mda, t_celsius_to_t_fahrenheit(t_dry_bulb - 15.0))
+                    .unwrap(),
+            );
+            assert_same_state(&a, &b);
+            energy(
+                a.cooling_dt(mda, 5.0).unwrap(),
+                b.cooling_dt(mda, 9.0).unwrap(),
+            );
+            assert_same_state(&a, &b);
+            a.cooling_q(mda, 10.0).unwrap();
+            b.cooling_q(mda, specific_energy_from_si(10.0, UnitSystem::IP))
+                .unwrap();
+            assert_same_state(&a, &b);
+
+            let (mut a, mut b) = (si, ip);
+            a.humidify_adiabatic(mda, 0.003).unwrap();
+            b.humidify_adiabatic(mda, 0.003).unwrap();
+            assert_same_state(&a, &b);
+            a.humidify_isothermal(mda, 0.003).unwrap();
+            b.humidify_isothermal(mda, 0.003).unwrap();
+            assert_same_state(&a, &b);
+
+            let (mut a, mut b) = (si, ip);
+            energy(
+                a.cooling_saturation(mda).unwrap(),
+                b.cooling_saturation(mda).unwrap(),
+            );
+            assert_same_state(&a, &b);
+        }
+    }
+
+    #[test]
+    fn test_reference_state_ip() {
+        let options = ModelOptions::default();
+        // dry air at 0 °F and standard atmosphere
+        let datum = specific_enthalpy_datum_ip(&options);
+        assert_eq!(
+            specific_enthalpy_from_si(datum, UnitSystem::IP, &options),
+            0.0
+        );
+        assert_relative_eq!(
+            specific_enthalpy_to_si(0.0, UnitSystem::IP, &options),
+            datum
+        );
+        assert_relative_eq!(
+            specific_enthalpy_to_si(
+                specific_enthalpy_from_si(50.0, UnitSystem::IP, &options),
+                UnitSystem::IP,
+                &options
+            ),
+            50.0,
+            max_relative = 1.0E-12
+        );
+    }
+}
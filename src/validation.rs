@@ -0,0 +1,202 @@
+//! Validation of the inputs shared by the constructors of [`MoistAir`](crate::MoistAir),
+//! [`SaturatedWaterVapor`](crate::SaturatedWaterVapor) and the [`functions`](crate::functions).
+//! Each check returns the dedicated [`PsychroidError`] variant of the violated constraint.
+use crate::common::UnitSystem;
+use crate::error::PsychroidError;
+use crate::moist_air::{
+    specific_enthalpy_from_humidity_ratio, specific_volume_from_humidity_ratio,
+};
+use crate::moist_air::{t_wet_bulb_from_humidity_ratio, ModelOptions, Property, TOLERANCE};
+use crate::saturated_water_vapor::{check_range_t_dry_bulb, t_dry_bulb_range};
+use crate::units;
+
+/// Check if the value is neither NaN nor infinite
+pub(crate) fn check_finite(name: &'static str, value: f64) -> Result<(), PsychroidError> {
+    if !value.is_finite() {
+        return Err(PsychroidError::NonFiniteInput { name, value });
+    }
+    Ok(())
+}
+
+/// Check if the total pressure is finite, then positive
+pub(crate) fn check_pressure(pressure: f64, unit: UnitSystem) -> Result<(), PsychroidError> {
+    check_finite("pressure", pressure)?;
+    if pressure <= 0.0 {
+        return Err(PsychroidError::InvalidPressure { pressure, unit });
+    }
+    Ok(())
+}
+
+/// Check if the relative humidity is within [0, 1]
+pub(crate) fn check_relative_humidity(relative_humidity: f64) -> Result<(), PsychroidError> {
+    check_finite("relative humidity", relative_humidity)?;
+    if !(0.0..=1.0 + TOLERANCE).contains(&relative_humidity) {
+        return Err(PsychroidError::InvalidRelativeHumidity(relative_humidity));
+    }
+    Ok(())
+}
+
+/// Check if the humidity ratio is non-negative and finite
+pub(crate) fn check_humidity_ratio(humidity_ratio: f64) -> Result<(), PsychroidError> {
+    check_finite("humidity ratio", humidity_ratio)?;
+    if humidity_ratio < 0.0 {
+        return Err(PsychroidError::InvalidHumidityRatio(humidity_ratio));
+    }
+    Ok(())
+}
+
+/// Check if the wet-bulb temperature is within the valid range and not above the dry-bulb temperature
+pub(crate) fn check_t_wet_bulb(
+    t_wet_bulb: f64,
+    t_dry_bulb: f64,
+    pressure: f64,
+    unit: UnitSystem,
+    options: &ModelOptions,
+) -> Result<(), PsychroidError> {
+    check_range_t_dry_bulb(t_wet_bulb, unit)?;
+    if t_wet_bulb > t_dry_bulb {
+        let error = t_wet_bulb_error(
+            units::t_to_si(t_wet_bulb, unit),
+            units::t_to_si(t_dry_bulb, unit),
+            units::pressure_to_si(pressure, unit),
+            options,
+        );
+        return Err(units::error_from_si(unit, options)(error));
+    }
+    Ok(())
+}
+
+/// Returns the error of an infeasible wet-bulb temperature,
+/// of which the valid range is bounded by the wet-bulb temperature of dry air and the dry-bulb temperature.
+/// The temperatures in °C and the pressure in Pa give the error in SI units.
+pub(crate) fn t_wet_bulb_error(
+    t_wet_bulb: f64,
+    t_dry_bulb: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> PsychroidError {
+    let min = t_wet_bulb_from_humidity_ratio(t_dry_bulb, 0.0, pressure, options)
+        .unwrap_or(t_dry_bulb_range(UnitSystem::SI).0);
+    PsychroidError::InvalidTWetBulb {
+        t_wet_bulb,
+        min,
+        max: t_dry_bulb,
+        unit: UnitSystem::SI,
+    }
+}
+
+/// Returns the error of a specific enthalpy lower than that of dry air at the dry-bulb temperature,
+/// in SI units like the arguments
+pub(crate) fn specific_enthalpy_error(
+    specific_enthalpy: f64,
+    t_dry_bulb: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> PsychroidError {
+    PsychroidError::InvalidSpecificEnthalpy {
+        specific_enthalpy,
+        min: specific_enthalpy_from_humidity_ratio(t_dry_bulb, 0.0, pressure, options),
+        unit: UnitSystem::SI,
+    }
+}
+
+/// Returns the error of a specific volume lower than that of dry air at the dry-bulb temperature,
+/// in SI units like the arguments
+pub(crate) fn specific_volume_error(
+    specific_volume: f64,
+    t_dry_bulb: f64,
+    pressure: f64,
+    options: &ModelOptions,
+) -> PsychroidError {
+    PsychroidError::InvalidSpecificVolume {
+        specific_volume,
+        min: specific_volume_from_humidity_ratio(t_dry_bulb, 0.0, pressure, options),
+        unit: UnitSystem::SI,
+    }
+}
+
+/// Check if the dew point temperature is within the valid range and not above the dry-bulb temperature
+pub(crate) fn check_t_dew_point(
+    t_dew_point: f64,
+    t_dry_bulb: f64,
+    unit: UnitSystem,
+) -> Result<(), PsychroidError> {
+    check_range_t_dry_bulb(t_dew_point, unit)?;
+    if t_dew_point > t_dry_bulb {
+        return Err(PsychroidError::InvalidTDewPoint {
+            t_dew_point,
+            t_dry_bulb,
+            unit,
+        });
+    }
+    Ok(())
+}
+
+/// Check the value of a property on its own, regardless of the other property of the pair
+pub(crate) fn check_property(property: Property, unit: UnitSystem) -> Result<(), PsychroidError> {
+    match property {
+        Property::TDryBulb(t) | Property::TWetBulb(t) | Property::TDewPoint(t) => {
+            check_range_t_dry_bulb(t, unit)
+        }
+        Property::HumidityRatio(humidity_ratio) => check_humidity_ratio(humidity_ratio),
+        Property::RelativeHumidity(relative_humidity) => check_relative_humidity(relative_humidity),
+        Property::SpecificEnthalpy(specific_enthalpy) => {
+            check_finite("specific enthalpy", specific_enthalpy)
+        }
+        Property::SpecificVolume(specific_volume) => {
+            check_finite("specific volume", specific_volume)?;
+            if specific_volume <= 0.0 {
+                return Err(PsychroidError::InvalidSpecificVolume {
+                    specific_volume,
+                    min: 0.0,
+                    unit,
+                });
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checks() {
+        let unit = UnitSystem::SI;
+        assert!(matches!(
+            check_pressure(f64::NAN, unit),
+            Err(PsychroidError::NonFiniteInput { .. })
+        ));
+        assert!(matches!(
+            check_pressure(0.0, unit),
+            Err(PsychroidError::InvalidPressure { .. })
+        ));
+        assert!(check_pressure(101325.0, unit).is_ok());
+        assert!(matches!(
+            check_relative_humidity(f64::INFINITY),
+            Err(PsychroidError::NonFiniteInput { .. })
+        ));
+        assert!(matches!(
+            check_humidity_ratio(-0.001),
+            Err(PsychroidError::InvalidHumidityRatio(_))
+        ));
+        assert!(matches!(
+            check_t_wet_bulb(25.0, 20.0, 101325.0, unit, &ModelOptions::default()),
+            Err(PsychroidError::InvalidTWetBulb { .. })
+        ));
+        assert!(matches!(
+            check_t_dew_point(25.0, 20.0, unit),
+            Err(PsychroidError::InvalidTDewPoint { .. })
+        ));
+        assert!(check_t_dew_point(20.0, 20.0, unit).is_ok());
+        assert!(matches!(
+            check_property(Property::TDewPoint(f64::NAN), unit),
+            Err(PsychroidError::NonFiniteInput { .. })
+        ));
+        assert!(matches!(
+            check_property(Property::SpecificVolume(-1.0), unit),
+            Err(PsychroidError::InvalidSpecificVolume { .. })
+        ));
+    }
+}
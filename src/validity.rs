@@ -0,0 +1,298 @@
+//! Validity of the computed properties with respect to the ranges of the correlations.
+//!
+//! The inputs are checked against [`t_dry_bulb_range`](crate::saturated_water_vapor::t_dry_bulb_range),
+//! which is that of the saturation pressure. The other correlations of [`ModelOptions`] are narrower,
+//! and a property which depends on one of them outside its range is extrapolated:
+//!
+//! | Correlation | Range |
+//! |---|---|
+//! | Saturation pressure over water, ASHRAE Handbook - Fundamentals (2017) Ch. 1 Eq. (6) | 0 to 190 °C, IAPWS-IF97 above 200 °C, blended in between |
+//! | Saturation pressure over ice, Eq. (5) | -100 to 0 °C |
+//! | Enhancement factor of [`GasModel::Real`], Greenspan (1976) | -100 to 100 °C |
+//! | Second virial coefficients of [`GasModel::Real`], Hyland and Wexler (1983) | -100 to 200 °C, up to 5 MPa |
+//! | Heat capacities of [`EnthalpyModel::VariableHeatCapacity`], NASA polynomials | 200 K to 1000 K |
+//!
+//! With [`SaturationReference::Water`], the saturation pressure is extrapolated below the freezing point
+//! to supercooled water. With [`SaturationReference::Ice`], the saturation and the energy balance of
+//! the wet-bulb temperature switch from water to ice at the freezing point, where the wet-bulb temperature
+//! jumps, so a property evaluated within [`NEAR_FREEZING_POINT`] of it is flagged as near the switch.
+//!
+//! A property is flagged only for the correlations it depends on, e.g. the specific enthalpy of
+//! unsaturated moist air does not depend on the saturation pressure. See [`MoistAir::validity`].
+//!
+//! [`EnthalpyModel::VariableHeatCapacity`]: crate::EnthalpyModel::VariableHeatCapacity
+//! [`SaturationReference::Water`]: crate::SaturationReference::Water
+//! [`SaturationReference::Ice`]: crate::SaturationReference::Ice
+//!
+//! # Example
+//! ```
+//! use psychroid::batch::Output;
+//! use psychroid::{MoistAir, UnitSystem, Validity};
+//!
+//! let air = MoistAir::from_t_dry_bulb_relative_humidity(0.5, 0.9, 101325.0, UnitSystem::SI).unwrap();
+//! assert_eq!(air.validity(Output::SpecificEnthalpy).unwrap(), Validity::InRange);
+//! // the wet-bulb temperature is slightly below the dry-bulb temperature, near the freezing point
+//! assert_eq!(air.validity(Output::TWetBulb).unwrap(), Validity::NearBranchSwitch);
+//! ```
+use crate::batch::Output;
+use crate::common::ZERO_CELSIUS_AS_KELVIN;
+use crate::error::PsychroidError;
+use crate::heat_capacity::EnthalpyModel;
+use crate::moist_air::{saturation_humidity_ratio, GasModel, ModelOptions, MoistAir};
+use crate::saturated_water_vapor::SaturationReference;
+use crate::units;
+
+/// Half width of the band around the freezing point, in \\(\\mathrm{K}\\),
+/// within which a property evaluated with [`SaturationReference::Ice`] is near the switch from water to ice
+pub const NEAR_FREEZING_POINT: f64 = 0.5;
+
+/// Validity of a computed property, in increasing order of concern
+///
+/// The validity of a property which depends on several correlations is the worst of them,
+/// e.g. by [`Ord::max`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Validity {
+    /// Within the ranges of all the correlations
+    InRange,
+    /// Within the ranges, but close to the switch between the branches of a correlation,
+    /// where the property may change discontinuously with the inputs
+    NearBranchSwitch,
+    /// Outside the range of a correlation, i.e. the value is extrapolated
+    Extrapolated,
+}
+
+/// Upper limit of the enhancement factor of Greenspan (1976), in °C
+const MAX_T_ENHANCEMENT_FACTOR: f64 = 100.0;
+/// Upper limit of the second virial coefficients of Hyland and Wexler (1983), in °C
+const MAX_T_VIRIAL: f64 = 200.0;
+/// Upper limit of the pressure of the second virial coefficients, in Pa
+const MAX_PRESSURE_VIRIAL: f64 = 5.0E+06;
+/// Lower limit of the NASA polynomials of the heat capacities, in K
+const MIN_T_HEAT_CAPACITY: f64 = 200.0;
+
+/// Validity of the correlations at a state, with the pressure in Pa and the temperatures in °C
+struct Correlations {
+    pressure: f64,
+    options: ModelOptions,
+}
+
+impl Correlations {
+    /// Validity of the saturation over water or ice, including the enhancement factor of the real gas model
+    fn saturation(&self, t_celsius: f64) -> Validity {
+        let validity = match self.options.saturation_reference {
+            SaturationReference::Water if t_celsius < 0.0 => Validity::Extrapolated,
+            SaturationReference::Ice if t_celsius.abs() < NEAR_FREEZING_POINT => {
+                Validity::NearBranchSwitch
+            }
+            _ => Validity::InRange,
+        };
+        match self.options.gas_model {
+            GasModel::Real if t_celsius > MAX_T_ENHANCEMENT_FACTOR => Validity::Extrapolated,
+            _ => validity,
+        }
+    }
+
+    /// Validity of the second virial coefficients of the real gas model
+    fn gas(&self, t_celsius: f64) -> Validity {
+        match self.options.gas_model {
+            GasModel::Real if t_celsius > MAX_T_VIRIAL || self.pressure > MAX_PRESSURE_VIRIAL => {
+                Validity::Extrapolated
+            }
+            _ => Validity::InRange,
+        }
+    }
+
+    /// Validity of the heat capacities of the enthalpy model
+    fn enthalpy(&self, t_celsius: f64) -> Validity {
+        match self.options.enthalpy_model {
+            EnthalpyModel::VariableHeatCapacity
+                if t_celsius + ZERO_CELSIUS_AS_KELVIN < MIN_T_HEAT_CAPACITY =>
+            {
+                Validity::Extrapolated
+            }
+            _ => Validity::InRange,
+        }
+    }
+}
+
+/// Returns the validity of the property of the state, from the correlations it depends on
+pub(crate) fn validity(moist_air: &MoistAir, output: Output) -> Result<Validity, PsychroidError> {
+    let unit = moist_air.unit();
+    let options = moist_air.options();
+    let t_celsius = |t: f64| units::t_to_si(t, unit);
+    let correlations = Correlations {
+        pressure: units::pressure_to_si(moist_air.pressure(), unit),
+        options,
+    };
+    let t_dry_bulb = t_celsius(moist_air.t_dry_bulb());
+    // the properties other than the relative humidity depend on the saturation only if saturated,
+    // where the humidity ratio of the water vapor is limited to that of saturation
+    let saturation = match moist_air.humidity_ratio()
+        >= saturation_humidity_ratio(t_dry_bulb, correlations.pressure, &options)
+    {
+        true => correlations.saturation(t_dry_bulb),
+        false => Validity::InRange,
+    };
+    let validity = match output {
+        Output::TDryBulb | Output::HumidityRatio => Validity::InRange,
+        Output::RelativeHumidity | Output::DegreeOfSaturation => {
+            correlations.saturation(t_dry_bulb)
+        }
+        Output::TWetBulb => {
+            let t_wet_bulb = t_celsius(moist_air.t_wet_bulb()?);
+            correlations
+                .saturation(t_wet_bulb)
+                .max(correlations.enthalpy(t_dry_bulb))
+                .max(correlations.enthalpy(t_wet_bulb))
+        }
+        Output::TDewPoint => match moist_air.t_dew_point()? {
+            // the dew point of dry air is not defined, and is not evaluated
+            t_dew_point if t_dew_point.is_nan() => Validity::InRange,
+            t_dew_point => correlations.saturation(t_celsius(t_dew_point)),
+        },
+        Output::SpecificEnthalpy | Output::SpecificHeat => saturation
+            .max(correlations.gas(t_dry_bulb))
+            .max(correlations.enthalpy(t_dry_bulb)),
+        Output::SpecificVolume | Output::Density => saturation.max(correlations.gas(t_dry_bulb)),
+        Output::PartialWaterVaporPressure => saturation,
+    };
+    Ok(validity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::UnitSystem;
+    use crate::moist_air::{Property, PropertyPair};
+
+    const OUTPUTS: [Output; 11] = [
+        Output::TDryBulb,
+        Output::HumidityRatio,
+        Output::RelativeHumidity,
+        Output::TWetBulb,
+        Output::TDewPoint,
+        Output::SpecificEnthalpy,
+        Output::SpecificVolume,
+        Output::Density,
+        Output::PartialWaterVaporPressure,
+        Output::DegreeOfSaturation,
+        Output::SpecificHeat,
+    ];
+
+    fn state(
+        t_dry_bulb: f64,
+        relative_humidity: f64,
+        pressure: f64,
+        unit: UnitSystem,
+        options: ModelOptions,
+    ) -> MoistAir {
+        let pair = PropertyPair(
+            Property::TDryBulb(t_dry_bulb),
+            Property::RelativeHumidity(relative_humidity),
+        );
+        MoistAir::from_pair_with_options(pair, pressure, unit, options).unwrap()
+    }
+
+    #[test]
+    fn test_in_range() {
+        for (unit, t_dry_bulb, pressure) in [
+            (UnitSystem::SI, 25.0, 101325.0),
+            (UnitSystem::IP, 77.0, 14.696),
+        ] {
+            let air = state(t_dry_bulb, 0.5, pressure, unit, ModelOptions::default());
+            for output in OUTPUTS {
+                assert_eq!(air.validity(output), Ok(Validity::InRange));
+            }
+        }
+    }
+
+    #[test]
+    fn test_saturation_reference() {
+        let unit = UnitSystem::SI;
+        let water = ModelOptions {
+            saturation_reference: SaturationReference::Water,
+            ..Default::default()
+        };
+        // supercooled water is out of the range of the saturation pressure over water
+        let air = state(-10.0, 0.5, 101325.0, unit, water);
+        assert_eq!(
+            air.validity(Output::RelativeHumidity),
+            Ok(Validity::Extrapolated)
+        );
+        assert_eq!(air.validity(Output::TDewPoint), Ok(Validity::Extrapolated));
+        assert_eq!(
+            air.validity(Output::SpecificEnthalpy),
+            Ok(Validity::InRange)
+        );
+        let air = state(-10.0, 0.5, 101325.0, unit, ModelOptions::default());
+        assert_eq!(
+            air.validity(Output::RelativeHumidity),
+            Ok(Validity::InRange)
+        );
+
+        // near the freezing point over ice, in both unit systems
+        let air = state(-0.2, 0.5, 101325.0, unit, ModelOptions::default());
+        assert_eq!(
+            air.validity(Output::RelativeHumidity),
+            Ok(Validity::NearBranchSwitch)
+        );
+        assert_eq!(air.validity(Output::TDryBulb), Ok(Validity::InRange));
+        // the wet-bulb temperature is away from the freezing point
+        assert_eq!(air.validity(Output::TWetBulb), Ok(Validity::InRange));
+        let air = state(31.5, 0.5, 14.696, UnitSystem::IP, ModelOptions::default());
+        assert_eq!(
+            air.validity(Output::DegreeOfSaturation),
+            Ok(Validity::NearBranchSwitch)
+        );
+
+        // saturated moist air depends on the saturation, unsaturated does not
+        let air = state(0.2, 1.0, 101325.0, unit, ModelOptions::default());
+        assert_eq!(
+            air.validity(Output::SpecificVolume),
+            Ok(Validity::NearBranchSwitch)
+        );
+        let air = state(0.2, 0.9, 101325.0, unit, ModelOptions::default());
+        assert_eq!(air.validity(Output::SpecificVolume), Ok(Validity::InRange));
+    }
+
+    #[test]
+    fn test_model_options() {
+        let unit = UnitSystem::SI;
+        let variable = ModelOptions {
+            enthalpy_model: EnthalpyModel::VariableHeatCapacity,
+            ..Default::default()
+        };
+        let air = state(-80.0, 0.5, 101325.0, unit, variable);
+        assert_eq!(
+            air.validity(Output::SpecificEnthalpy),
+            Ok(Validity::Extrapolated)
+        );
+        assert_eq!(air.validity(Output::TWetBulb), Ok(Validity::Extrapolated));
+        assert_eq!(
+            air.validity(Output::RelativeHumidity),
+            Ok(Validity::InRange)
+        );
+
+        let real = ModelOptions {
+            gas_model: GasModel::Real,
+            ..Default::default()
+        };
+        let air = state(150.0, 0.1, 1.0E+06, unit, real);
+        assert_eq!(
+            air.validity(Output::RelativeHumidity),
+            Ok(Validity::Extrapolated)
+        );
+        assert_eq!(air.validity(Output::SpecificVolume), Ok(Validity::InRange));
+        let air = state(250.0, 0.01, 1.0E+06, unit, real);
+        assert_eq!(
+            air.validity(Output::SpecificVolume),
+            Ok(Validity::Extrapolated)
+        );
+        let air = state(25.0, 0.5, 6.0E+06, unit, real);
+        assert_eq!(air.validity(Output::Density), Ok(Validity::Extrapolated));
+        assert_eq!(air.validity(Output::HumidityRatio), Ok(Validity::InRange));
+        // the worst of the correlations
+        assert!(Validity::Extrapolated > Validity::NearBranchSwitch);
+        assert!(Validity::NearBranchSwitch > Validity::InRange);
+    }
+}
@@ -5,7 +5,7 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsError;
 
 // For building the WebAssembly module, run:
-// wasm-pack build --target web --out-dir ../psychroid-web/src/lib/
+// wasm-pack build --target web --out-dir ../psychroid-web/src/lib/ -- --features wasm
 
 #[wasm_bindgen]
 pub struct WasmPoint {
@@ -122,17 +122,21 @@ pub fn specificEnthalpyLine(
     // Generate data points for constant specific enthalpy line
     // Since the line is assumed to be linear, we only generate start and end points
     let t_array: [f64; 2] = [t_start, t_end];
-    let point_array: Vec<WasmPoint> = t_array
+    // the end point on the dry air line is reused, as the round-off of the humidity ratio may be negative
+    let point_array: Result<Vec<WasmPoint>, PsychroidError> = t_array
         .iter()
         .map(|&t_dry_bulb| {
-            let moist_air = MoistAir::from_t_dry_bulb_enthalpy(t_dry_bulb, h, pressure, unit);
-            WasmPoint {
+            let moist_air = match t_dry_bulb == t_dry_bulb_rh0 {
+                true => moist_air_rh0,
+                false => MoistAir::from_t_dry_bulb_enthalpy(t_dry_bulb, h, pressure, unit)?,
+            };
+            Ok(WasmPoint {
                 x: t_dry_bulb,
                 y: moist_air.humidity_ratio(),
-            }
+            })
         })
         .collect();
-    Ok(point_array)
+    point_array.map_err(to_js_error)
 }
 
 /// A WASM-friendly wrapper around the MoistAir struct.
@@ -208,15 +212,16 @@ impl WasmMoistAir {
         specific_enthalpy: f64,
         pressure: f64,
         is_si: bool,
-    ) -> WasmMoistAir {
+    ) -> Result<WasmMoistAir, JsError> {
         let unit = if is_si {
             UnitSystem::SI
         } else {
             UnitSystem::IP
         };
-        let inner =
-            MoistAir::from_t_dry_bulb_enthalpy(t_dry_bulb, specific_enthalpy, pressure, unit);
-        WasmMoistAir { inner }
+        match MoistAir::from_t_dry_bulb_enthalpy(t_dry_bulb, specific_enthalpy, pressure, unit) {
+            Ok(inner) => Ok(WasmMoistAir { inner }),
+            Err(e) => Err(to_js_error(e)),
+        }
     }
 
     /// Returns the current wet-bulb temperature.
@@ -320,15 +325,21 @@ impl WasmMoistAir {
     /// Heating process
     #[wasm_bindgen]
     #[allow(non_snake_case)]
-    pub fn heatingPower(&mut self, mda: f64, power: f64) {
-        self.inner.heating_q(mda, power);
+    pub fn heatingPower(&mut self, mda: f64, power: f64) -> Result<(), JsError> {
+        match self.inner.heating_q(mda, power) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(to_js_error(e)),
+        }
     }
 
     /// Heating process
     #[wasm_bindgen]
     #[allow(non_snake_case)]
-    pub fn heatingDeltaTemperature(&mut self, mda: f64, dt: f64) -> f64 {
-        self.inner.heating_dt(mda, dt)
+    pub fn heatingDeltaTemperature(&mut self, mda: f64, dt: f64) -> Result<f64, JsError> {
+        match self.inner.heating_dt(mda, dt) {
+            Ok(v) => Ok(v),
+            Err(e) => Err(to_js_error(e)),
+        }
     }
 
     /// Cooling process